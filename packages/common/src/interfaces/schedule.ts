@@ -73,6 +73,8 @@ export interface CronSchedule {
 export interface BaseFrequency {
   type: FrequencyType
   until?: Date | null
+  /** The number of occurrences, counting the anchor, after which the frequency stops repeating */
+  count?: number | null
 }
 
 export type RegularFrequency =
@@ -103,6 +105,18 @@ export interface BaseTaskSchedule<F extends FrequencyAggregateType = FrequencyAg
   taskId: string
   timing: Timing
   frequency: Frequency<F>
+  /**
+   * How long before each occurrence to remind of it, in milliseconds
+   */
+  reminders?: number[]
+  /**
+   * The times the schedule does not occur at, e.g. the occurrences skipped
+   */
+  exclusions?: Date[]
+  /**
+   * The times the schedule occurs at besides the occurrences of its frequency
+   */
+  inclusions?: Date[]
 }
 
 export interface TaskSchedule<F extends FrequencyAggregateType = FrequencyAggregateType>
@@ -0,0 +1,755 @@
+use core::cell::RefCell;
+use core::fmt;
+use core::str::FromStr;
+use std::collections::HashMap;
+
+use chrono::prelude::*;
+use chrono::TimeDelta;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::core::errors::CalendarError;
+use crate::core::frequency::{StConstWeekday, StMonth, StOrdinals};
+
+/// The most days an occurrence is rolled by to reach a business day, beyond which it is skipped instead
+pub(crate) const ROLL_LIMIT_DAYS: i64 = 14;
+
+/// The format of the dates of holidays
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// The names of the weekdays in rules, indexed by weekday from Sunday
+const WEEKDAY_NAMES: [&str; 7] = [
+    "sunday",
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+];
+
+/// The names of the months in rules, indexed by month from January
+const MONTH_NAMES: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+/// The names of the ordinals in rules, indexed by the value of the ordinal
+const ORDINAL_NAMES: [&str; 6] = ["first", "second", "third", "fourth", "fifth", "last"];
+
+/// The days that are not business days, that is the weekend and the holidays, which a schedule's occurrences are
+/// rolled off of.
+///
+/// Besides holidays given as dates, a calendar can hold rules for the holidays that recur every year, which are
+/// usually read from a file with one rule per line, e.g.
+///
+/// ```text
+/// # The weekend, Saturday and Sunday unless stated
+/// weekend: sat, sun
+/// # A one-off holiday
+/// 2026-06-12
+/// # The same day every year, observed on the next business day when it falls on the weekend
+/// 01-01 observed
+/// 12-25
+/// # The nth weekday of a month every year
+/// last mon of may
+/// first monday of august
+/// # A number of days from Easter Sunday every year
+/// easter-2
+/// easter+1
+/// ```
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StHolidayCalendar {
+    /// The weekdays that are not business days, sorted
+    weekend: Vec<StConstWeekday>,
+    /// The holidays given as dates, sorted
+    dates: Vec<NaiveDate>,
+    /// The rules of the holidays that recur every year
+    rules: Vec<HolidayRule>,
+    /// The holidays of the rules by year, as they are read for every day an occurrence is rolled over
+    #[cfg_attr(feature = "serde", serde(skip))]
+    holidays: HolidayCache,
+}
+
+/// The holidays of the rules of a calendar by year, which is emptied whenever the calendar changes
+#[derive(Default, Clone)]
+struct HolidayCache(RefCell<HashMap<i32, Vec<NaiveDate>>>);
+
+impl HolidayCache {
+    fn clear(&mut self) {
+        self.0.get_mut().clear();
+    }
+}
+
+impl PartialEq for HolidayCache {
+    /// Calendars are the same regardless of the years they have read holidays in
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for HolidayCache {}
+
+impl fmt::Debug for HolidayCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("HolidayCache")
+    }
+}
+
+/// A rule that a holiday recurs by every year
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum HolidayRule {
+    /// The same day of the same month every year, e.g. the 25th of December, which is observed on the next
+    /// business day instead when it falls on the weekend and `observed` is set
+    Fixed {
+        month: StMonth,
+        day: u32,
+        observed: bool,
+    },
+    /// The nth weekday of the same month every year, e.g. the last Monday of May
+    Nth {
+        ordinal: StOrdinals,
+        weekday: StConstWeekday,
+        month: StMonth,
+    },
+    /// A number of days from Easter Sunday every year, e.g. -2 for Good Friday
+    Easter { offset: i32 },
+}
+
+/// What happens to an occurrence of a schedule that falls on a weekend or holiday
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum StRollConvention {
+    /// Moved to the next business day
+    #[default]
+    Following,
+    /// Moved to the next business day, unless it is in the next month, in which case it is moved to the previous
+    /// business day instead
+    ModifiedFollowing,
+    /// Moved to the previous business day, e.g. pay on the 25th, or the previous business day
+    Preceding,
+    /// Skipped, so the schedule does not occur until its next occurrence on a business day
+    Skip,
+}
+
+/// The business days a schedule occurs on, along with what happens to its occurrences that fall on another day
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BusinessDays {
+    /// The calendar of the days that are not business days
+    calendar: StHolidayCalendar,
+    /// What happens to the occurrences that do not fall on a business day
+    roll: StRollConvention,
+}
+
+#[wasm_bindgen]
+impl StHolidayCalendar {
+    /// Initializes a calendar with no holidays, whose weekend is Saturday and Sunday
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> StHolidayCalendar {
+        StHolidayCalendar {
+            weekend: vec![StConstWeekday::Sun, StConstWeekday::Sat],
+            dates: vec![],
+            rules: vec![],
+            holidays: HolidayCache::default(),
+        }
+    }
+
+    /// Reads a calendar from the rules of its holidays, one per line, e.g. the contents of a file of rules.
+    ///
+    /// Throws when a line is not a rule, naming the line.
+    pub fn from_rules(rules: &str) -> Result<StHolidayCalendar, CalendarError> {
+        rules.parse()
+    }
+
+    /// Returns the weekdays that are not business days, from 0-6 starting on Sunday
+    pub fn get_weekend(&self) -> Vec<u32> {
+        self.weekend
+            .iter()
+            .map(|weekday| u32::from(*weekday))
+            .collect()
+    }
+
+    /// Sets the weekdays that are not business days, from 0-6 starting on Sunday, e.g. `[5, 6]` for Friday and
+    /// Saturday.
+    ///
+    /// Throws when a weekday is out of range, or when every day of the week would be on the weekend.
+    pub fn set_weekend(&mut self, weekdays: Vec<u32>) -> Result<(), CalendarError> {
+        if weekdays.iter().any(|weekday| *weekday > 6) {
+            return Err(CalendarError::InvalidWeekend(
+                "weekdays should be between 0 (Sunday) and 6 (Saturday)",
+            ));
+        }
+
+        let mut weekend = weekdays
+            .into_iter()
+            .map(StConstWeekday::from)
+            .collect::<Vec<_>>();
+        weekend.sort();
+        weekend.dedup();
+
+        if weekend.len() == 7 {
+            return Err(CalendarError::InvalidWeekend(
+                "at least one day of the week should be a business day",
+            ));
+        }
+
+        self.weekend = weekend;
+        self.holidays.clear();
+        Ok(())
+    }
+
+    /// Returns the holidays given as dates, in the format `YYYY-MM-DD`, earliest first
+    pub fn get_holidays(&self) -> Vec<String> {
+        self.dates
+            .iter()
+            .map(|date| date.format(DATE_FORMAT).to_string())
+            .collect()
+    }
+
+    /// Returns the holidays in the given year, both the ones given as dates and the ones of the rules, in the
+    /// format `YYYY-MM-DD`, earliest first
+    pub fn get_holidays_of_year(&self, year: i32) -> Vec<String> {
+        // A holiday observed on the next business day can be moved past the end of its year
+        let mut dates = self.holidays_of_year(year - 1);
+        dates.extend(self.holidays_of_year(year));
+        dates.extend(self.dates.iter().filter(|date| date.year() == year));
+        dates.retain(|date| date.year() == year);
+        dates.sort();
+        dates.dedup();
+        dates
+            .iter()
+            .map(|date| date.format(DATE_FORMAT).to_string())
+            .collect()
+    }
+
+    /// Adds the given date, in the format `YYYY-MM-DD`, to the holidays.
+    ///
+    /// Returns `false` when the date is already a holiday given as a date.
+    pub fn add_holiday(&mut self, date: &str) -> Result<bool, CalendarError> {
+        Ok(self.add_holiday_date(parse_date(date)?))
+    }
+
+    /// Removes the given date, in the format `YYYY-MM-DD`, from the holidays given as dates.
+    ///
+    /// Returns `false` when the date is not one of them.
+    pub fn remove_holiday(&mut self, date: &str) -> Result<bool, CalendarError> {
+        Ok(self.remove_holiday_date(parse_date(date)?))
+    }
+
+    /// Checks if the given date, in the format `YYYY-MM-DD`, is a business day, that is neither on the weekend nor
+    /// a holiday
+    pub fn is_business_day(&self, date: &str) -> Result<bool, CalendarError> {
+        Ok(self.is_business_date(parse_date(date)?))
+    }
+}
+
+impl StHolidayCalendar {
+    /// Reads a calendar from the file of rules at the given path, see [`StHolidayCalendar::from_rules`]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<StHolidayCalendar, CalendarError> {
+        std::fs::read_to_string(path)
+            .map_err(|err| CalendarError::Unreadable(err.to_string()))?
+            .parse()
+    }
+
+    /// Returns the holidays given as dates, earliest first
+    pub fn get_dates(&self) -> &[NaiveDate] {
+        &self.dates
+    }
+
+    /// Returns the rules of the holidays that recur every year
+    pub fn get_rules(&self) -> &[HolidayRule] {
+        &self.rules
+    }
+
+    /// Adds the given date to the holidays, returning `false` when it is already there
+    pub fn add_holiday_date(&mut self, date: NaiveDate) -> bool {
+        match self.dates.binary_search(&date) {
+            Ok(_) => false,
+            Err(position) => {
+                self.dates.insert(position, date);
+                self.holidays.clear();
+                true
+            }
+        }
+    }
+
+    /// Removes the given date from the holidays given as dates, returning whether it was there
+    pub fn remove_holiday_date(&mut self, date: NaiveDate) -> bool {
+        match self.dates.binary_search(&date) {
+            Ok(position) => {
+                self.dates.remove(position);
+                self.holidays.clear();
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Adds a rule for a holiday that recurs every year
+    pub fn add_rule(&mut self, rule: HolidayRule) {
+        self.rules.push(rule);
+        self.holidays.clear();
+    }
+
+    /// Checks if the given date falls on the weekend
+    pub fn is_weekend(&self, date: NaiveDate) -> bool {
+        self.weekend.contains(&date.weekday().into())
+    }
+
+    /// Checks if the given date is a holiday, either one given as a date or one of the rules
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        // A holiday observed on the next business day can be moved past the end of its year
+        if self.dates.binary_search(&date).is_ok() {
+            return true;
+        }
+
+        let mut holidays = self.holidays.0.borrow_mut();
+        (date.year() - 1..=date.year()).any(|year| {
+            holidays
+                .entry(year)
+                .or_insert_with(|| self.holidays_of_year(year))
+                .contains(&date)
+        })
+    }
+
+    /// Checks if the given date is a business day, that is neither on the weekend nor a holiday
+    pub fn is_business_date(&self, date: NaiveDate) -> bool {
+        !self.is_weekend(date) && !self.is_holiday(date)
+    }
+
+    /// Returns the business day the given date is rolled to by the given convention, the date itself when it is
+    /// one, or `None` when it is skipped, which is also the case when there is no business day within
+    /// two weeks of it.
+    pub fn roll(&self, date: NaiveDate, convention: StRollConvention) -> Option<NaiveDate> {
+        if self.is_business_date(date) {
+            return Some(date);
+        }
+
+        match convention {
+            StRollConvention::Following => self.business_date_from(date, 1),
+            StRollConvention::Preceding => self.business_date_from(date, -1),
+            StRollConvention::ModifiedFollowing => self
+                .business_date_from(date, 1)
+                .filter(|following| following.month() == date.month())
+                .or_else(|| self.business_date_from(date, -1)),
+            StRollConvention::Skip => None,
+        }
+    }
+
+    /// Returns the first business day after the given date going forwards, or backwards for a negative direction
+    fn business_date_from(&self, date: NaiveDate, direction: i64) -> Option<NaiveDate> {
+        (1..=ROLL_LIMIT_DAYS)
+            .filter_map(|days| date.checked_add_signed(TimeDelta::days(days * direction)))
+            .find(|date| self.is_business_date(*date))
+    }
+
+    /// Returns the holidays of the rules in the given year, where an observed holiday is moved off the weekend to
+    /// the next day that is not already a holiday, unless there is none within two weeks of it
+    fn holidays_of_year(&self, year: i32) -> Vec<NaiveDate> {
+        let (observed, fixed): (Vec<&HolidayRule>, Vec<_>) = self
+            .rules
+            .iter()
+            .partition(|rule| matches!(rule, HolidayRule::Fixed { observed: true, .. }));
+        let mut holidays = fixed
+            .into_iter()
+            .filter_map(|rule| rule.date_in(year))
+            .collect::<Vec<_>>();
+        let mut observed = observed
+            .into_iter()
+            .filter_map(|rule| rule.date_in(year))
+            .collect::<Vec<_>>();
+        observed.sort();
+
+        for date in observed {
+            let mut day = date;
+
+            if self.is_weekend(day) {
+                let free = (1..=ROLL_LIMIT_DAYS)
+                    .filter_map(|days| date.checked_add_signed(TimeDelta::days(days)))
+                    .find(|day| {
+                        !self.is_weekend(*day)
+                            && !holidays.contains(day)
+                            && self.dates.binary_search(day).is_err()
+                    });
+                day = free.unwrap_or(date);
+            }
+
+            holidays.push(day);
+        }
+
+        holidays
+    }
+}
+
+impl Default for StHolidayCalendar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FromStr for StHolidayCalendar {
+    type Err = CalendarError;
+
+    fn from_str(rules: &str) -> Result<Self, Self::Err> {
+        let mut calendar = StHolidayCalendar::new();
+
+        for (number, line) in rules.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default();
+            let line = line.trim().to_ascii_lowercase();
+            let malformed = |reason: String| CalendarError::Malformed(number + 1, reason);
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(weekdays) = line.strip_prefix("weekend:") {
+                let weekdays = weekdays
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(|name| {
+                        find_name(&WEEKDAY_NAMES, name)
+                            .ok_or_else(|| malformed(format!("unknown weekday '{}'", name)))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                calendar.set_weekend(weekdays).map_err(|err| match err {
+                    CalendarError::InvalidWeekend(reason) => malformed(String::from(reason)),
+                    err => err,
+                })?;
+                continue;
+            }
+
+            if let Ok(date) = NaiveDate::parse_from_str(&line, DATE_FORMAT) {
+                calendar.add_holiday_date(date);
+                continue;
+            }
+
+            let rule = parse_rule(&line).map_err(malformed)?;
+            calendar.add_rule(rule);
+        }
+
+        Ok(calendar)
+    }
+}
+
+impl HolidayRule {
+    /// Returns the date of the holiday in the given year, if it falls in it, e.g. not for the 29th of February of a
+    /// common year, before it is observed on another day
+    pub fn date_in(&self, year: i32) -> Option<NaiveDate> {
+        match *self {
+            HolidayRule::Fixed { month, day, .. } => {
+                NaiveDate::from_ymd_opt(year, month.to_value() + 1, day)
+            }
+            HolidayRule::Nth {
+                ordinal: StOrdinals::Last,
+                weekday,
+                month,
+            } => {
+                let first_of_next = match month {
+                    StMonth::Dec => NaiveDate::from_ymd_opt(year + 1, 1, 1),
+                    month => NaiveDate::from_ymd_opt(year, month.to_value() + 2, 1),
+                }?;
+                let last = first_of_next.pred_opt()?;
+                let back = (7 + last.weekday().num_days_from_sunday() - u32::from(weekday)) % 7;
+                last.checked_sub_signed(TimeDelta::days(back as i64))
+            }
+            HolidayRule::Nth {
+                ordinal,
+                weekday,
+                month,
+            } => NaiveDate::from_weekday_of_month_opt(
+                year,
+                month.to_value() + 1,
+                weekday.into(),
+                ordinal.to_value() as u8 + 1,
+            ),
+            HolidayRule::Easter { offset } => {
+                easter_sunday(year)?.checked_add_signed(TimeDelta::days(offset as i64))
+            }
+        }
+    }
+}
+
+impl BusinessDays {
+    pub fn new(calendar: StHolidayCalendar, roll: StRollConvention) -> Self {
+        BusinessDays { calendar, roll }
+    }
+
+    pub fn get_calendar(&self) -> &StHolidayCalendar {
+        &self.calendar
+    }
+
+    pub fn get_roll(&self) -> StRollConvention {
+        self.roll
+    }
+
+    /// Returns the business day the given date is rolled to, see [`StHolidayCalendar::roll`]
+    pub fn roll_date(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.calendar.roll(date, self.roll)
+    }
+}
+
+/// Reads a date in the format `YYYY-MM-DD`
+fn parse_date(date: &str) -> Result<NaiveDate, CalendarError> {
+    NaiveDate::parse_from_str(date.trim(), DATE_FORMAT)
+        .map_err(|_| CalendarError::InvalidDate(date.to_string()))
+}
+
+/// Reads a rule of a holiday that recurs every year from a line in lowercase, returning the reason it is not one
+/// otherwise
+fn parse_rule(line: &str) -> Result<HolidayRule, String> {
+    if let Some(offset) = line.strip_prefix("easter") {
+        let offset = offset.replace(' ', "");
+        let offset = offset.strip_prefix('+').unwrap_or(&offset);
+        return match offset {
+            "" => Ok(HolidayRule::Easter { offset: 0 }),
+            offset => offset
+                .parse()
+                .map(|offset| HolidayRule::Easter { offset })
+                .map_err(|_| format!("invalid offset from easter '{}'", offset)),
+        };
+    }
+
+    let words = line.split_whitespace().collect::<Vec<_>>();
+
+    match words.as_slice() {
+        [ordinal, weekday, "of", month] => Ok(HolidayRule::Nth {
+            ordinal: ORDINAL_NAMES
+                .iter()
+                .position(|name| name == ordinal)
+                .map(|value| StOrdinals::from_value(&(value as u32)))
+                .ok_or_else(|| format!("unknown ordinal '{}'", ordinal))?,
+            weekday: find_name(&WEEKDAY_NAMES, weekday)
+                .map(StConstWeekday::from)
+                .ok_or_else(|| format!("unknown weekday '{}'", weekday))?,
+            month: find_name(&MONTH_NAMES, month)
+                .map(StMonth::from)
+                .ok_or_else(|| format!("unknown month '{}'", month))?,
+        }),
+        [day] | [day, "observed"] => {
+            let (month, day_of_month) = day
+                .split_once('-')
+                .and_then(|(month, day)| Some((month.parse().ok()?, day.parse().ok()?)))
+                // A leap year, so the 29th of February is a valid day
+                .filter(|(month, day)| NaiveDate::from_ymd_opt(2000, *month, *day).is_some())
+                .ok_or_else(|| format!("invalid date '{}'", day))?;
+
+            Ok(HolidayRule::Fixed {
+                month: StMonth::from_value(month - 1),
+                day: day_of_month,
+                observed: words.len() == 2,
+            })
+        }
+        _ => Err(format!("unknown rule '{}'", line)),
+    }
+}
+
+/// Returns the index of the given name among the names, which can also be shortened to its first three letters
+fn find_name(names: &[&str], name: &str) -> Option<u32> {
+    names
+        .iter()
+        .position(|full| *full == name || (name.len() == 3 && full.starts_with(name)))
+        .map(|index| index as u32)
+}
+
+/// Returns the date of Easter Sunday in the given year of the Gregorian calendar
+fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year.rem_euclid(19);
+    let (b, c) = (year.div_euclid(100), year.rem_euclid(100));
+    let (d, e) = (b.div_euclid(4), b.rem_euclid(4));
+    let f = (b + 8).div_euclid(25);
+    let g = (b - f + 1).div_euclid(3);
+    let h = (19 * a + b - d - g + 15).rem_euclid(30);
+    let (i, k) = (c.div_euclid(4), c.rem_euclid(4));
+    let l = (32 + 2 * e + 2 * i - h - k).rem_euclid(7);
+    let m = (a + 11 * h + 22 * l).div_euclid(451);
+    let month = (h + l - 7 * m + 114).div_euclid(31);
+    let day = (h + l - 7 * m + 114).rem_euclid(31) + 1;
+
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    /// Should read the rules of a calendar and tell its holidays, observing the ones on the weekend on the next
+    /// business day
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_read_rules() {
+        let calendar = StHolidayCalendar::from_rules(
+            "# England and Wales\n\
+             weekend: Sat, Sun\n\
+             2026-06-12\n\
+             12-25 observed\n\
+             12-26 observed   # Boxing Day\n\
+             last mon of may\n\
+             first Monday of august\n\
+             easter-2\n\
+             easter + 1\n",
+        )
+        .unwrap();
+
+        assert_eq!(calendar.get_weekend(), vec![0, 6]);
+        assert_eq!(calendar.get_holidays(), vec!["2026-06-12"]);
+        assert_eq!(calendar.get_rules().len(), 6);
+        assert_eq!(
+            calendar.get_holidays_of_year(2027),
+            vec![
+                "2027-03-26",
+                "2027-03-29",
+                "2027-05-31",
+                "2027-08-02",
+                "2027-12-27",
+                "2027-12-28",
+            ]
+        );
+
+        // Christmas falls on a Saturday and Boxing Day on a Sunday in 2027
+        assert!(calendar.is_holiday(date(2027, 12, 27)));
+        assert!(calendar.is_holiday(date(2027, 12, 28)));
+        assert!(!calendar.is_holiday(date(2027, 12, 29)));
+        assert!(calendar.is_business_day("2026-06-11").unwrap());
+        assert!(!calendar.is_business_day("2026-06-12").unwrap());
+        assert!(!calendar.is_business_day("2026-06-13").unwrap());
+    }
+
+    /// Should name the line of a rule that cannot be read
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_read_malformed_rules() {
+        let cases = [
+            ("weekend: sat, sunny", "unknown weekday 'sunny'"),
+            ("02-30", "invalid date '02-30'"),
+            ("fifth fri of smarch", "unknown month 'smarch'"),
+            ("easter+one", "invalid offset from easter 'one'"),
+            ("every day", "unknown rule 'every day'"),
+            (
+                "weekend: sun, mon, tue, wed, thu, fri, sat",
+                "at least one day of the week should be a business day",
+            ),
+        ];
+
+        for (rule, reason) in cases {
+            let rules = format!("# comment\n\n{}", rule);
+            assert_eq!(
+                StHolidayCalendar::from_rules(&rules),
+                Err(CalendarError::Malformed(3, reason.to_string())),
+                "{}",
+                rule
+            );
+        }
+
+        let mut calendar = StHolidayCalendar::new();
+        assert_eq!(
+            calendar.add_holiday("25/12/2026"),
+            Err(CalendarError::InvalidDate("25/12/2026".to_string()))
+        );
+        assert_eq!(calendar.add_holiday("2026-12-25"), Ok(true));
+        assert_eq!(calendar.add_holiday("2026-12-25"), Ok(false));
+        assert_eq!(calendar.remove_holiday("2026-12-25"), Ok(true));
+        assert_eq!(calendar.remove_holiday("2026-12-25"), Ok(false));
+    }
+
+    /// Should roll a weekend or holiday to a business day by each convention
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_roll_conventions() {
+        let mut calendar = StHolidayCalendar::new();
+        calendar.add_holiday("2026-08-31").unwrap();
+        calendar.add_holiday("2026-10-30").unwrap();
+
+        // Saturday 29th of August 2026 to Tuesday 1st of September, past the holiday on the Monday
+        let saturday = date(2026, 8, 29);
+        assert_eq!(
+            calendar.roll(saturday, StRollConvention::Following),
+            Some(date(2026, 9, 1))
+        );
+        assert_eq!(
+            calendar.roll(saturday, StRollConvention::ModifiedFollowing),
+            Some(date(2026, 8, 28))
+        );
+        assert_eq!(
+            calendar.roll(saturday, StRollConvention::Preceding),
+            Some(date(2026, 8, 28))
+        );
+        assert_eq!(calendar.roll(saturday, StRollConvention::Skip), None);
+
+        // Sunday 25th of October 2026 stays in its month either way
+        let sunday = date(2026, 10, 25);
+        assert_eq!(
+            calendar.roll(sunday, StRollConvention::ModifiedFollowing),
+            Some(date(2026, 10, 26))
+        );
+        assert_eq!(
+            calendar.roll(sunday, StRollConvention::Preceding),
+            Some(date(2026, 10, 23))
+        );
+
+        // A business day is never rolled
+        let monday = date(2026, 10, 26);
+        assert_eq!(calendar.roll(monday, StRollConvention::Skip), Some(monday));
+
+        // No business day at all within two weeks, with Wednesday the only weekday off the weekend
+        calendar.set_weekend(vec![0, 1, 2, 4, 5, 6]).unwrap();
+        calendar.add_holiday("2026-10-28").unwrap();
+        calendar.add_holiday("2026-11-04").unwrap();
+        assert_eq!(calendar.roll(monday, StRollConvention::Following), None);
+
+        // Every day cannot be on the weekend
+        assert_eq!(
+            calendar.set_weekend((0..7).collect()),
+            Err(CalendarError::InvalidWeekend(
+                "at least one day of the week should be a business day"
+            ))
+        );
+        assert!(calendar.set_weekend(vec![5, 7]).is_err());
+        assert_eq!(calendar.get_weekend(), vec![0, 1, 2, 4, 5, 6]);
+    }
+
+    /// Should date Easter Sunday and the holidays around it
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_easter_sunday() {
+        let expected = [
+            (2024, date(2024, 3, 31)),
+            (2025, date(2025, 4, 20)),
+            (2026, date(2026, 4, 5)),
+            (2038, date(2038, 4, 25)),
+            (2285, date(2285, 3, 22)),
+        ];
+
+        for (year, sunday) in expected {
+            assert_eq!(easter_sunday(year), Some(sunday));
+        }
+
+        let good_friday = HolidayRule::Easter { offset: -2 };
+        assert_eq!(good_friday.date_in(2026), Some(date(2026, 4, 3)));
+    }
+}
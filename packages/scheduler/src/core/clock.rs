@@ -0,0 +1,133 @@
+use core::fmt::Debug;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+
+use chrono::Utc;
+use wasm_bindgen::prelude::*;
+
+use crate::core::time::{Timestamp, Ts};
+
+/// A source of the current time for the timing engine.
+///
+/// Every computation that needs to know "now" asks a [`Clock`] rather than the system time directly,
+/// so that the current time can be pinned or stepped through deterministically.
+pub trait Clock: Debug {
+    /// Returns the current timestamp as seen by this clock
+    fn now(&self) -> Timestamp;
+}
+
+/// A [`Clock`] backed by the system (wall) time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+/// A [`Clock`] that is pinned to a given timestamp and never moves
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(Timestamp);
+
+/// A [`Clock`] that only moves when it is explicitly set or advanced.
+///
+/// Clones of a [`ManualClock`] share the same time, so a clone handed over to a scheduler can still be
+/// advanced from the outside.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    millis: Arc<AtomicI64>,
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Timestamp {
+        Utc::now().to_timestamp()
+    }
+}
+
+impl FixedClock {
+    pub fn new(now: Timestamp) -> Self {
+        FixedClock(now)
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> Timestamp {
+        self.0
+    }
+}
+
+#[wasm_bindgen]
+impl ManualClock {
+    #[wasm_bindgen(constructor)]
+    /// Initializes a manual clock starting at the given timestamp in milliseconds
+    pub fn new(millis: i64) -> Self {
+        ManualClock {
+            millis: Arc::new(AtomicI64::new(millis)),
+        }
+    }
+
+    /// Moves the clock to the given timestamp in milliseconds
+    pub fn set(&self, millis: i64) {
+        self.millis.store(millis, Ordering::SeqCst);
+    }
+
+    /// Moves the clock forward by the given number of milliseconds
+    pub fn advance(&self, millis: i64) {
+        self.millis.fetch_add(millis, Ordering::SeqCst);
+    }
+
+    pub fn get_millis(&self) -> i64 {
+        self.millis.load(Ordering::SeqCst)
+    }
+}
+
+impl ManualClock {
+    /// Moves the clock forward by the given timestamp
+    pub fn advance_by(&self, by: Timestamp) {
+        self.advance(by.as_ms());
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Timestamp {
+        Timestamp::Millis(self.get_millis())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    use crate::core::time::timestamp;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_system_clock() {
+        let before = timestamp();
+        let now = SystemClock.now();
+
+        assert!(now >= before);
+        assert!(timestamp() >= now);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_fixed_clock() {
+        let clock = FixedClock::new(Timestamp::Millis(1729520340000));
+
+        assert_eq!(clock.now(), Timestamp::Millis(1729520340000));
+        assert_eq!(clock.now(), clock.now());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_manual_clock() {
+        let clock = ManualClock::new(1729520340000);
+        let shared = clock.clone();
+
+        clock.advance_by(Timestamp::from_hours(1.0));
+        assert_eq!(shared.now(), Timestamp::Millis(1729523940000));
+
+        shared.set(0);
+        assert_eq!(clock.now(), Timestamp::Millis(0));
+    }
+}
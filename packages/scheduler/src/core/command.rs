@@ -0,0 +1,78 @@
+use wasm_bindgen::prelude::*;
+
+use crate::core::event::to_js_object;
+use crate::core::time::Timestamp;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_ST_SCHEDULE_ENTRY: &str = r#"
+/**
+ * A schedule held by a scheduler, as listed by the runner
+ */
+export interface StScheduleEntry {
+  /** The ID of the schedule */
+  id: string;
+  /** The time the schedule is next due at, in milliseconds */
+  nextAt: number;
+  /** Whether the schedule is paused, in which case it is not due until it is resumed */
+  paused: boolean;
+  /** The time a paused schedule is automatically resumed at, in milliseconds, if ever */
+  resumesAt?: number;
+}
+"#;
+
+/// The outcome of a command carried out on a scheduler through its runner
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StCommandOutcome {
+    /// A schedule that did not exist was added
+    Inserted,
+    /// An existing schedule was replaced
+    Replaced,
+    /// The schedule was removed
+    Removed,
+    /// The schedule was paused, or the time it is automatically resumed was changed
+    Paused,
+    /// The paused schedule was resumed
+    Resumed,
+    /// The occurrence of the schedule that was fired last was snoozed
+    Snoozed,
+    /// The fired occurrences of the schedule waiting to be acknowledged were acknowledged
+    Acknowledged,
+    /// There is no schedule with the given ID the command applies to, e.g. none of its occurrences were fired
+    /// to snooze or acknowledge
+    NotFound,
+    /// A schedule with the same ID already exists, so it was not added
+    Duplicate,
+    /// The schedule has no upcoming occurrence to queue, e.g. it expired or its timing failed to evaluate
+    Invalid,
+    /// The runner stopped before it could carry out the command
+    Unavailable,
+}
+
+/// A schedule held by a scheduler, as listed by [`crate::core::scheduler::StSchedulerRunner`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleEntry {
+    pub id: String,
+    /// The time the schedule is next due at, a paused schedule only being due once resumed
+    pub next_at: Timestamp,
+    pub paused: bool,
+    /// The time a paused schedule is automatically resumed at, if ever
+    pub resumes_at: Option<Timestamp>,
+}
+
+impl From<&ScheduleEntry> for JsValue {
+    /// Converts the entry into a plain JavaScript object of the shape of `StScheduleEntry`
+    fn from(entry: &ScheduleEntry) -> Self {
+        to_js_object(&[
+            ("id", entry.id.as_str().into()),
+            ("nextAt", entry.next_at.as_ms_f64().into()),
+            ("paused", entry.paused.into()),
+            (
+                "resumesAt",
+                entry
+                    .resumes_at
+                    .map_or(JsValue::UNDEFINED, |ts| ts.as_ms_f64().into()),
+            ),
+        ])
+    }
+}
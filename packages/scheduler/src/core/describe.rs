@@ -0,0 +1,698 @@
+use core::fmt;
+
+use chrono::prelude::*;
+use chrono_tz::Tz;
+use wasm_bindgen::prelude::*;
+
+use crate::core::calendar::StRollConvention;
+use crate::core::frequency::{
+    StConstWeekday, StCustomFrequency, StFrequency, StFrequencyExpression, StMonth,
+    StMonthlySubExpression, StRegularFrequency, StWeekday, MAX_CRON_EXPRESSIONS,
+};
+use crate::core::schedule::StSchedule;
+use crate::core::time::Timestamp;
+
+/// The format of the dates in descriptions, e.g. "31 Mar 2026"
+const DATE_FORMAT: &str = "%-d %b %Y";
+
+/// The most times a cron expression is described at, e.g. "At 09:00 and 17:00", before its minutes and hours are
+/// described apart instead
+const MAX_CRON_TIMES: usize = 4;
+
+/// A field of a cron expression, as far as it is described
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CronField {
+    /// Any value, `*`
+    Any,
+    /// Every nth value from the first, `*/n`
+    Every(u32),
+    /// A list of values and inclusive ranges of values, `a,b-c`, where a value is a range of one
+    List(Vec<(u32, u32)>),
+}
+
+#[wasm_bindgen]
+impl StRegularFrequency {
+    /// Describes the frequency in words, e.g. "Every 2 weeks on Mon and Wed until 31 Mar 2026", where the `until` is
+    /// dated in UTC.
+    ///
+    /// A frequency on the anchor's day is described without the day, which only a schedule knows, see
+    /// [`StSchedule::describe`].
+    pub fn describe(&self) -> String {
+        self.to_string()
+    }
+}
+
+#[wasm_bindgen]
+impl StCustomFrequency {
+    /// Describes the cron expressions of the frequency in words, e.g. "At 09:00 on Mon through Fri", where the
+    /// `until` is dated in UTC.
+    ///
+    /// Only the expressions that are evaluated are described, and an expression that cannot be described is quoted
+    /// as it is.
+    pub fn describe(&self) -> String {
+        self.to_string()
+    }
+}
+
+#[wasm_bindgen]
+impl StSchedule {
+    /// Describes the schedule in words, e.g. "Every 2 weeks on Mon and Wed at 09:00 (Africa/Lagos) until 31 Mar
+    /// 2026", with its times and dates in its timezone.
+    ///
+    /// The excluded and included times of the schedule are left out.
+    pub fn describe(&self) -> String {
+        let timezone = self.get_timezone();
+        let anchor = self.get_local_anchor();
+        let time = format_time(anchor.time());
+
+        let mut text = match self.get_frequency() {
+            None => format!(
+                "Once on {} at {} ({})",
+                anchor.format(DATE_FORMAT),
+                time,
+                timezone.name()
+            ),
+            Some(StFrequency::Regular(reg)) => {
+                let at = match reg.get_expr() {
+                    StFrequencyExpression::Hourly(_) => "from",
+                    _ => "at",
+                };
+                format!(
+                    "{} {} {} ({}){}",
+                    describe_repeat(reg, Some(&anchor)),
+                    at,
+                    time,
+                    timezone.name(),
+                    describe_end(reg.until, reg.count, timezone)
+                )
+            }
+            Some(StFrequency::Custom(cstm)) => format!(
+                "{} ({}){}",
+                describe_crons(&cstm.cron_expressions),
+                timezone.name(),
+                describe_end(cstm.until, cstm.count, timezone)
+            ),
+        };
+
+        if let Some(business_days) = self.get_business_days() {
+            text.push_str(match business_days.get_roll() {
+                StRollConvention::Following => ", or the next business day",
+                StRollConvention::ModifiedFollowing => {
+                    ", or the next business day in the month, else the previous one"
+                }
+                StRollConvention::Preceding => ", or the previous business day",
+                StRollConvention::Skip => ", except on weekends and holidays",
+            });
+        }
+
+        text
+    }
+}
+
+impl fmt::Display for StRegularFrequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&describe_repeat(self, None))?;
+        f.write_str(&describe_end(self.until, self.count, Tz::UTC))
+    }
+}
+
+impl fmt::Display for StCustomFrequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&describe_crons(&self.cron_expressions))?;
+        f.write_str(&describe_end(self.until, self.count, Tz::UTC))
+    }
+}
+
+/// Describes how often a regular frequency repeats and on which days, with the days it leaves to the anchor taken
+/// from the given anchor, if any
+fn describe_repeat(freq: &StRegularFrequency, anchor: Option<&NaiveDateTime>) -> String {
+    match freq.get_expr() {
+        StFrequencyExpression::Hourly(expr) => every(expr.every, "hour"),
+        StFrequencyExpression::Daily(expr) => every(expr.every, "day"),
+        StFrequencyExpression::Weekly(expr) => {
+            let mut weekdays = expr.subexpr.weekdays.clone();
+            if weekdays.is_empty() {
+                weekdays.extend(anchor.map(|anchor| StConstWeekday::from(anchor.weekday())));
+            }
+            weekdays.sort();
+
+            match weekdays.is_empty() {
+                true => every(expr.every, "week"),
+                false => format!("{} on {}", every(expr.every, "week"), join_words(&weekdays)),
+            }
+        }
+        StFrequencyExpression::Monthly(expr) => {
+            let on = match &expr.subexpr {
+                StMonthlySubExpression::OnDays(subexpr) => {
+                    let mut days = subexpr.days.clone();
+                    if days.is_empty() {
+                        days.extend(anchor.map(|anchor| anchor.day()));
+                    }
+                    days.sort_unstable();
+
+                    let days = days.into_iter().map(nth).collect::<Vec<_>>();
+                    (!days.is_empty()).then(|| format!(" on the {}", join_words(&days)))
+                }
+                StMonthlySubExpression::OnThe(subexpr) => Some(format!(
+                    " on the {} {}",
+                    subexpr.ordinal.to_string().to_lowercase(),
+                    subexpr.weekday
+                )),
+            };
+
+            format!("{}{}", every(expr.every, "month"), on.unwrap_or_default())
+        }
+        StFrequencyExpression::Yearly(expr) => {
+            let mut months = expr.subexpr.months.clone();
+            months.sort();
+
+            let on = match (expr.subexpr.on, anchor) {
+                (Some(on), _) => format!(
+                    " on the {} {} of",
+                    on.ordinal.to_string().to_lowercase(),
+                    describe_weekday(on.weekday)
+                ),
+                (None, Some(anchor)) => format!(" on the {} of", nth(anchor.day())),
+                (None, None) => String::from(" in"),
+            };
+
+            match months.is_empty() {
+                true => every(expr.every, "year"),
+                false => format!(
+                    "{}{} {}",
+                    every(expr.every, "year"),
+                    on,
+                    join_words(&months)
+                ),
+            }
+        }
+    }
+}
+
+/// Describes when a frequency stops repeating, if it does, with the `until` dated in the given timezone
+fn describe_end(until: Option<Timestamp>, count: Option<u32>, timezone: Tz) -> String {
+    let mut text = String::new();
+
+    // The `until` is exclusive, so the frequency repeats until the day of the moment before it
+    if let Some(until) = until {
+        let last = (until - Timestamp::Millis(1))
+            .to_datetime()
+            .with_timezone(&timezone);
+        text.push_str(&format!(" until {}", last.format(DATE_FORMAT)));
+    }
+
+    match count {
+        Some(1) => text.push_str(" for 1 occurrence"),
+        Some(count) => text.push_str(&format!(" for {} occurrences", count)),
+        None => {}
+    }
+
+    text
+}
+
+/// Describes a weekday of a yearly frequency, where a variable weekday reads as a noun, e.g. "weekend day"
+fn describe_weekday(weekday: StWeekday) -> String {
+    match weekday {
+        StWeekday::Const(weekday) => weekday.to_string(),
+        StWeekday::Var(weekday) => weekday.to_string().to_lowercase(),
+    }
+}
+
+/// Describes the cron expressions that are evaluated, joined as one sentence
+fn describe_crons(expressions: &[String]) -> String {
+    let descriptions = expressions
+        .iter()
+        .take(MAX_CRON_EXPRESSIONS)
+        .enumerate()
+        .map(|(index, expression)| {
+            let description = describe_cron(expression);
+            match index {
+                0 => description,
+                _ => uncapitalize(&description),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    match descriptions.is_empty() {
+        true => String::from("Never"),
+        false => join_words(&descriptions),
+    }
+}
+
+/// Describes a cron expression, e.g. "At minute 0 past hour 9 through 17 on Mon through Fri" for "0 9-17 * * 1-5",
+/// or quotes it when it cannot be described
+fn describe_cron(expression: &str) -> String {
+    let fields = expression.split_whitespace().collect::<Vec<_>>();
+    let parsed = match fields.as_slice() {
+        [minute, hour, day, month, weekday] => (|| {
+            Some([
+                parse_cron_field(minute, 0, 59, &[])?,
+                parse_cron_field(hour, 0, 23, &[])?,
+                parse_cron_field(day, 1, 31, &[])?,
+                parse_cron_field(month, 1, 12, &MONTH_CODES)?,
+                parse_cron_field(weekday, 0, 7, &WEEKDAY_CODES)?,
+            ])
+        })(),
+        _ => None,
+    };
+    let [minute, hour, day, month, weekday] = match parsed {
+        Some(parsed) => parsed,
+        None => return format!("On the cron schedule \"{}\"", expression),
+    };
+
+    let mut text = describe_cron_time(&minute, &hour);
+
+    // A restricted day of the month and day of the week are either, as with cron
+    let mut days = vec![];
+    match &day {
+        CronField::Any => {}
+        CronField::Every(n) => days.push(format!("on every {} day of the month", nth(*n))),
+        CronField::List(values) => {
+            days.push(format!("on the {}", describe_cron_values(values, nth)))
+        }
+    }
+    match &weekday {
+        CronField::Any => {}
+        CronField::Every(n) => days.push(format!("on every {} day of the week", nth(*n))),
+        CronField::List(values) => days.push(format!(
+            "on {}",
+            describe_cron_values(values, |weekday| StConstWeekday::from(weekday).to_string())
+        )),
+    }
+    if !days.is_empty() {
+        text.push(' ');
+        text.push_str(&days.join(" or "));
+    }
+
+    match &month {
+        CronField::Any => {}
+        CronField::Every(n) => text.push_str(&format!(" in every {} month", nth(*n))),
+        CronField::List(values) => text.push_str(&format!(
+            " in {}",
+            describe_cron_values(values, |month| StMonth::from(month - 1).to_string())
+        )),
+    }
+
+    text
+}
+
+/// Describes the minutes and hours of a cron expression, as wall times when there are only a few of them
+fn describe_cron_time(minute: &CronField, hour: &CronField) -> String {
+    if let (Some(minutes), Some(hours)) = (cron_singles(minute), cron_singles(hour)) {
+        if minutes.len() * hours.len() <= MAX_CRON_TIMES {
+            let mut times = hours
+                .iter()
+                .flat_map(|hour| minutes.iter().map(move |minute| (*hour, *minute)))
+                .collect::<Vec<_>>();
+            times.sort_unstable();
+
+            let times = times
+                .into_iter()
+                .map(|(hour, minute)| format!("{:02}:{:02}", hour, minute))
+                .collect::<Vec<_>>();
+            return format!("At {}", join_words(&times));
+        }
+    }
+
+    let minutes = match minute {
+        CronField::Any => String::from("every minute"),
+        CronField::Every(n) => format!("every {} minute", nth(*n)),
+        CronField::List(values) => {
+            format!("minute {}", describe_cron_values(values, |m| m.to_string()))
+        }
+    };
+
+    match hour {
+        CronField::Any => format!("At {}", minutes),
+        CronField::Every(n) => format!("At {} past every {} hour", minutes, nth(*n)),
+        CronField::List(values) => format!(
+            "At {} past hour {}",
+            minutes,
+            describe_cron_values(values, |h| h.to_string())
+        ),
+    }
+}
+
+/// Describes the values and ranges of a field of a cron expression with the given name of a value, e.g. "Mon
+/// through Fri and Sun"
+fn describe_cron_values(values: &[(u32, u32)], name: impl Fn(u32) -> String) -> String {
+    let values = values
+        .iter()
+        .map(|(from, to)| match from == to {
+            true => name(*from),
+            false => format!("{} through {}", name(*from), name(*to)),
+        })
+        .collect::<Vec<_>>();
+
+    join_words(&values)
+}
+
+/// Returns the values of a field of a cron expression when it lists single values only
+fn cron_singles(field: &CronField) -> Option<Vec<u32>> {
+    match field {
+        CronField::List(values) => values
+            .iter()
+            .map(|(from, to)| (from == to).then_some(*from))
+            .collect(),
+        _ => None,
+    }
+}
+
+/// The codes of the months in cron expressions, indexed by month from January
+const MONTH_CODES: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+/// The codes of the weekdays in cron expressions, indexed by weekday from Sunday
+const WEEKDAY_CODES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Reads a field of a cron expression whose values are between the given bounds, and which can also be given as
+/// the codes, counted from the lower bound, returning `None` when it cannot be described
+fn parse_cron_field(field: &str, min: u32, max: u32, codes: &[&str]) -> Option<CronField> {
+    let value = |value: &str| {
+        let value = match codes
+            .iter()
+            .position(|code| code.eq_ignore_ascii_case(value))
+        {
+            Some(index) => index as u32 + min,
+            None => value.parse().ok()?,
+        };
+        (min..=max).contains(&value).then_some(value)
+    };
+
+    if field == "*" {
+        return Some(CronField::Any);
+    }
+
+    if let Some(step) = field.strip_prefix("*/") {
+        return match step.parse().ok()? {
+            0 => None,
+            1 => Some(CronField::Any),
+            step => Some(CronField::Every(step)),
+        };
+    }
+
+    field
+        .split(',')
+        .map(|item| match item.split_once('-') {
+            Some((from, to)) => Some((value(from)?, value(to)?)).filter(|(from, to)| from <= to),
+            None => value(item).map(|value| (value, value)),
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(CronField::List)
+}
+
+/// Describes how often a frequency repeats by its unit, e.g. "Every day" or "Every 2 days"
+fn every(every: u32, unit: &str) -> String {
+    match every {
+        1 => format!("Every {}", unit),
+        every => format!("Every {} {}s", every, unit),
+    }
+}
+
+/// Writes a number as an ordinal, e.g. "1st", "22nd" or "13th"
+fn nth(n: u32) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+
+    format!("{}{}", n, suffix)
+}
+
+/// Writes a wall time to the minute, or to the second when it has any
+fn format_time(time: NaiveTime) -> String {
+    match time.second() {
+        0 => time.format("%H:%M").to_string(),
+        _ => time.format("%H:%M:%S").to_string(),
+    }
+}
+
+/// Joins the given words as a list, e.g. "Mon, Wed and Fri"
+fn join_words(words: &[impl ToString]) -> String {
+    let words = words.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    match words.split_last() {
+        None => String::new(),
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+/// Lowers the first letter of a sentence, so it can be joined to another
+fn uncapitalize(text: &str) -> String {
+    let mut chars = text.chars();
+
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    use crate::core::calendar::StHolidayCalendar;
+    use crate::core::clock::FixedClock;
+    use crate::core::frequency::{
+        StDailyExpression, StFrequencyType, StHourlyExpression, StMonthlyExpression, StOrdinals,
+        StVarWeekday, StWeeklyExpression, StYearlyExpression,
+    };
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn schedule(naive_anchor: &str, frequency: Option<StFrequency>) -> StSchedule {
+        StSchedule::try_with_frequency(
+            "id",
+            naive_anchor,
+            "Africa/Lagos",
+            frequency,
+            None,
+            &FixedClock::new(Timestamp::Millis(0)),
+        )
+        .unwrap()
+    }
+
+    /// Returns the timestamp of the start of the given day in Africa/Lagos, in milliseconds
+    fn midnight(year: i32, month: u32, day: u32) -> u64 {
+        let tz: Tz = "Africa/Lagos".parse().unwrap();
+        tz.with_ymd_and_hms(year, month, day, 0, 0, 0)
+            .unwrap()
+            .timestamp_millis() as u64
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_describe_regular_frequencies() {
+        let cases = [
+            (
+                StRegularFrequency::new(StFrequencyType::Hour, StHourlyExpression::new(1), None),
+                "Every hour",
+            ),
+            (
+                StRegularFrequency::with_daily_expr(
+                    StFrequencyType::Day,
+                    StDailyExpression::new(3),
+                    None,
+                ),
+                "Every 3 days",
+            ),
+            (
+                StRegularFrequency::with_weekly_expr(
+                    StFrequencyType::Week,
+                    StWeeklyExpression::with_weekdays(1, vec![5, 1, 3]),
+                    None,
+                ),
+                "Every week on Mon, Wed and Fri",
+            ),
+            (
+                StRegularFrequency::with_monthly_expr(
+                    StFrequencyType::Month,
+                    StMonthlyExpression::with_days(2, vec![15, 1, 22]),
+                    None,
+                ),
+                "Every 2 months on the 1st, 15th and 22nd",
+            ),
+            (
+                StRegularFrequency::with_monthly_expr(
+                    StFrequencyType::Month,
+                    StMonthlyExpression::with_ordinal_weekday(1, StOrdinals::Last, 5.into()),
+                    None,
+                ),
+                "Every month on the last Fri",
+            ),
+            (
+                StRegularFrequency::with_yearly_expr(
+                    StFrequencyType::Year,
+                    StYearlyExpression::with_months_ordinal_var_weekday(
+                        1,
+                        vec![8, 2],
+                        StOrdinals::Second,
+                        StVarWeekday::Weekday,
+                    ),
+                    None,
+                ),
+                "Every year on the second weekday of Mar and Sep",
+            ),
+            (
+                StRegularFrequency::with_yearly_expr(
+                    StFrequencyType::Year,
+                    StYearlyExpression::with_months(1, vec![6, 0]),
+                    None,
+                ),
+                "Every year in Jan and Jul",
+            ),
+        ];
+
+        for (frequency, expected) in cases {
+            assert_eq!(frequency.describe(), expected);
+        }
+
+        // The `until` is exclusive and dated in UTC
+        let mut frequency = StRegularFrequency::with_daily_expr(
+            StFrequencyType::Day,
+            StDailyExpression::new(1),
+            Some(1_775_001_600_000),
+        );
+        assert_eq!(frequency.describe(), "Every day until 31 Mar 2026");
+        frequency.set_count(Some(10));
+        assert_eq!(
+            frequency.describe(),
+            "Every day until 31 Mar 2026 for 10 occurrences"
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_describe_cron_expressions() {
+        let cases = [
+            ("0 9 * * 1-5", "At 09:00 on Mon through Fri"),
+            ("30 8,17 * * *", "At 08:30 and 17:30"),
+            ("*/10 * * * *", "At every 10th minute"),
+            ("* * * * *", "At every minute"),
+            (
+                "0 9-17 * * MON-FRI",
+                "At minute 0 past hour 9 through 17 on Mon through Fri",
+            ),
+            ("15 */2 * * *", "At minute 15 past every 2nd hour"),
+            (
+                "0 12 1,15 */3 *",
+                "At 12:00 on the 1st and 15th in every 3rd month",
+            ),
+            ("0 0 13 * 5", "At 00:00 on the 13th or on Fri"),
+            (
+                "0 6 * jan,jul 0,6-7",
+                "At 06:00 on Sun and Sat through Sun in Jan and Jul",
+            ),
+            ("0 9 * * 1-5/2", "On the cron schedule \"0 9 * * 1-5/2\""),
+            ("0 25 * * *", "On the cron schedule \"0 25 * * *\""),
+            ("@daily", "On the cron schedule \"@daily\""),
+        ];
+
+        for (expression, expected) in cases {
+            let frequency = StCustomFrequency::new(vec![expression.to_string()], None);
+            assert_eq!(frequency.describe(), expected, "{}", expression);
+        }
+
+        // Only the expressions that are evaluated are described
+        let expressions = ["0 9 * * 1-5", "0 10 * * 6", "0 11 * * 0", "0 12 * * *"];
+        let frequency =
+            StCustomFrequency::new(expressions.iter().map(|e| e.to_string()).collect(), None);
+        assert_eq!(
+            frequency.describe(),
+            "At 09:00 on Mon through Fri, at 10:00 on Sat and at 11:00 on Sun"
+        );
+        assert_eq!(StCustomFrequency::new(vec![], None).describe(), "Never");
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_describe_schedules() {
+        let until = midnight(2026, 4, 1);
+        let fortnightly = StRegularFrequency::with_weekly_expr(
+            StFrequencyType::Week,
+            StWeeklyExpression::with_weekdays(2, vec![1, 3]),
+            Some(until),
+        );
+        let monthly = StRegularFrequency::with_monthly_expr(
+            StFrequencyType::Month,
+            StMonthlyExpression::with_days(1, vec![]),
+            None,
+        );
+        let hourly =
+            StRegularFrequency::new(StFrequencyType::Hour, StHourlyExpression::new(2), None);
+        let yearly = StRegularFrequency::with_yearly_expr(
+            StFrequencyType::Year,
+            StYearlyExpression::with_months(1, vec![0, 6]),
+            None,
+        );
+        let mut weekdays = StCustomFrequency::new(vec![String::from("0 9 * * 1-5")], None);
+        weekdays.set_count(Some(1));
+
+        let cases = [
+            (
+                schedule("2026-01-05T09:00:00.000", None),
+                "Once on 5 Jan 2026 at 09:00 (Africa/Lagos)",
+            ),
+            (
+                schedule(
+                    "2026-01-05T09:00:00.000",
+                    Some(StFrequency::Regular(fortnightly)),
+                ),
+                "Every 2 weeks on Mon and Wed at 09:00 (Africa/Lagos) until 31 Mar 2026",
+            ),
+            (
+                schedule(
+                    "2026-01-25T09:30:15.000",
+                    Some(StFrequency::Regular(monthly.clone())),
+                ),
+                "Every month on the 25th at 09:30:15 (Africa/Lagos)",
+            ),
+            (
+                schedule(
+                    "2026-01-25T08:00:00.000",
+                    Some(StFrequency::Regular(hourly)),
+                ),
+                "Every 2 hours from 08:00 (Africa/Lagos)",
+            ),
+            (
+                schedule(
+                    "2026-01-15T07:00:00.000",
+                    Some(StFrequency::Regular(yearly)),
+                ),
+                "Every year on the 15th of Jan and Jul at 07:00 (Africa/Lagos)",
+            ),
+            (
+                schedule(
+                    "2026-01-05T09:00:00.000",
+                    Some(StFrequency::Custom(weekdays)),
+                ),
+                "At 09:00 on Mon through Fri (Africa/Lagos) for 1 occurrence",
+            ),
+        ];
+
+        for (schedule, expected) in cases {
+            assert_eq!(schedule.describe(), expected);
+        }
+
+        // Pay on the 25th, or the previous business day
+        let mut payday = schedule(
+            "2026-01-25T09:00:00.000",
+            Some(StFrequency::Regular(monthly)),
+        );
+        payday.set_holiday_calendar_with(
+            Some(StHolidayCalendar::new()),
+            StRollConvention::Preceding,
+            &FixedClock::new(Timestamp::Millis(0)),
+        );
+        assert_eq!(
+            payday.describe(),
+            "Every month on the 25th at 09:00 (Africa/Lagos), or the previous business day"
+        );
+    }
+}
@@ -1,6 +1,11 @@
 use core::fmt;
 use core::fmt::{Debug, Display};
 
+use wasm_bindgen::JsValue;
+
+use crate::core::scheduler::StRunnerState;
+use crate::core::snapshot::SNAPSHOT_VERSION;
+
 #[derive(Debug, Clone, Copy)]
 pub enum ConversionError<T>
 where
@@ -83,6 +88,9 @@ pub enum TimingError {
     /// The frequency expression has a validity date after which the timing should stop
     /// repeating, and that validity date is now passed.
     FrequencyExpired,
+    /// The frequency expression has a number of occurrences after which the timing should stop
+    /// repeating, and that many occurrences have already come.
+    CountExhausted,
     /// Some of the required frequency expressions are missing, for example when supplied
     /// an empty cron expression vector or two mutually exclusive parameters are missing as
     /// in monthly frequency expressions.
@@ -105,6 +113,10 @@ impl Display for TimingError {
                 f,
                 "The timing frequency has expired since its given 'until' is passed "
             ),
+            TimingError::CountExhausted => write!(
+                f,
+                "The timing frequency has ended since its given 'count' of occurrences is reached"
+            ),
             TimingError::MissingExpression => {
                 write!(
                     f,
@@ -142,3 +154,236 @@ impl From<cron_parser::ParseError> for TimingError {
         TimingError::ParseError(value.into())
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunnerError {
+    /// The runner cannot be moved to a state from the state it is in, e.g. resumed while already running.
+    /// `InvalidTransition(from, to)`
+    InvalidTransition(StRunnerState, StRunnerState),
+}
+
+impl std::error::Error for RunnerError {}
+
+impl Display for RunnerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            RunnerError::InvalidTransition(from, to) => {
+                write!(f, "The runner cannot go from {} to {}", from, to)
+            }
+        }
+    }
+}
+
+impl From<RunnerError> for JsValue {
+    /// Converts the error into a JavaScript `Error`, so it is thrown as an exception
+    fn from(value: RunnerError) -> Self {
+        js_sys::Error::new(&value.to_string()).into()
+    }
+}
+
+#[derive(Debug)]
+pub enum TaskScheduleError {
+    /// A required field is missing or `null`.
+    /// `Missing(path)`
+    Missing(String),
+    /// A field is not of the expected shape, e.g. of another type.
+    /// `Malformed(path, reason)`
+    Malformed(String, String),
+    /// A field is of the expected type but its value is not one of the allowed values.
+    /// `InvalidValue(path, allowed)`
+    InvalidValue(String, &'static str),
+    /// The fields are valid but the timing of the schedule could not be evaluated from them
+    TimingError(TimingError),
+}
+
+impl std::error::Error for TaskScheduleError {}
+
+impl Display for TaskScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            TaskScheduleError::Missing(path) => write!(f, "'{}' is required", path),
+            TaskScheduleError::Malformed(path, reason) => {
+                write!(f, "'{}' is malformed: {}", path, reason)
+            }
+            TaskScheduleError::InvalidValue(path, allowed) => {
+                write!(f, "'{}' should be {}", path, allowed)
+            }
+            TaskScheduleError::TimingError(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl From<TimingError> for TaskScheduleError {
+    fn from(value: TimingError) -> Self {
+        TaskScheduleError::TimingError(value)
+    }
+}
+
+impl From<TaskScheduleError> for JsValue {
+    /// Converts the error into a JavaScript `TypeError`, so it is thrown as an exception
+    fn from(value: TaskScheduleError) -> Self {
+        js_sys::TypeError::new(&value.to_string()).into()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// The snapshot is of a version this version of the scheduler cannot restore, e.g. one taken by a newer
+    /// version of it.
+    /// `UnsupportedVersion(version)`
+    UnsupportedVersion(u32),
+    /// The snapshot could not be read or written.
+    /// `Malformed(reason)`
+    Malformed(String),
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            SnapshotError::UnsupportedVersion(version) => write!(
+                f,
+                "Snapshots of version {} are not supported, the latest supported version is {}",
+                version, SNAPSHOT_VERSION
+            ),
+            SnapshotError::Malformed(reason) => write!(f, "The snapshot is malformed: {}", reason),
+        }
+    }
+}
+
+impl From<SnapshotError> for JsValue {
+    /// Converts the error into a JavaScript `Error`, so it is thrown as an exception
+    fn from(value: SnapshotError) -> Self {
+        js_sys::Error::new(&value.to_string()).into()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RRuleError {
+    /// The rule is not a list of `NAME=VALUE` parts separated by semicolons, each given once.
+    /// `Malformed(reason)`
+    Malformed(String),
+    /// The rule has no `FREQ` part
+    MissingFrequency,
+    /// A part of the rule has a value that is not valid for it.
+    /// `InvalidValue(name, value, expected)`
+    InvalidValue(String, String, &'static str),
+    /// A part of the rule is valid but frequencies have no equivalent for it, e.g. `BYHOUR` or `FREQ=MINUTELY`.
+    /// `UnsupportedPart(name, value)`
+    UnsupportedPart(String, String),
+    /// The parts of the rule are each supported but not together, e.g. `BYMONTHDAY` with `FREQ=WEEKLY`.
+    /// `UnsupportedCombination(reason)`
+    UnsupportedCombination(String),
+    /// The frequency cannot be written as a single rule.
+    /// `Unrepresentable(reason)`
+    Unrepresentable(&'static str),
+}
+
+impl std::error::Error for RRuleError {}
+
+impl Display for RRuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            RRuleError::Malformed(reason) => write!(f, "The rule is malformed: {}", reason),
+            RRuleError::MissingFrequency => write!(f, "The rule has no FREQ part"),
+            RRuleError::InvalidValue(name, value, expected) => {
+                write!(f, "'{}={}' should be {}", name, value, expected)
+            }
+            RRuleError::UnsupportedPart(name, value) => {
+                write!(f, "'{}={}' is not supported", name, value)
+            }
+            RRuleError::UnsupportedCombination(reason) => {
+                write!(f, "The rule is not supported: {}", reason)
+            }
+            RRuleError::Unrepresentable(reason) => {
+                write!(f, "The frequency cannot be written as a rule: {}", reason)
+            }
+        }
+    }
+}
+
+impl From<RRuleError> for JsValue {
+    /// Converts the error into a JavaScript `Error`, so it is thrown as an exception
+    fn from(value: RRuleError) -> Self {
+        js_sys::Error::new(&value.to_string()).into()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IcsError {
+    /// The file is not an iCalendar file, or its components are not properly nested.
+    /// `Malformed(line, reason)`
+    Malformed(usize, String),
+    /// The timezone floating times are read in is not a known IANA timezone.
+    /// `UnknownTimezone(name)`
+    UnknownTimezone(String),
+}
+
+impl std::error::Error for IcsError {}
+
+impl Display for IcsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            IcsError::Malformed(line, reason) => {
+                write!(f, "The calendar is malformed at line {}: {}", line, reason)
+            }
+            IcsError::UnknownTimezone(name) => write!(f, "The timezone '{}' is not known", name),
+        }
+    }
+}
+
+impl From<IcsError> for JsValue {
+    /// Converts the error into a JavaScript `Error`, so it is thrown as an exception
+    fn from(value: IcsError) -> Self {
+        js_sys::Error::new(&value.to_string()).into()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalendarError {
+    /// A line of the rules of a calendar is not a rule.
+    /// `Malformed(line, reason)`
+    Malformed(usize, String),
+    /// A date is not in the format `YYYY-MM-DD`, or is not a valid date.
+    /// `InvalidDate(date)`
+    InvalidDate(String),
+    /// The file of the rules of a calendar could not be read.
+    /// `Unreadable(reason)`
+    Unreadable(String),
+    /// The weekend of a calendar has a weekday out of range or leaves no business day in the week.
+    /// `InvalidWeekend(reason)`
+    InvalidWeekend(&'static str),
+}
+
+impl std::error::Error for CalendarError {}
+
+impl Display for CalendarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            CalendarError::Malformed(line, reason) => {
+                write!(
+                    f,
+                    "The calendar rules are malformed at line {}: {}",
+                    line, reason
+                )
+            }
+            CalendarError::InvalidDate(date) => {
+                write!(f, "The date '{}' is not a valid YYYY-MM-DD date", date)
+            }
+            CalendarError::Unreadable(reason) => {
+                write!(f, "The calendar rules could not be read: {}", reason)
+            }
+            CalendarError::InvalidWeekend(reason) => {
+                write!(f, "The weekend is invalid: {}", reason)
+            }
+        }
+    }
+}
+
+impl From<CalendarError> for JsValue {
+    /// Converts the error into a JavaScript `Error`, so it is thrown as an exception
+    fn from(value: CalendarError) -> Self {
+        js_sys::Error::new(&value.to_string()).into()
+    }
+}
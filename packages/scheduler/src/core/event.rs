@@ -0,0 +1,558 @@
+use chrono::NaiveDateTime;
+use chrono_tz::Tz;
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+use crate::core::policy::StCatchUpPolicy;
+use crate::core::priority::StPriority;
+use crate::core::schedule::StSchedule;
+use crate::core::time::Timestamp;
+use crate::traits::ID;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_ST_SCHEDULER_EVENT: &str = r#"
+/**
+ * The payload a subscriber is called with when a schedule is due
+ */
+export interface StDueEvent {
+  kind: "due";
+  /** The ID of the schedule that is due */
+  id: string;
+  /** The time the occurrence was planned to fire at, in milliseconds */
+  plannedAt: number;
+  /** The time the occurrence was actually dispatched at, in milliseconds */
+  dispatchedAt: number;
+  /** How late the dispatch was relative to the planned time, in milliseconds */
+  lateness: number;
+  /** The zero-based index of the occurrence, the anchor being the first, unset once it is no longer tracked */
+  occurrence?: number;
+  /** The priority of the schedule */
+  priority?: StPriority;
+  /** The planned time as a wall time in the schedule's timezone, e.g. "2024-10-28T21:05:55.025" */
+  localTime: string;
+  /** The IANA timezone of the schedule */
+  timezone: string;
+  /** Whether this is the final occurrence of the schedule */
+  isFinal: boolean;
+  /** The planned times of the missed occurrences folded into this one, in milliseconds */
+  coalesced: number[];
+  /** Whether the occurrence was snoozed and is being re-fired, its planned time being the original one */
+  snoozed: boolean;
+}
+
+/**
+ * The payload a subscriber is called with when occurrences of a schedule were missed and not fired
+ */
+export interface StMissedEvent {
+  kind: "missed";
+  /** The ID of the schedule whose occurrences were missed */
+  id: string;
+  /** The planned times of the missed occurrences, in milliseconds */
+  missed: number[];
+  /**
+   * The number of missed occurrences before the ones in `missed`, past the most that are caught up on at once,
+   * estimated from the spacing of the latest ones when far more were missed
+   */
+  dropped: number;
+  /** The time the missed occurrences were detected at, in milliseconds */
+  detectedAt: number;
+  /** The policy the missed occurrences were handled with */
+  policy: StCatchUpPolicy;
+}
+
+/**
+ * The payload a subscriber is called with ahead of an occurrence of a schedule
+ */
+export interface StReminderEvent {
+  kind: "reminder";
+  /** The ID of the schedule the reminder is for */
+  id: string;
+  /** The time the occurrence is planned to fire at, in milliseconds */
+  plannedAt: number;
+  /** How long before the occurrence the reminder is, in milliseconds */
+  offset: number;
+  /** The time the reminder was actually dispatched at, in milliseconds */
+  dispatchedAt: number;
+  /** The planned time as a wall time in the schedule's timezone, e.g. "2024-10-28T21:05:55.025" */
+  localTime: string;
+  /** The IANA timezone of the schedule */
+  timezone: string;
+}
+
+/**
+ * The payload a subscriber is called with when an occurrence of a schedule is queued
+ */
+export interface StUpcomingEvent {
+  kind: "upcoming";
+  /** The ID of the schedule the occurrence belongs to */
+  id: string;
+  /** The time the occurrence is planned to fire at, in milliseconds */
+  plannedAt: number;
+  /** The planned time as a wall time in the schedule's timezone, e.g. "2024-10-28T21:05:55.025" */
+  localTime: string;
+  /** The IANA timezone of the schedule */
+  timezone: string;
+}
+
+/**
+ * The payload a subscriber is called with when a fired occurrence was not acknowledged within its grace period
+ */
+export interface StOverdueEvent {
+  kind: "overdue";
+  /** The ID of the schedule the occurrence belongs to */
+  id: string;
+  /** The time the occurrence was planned to fire at, in milliseconds */
+  plannedAt: number;
+  /** The grace period the occurrence was not acknowledged within, in milliseconds */
+  grace: number;
+  /** The time the occurrence was dispatched as overdue at, in milliseconds */
+  dispatchedAt: number;
+}
+
+/**
+ * The payload a subscriber is called with when a schedule has no more occurrences
+ */
+export interface StExpiredEvent {
+  kind: "expired";
+  /** The ID of the schedule that expired */
+  id: string;
+  /** The time the last occurrence of the schedule was planned to fire at, in milliseconds */
+  lastPlannedAt: number;
+  /** The time until when the schedule's frequency was valid, in milliseconds */
+  until?: number;
+  /** The number of occurrences the schedule's frequency was limited to */
+  count?: number;
+  /** The time the schedule was found to be expired at, in milliseconds */
+  detectedAt: number;
+}
+
+export type StSchedulerEvent =
+  | StUpcomingEvent
+  | StReminderEvent
+  | StDueEvent
+  | StMissedEvent
+  | StOverdueEvent
+  | StExpiredEvent;
+"#;
+
+/// The format of the wall time of a [`DueEvent`], matching that of a schedule's naive anchor
+const LOCAL_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.3f";
+
+/// An event dispatched by the scheduler to its subscribers.
+///
+/// Together, the events follow the lifecycle of a schedule's occurrence: it is upcoming once queued, it can be
+/// reminded of ahead of time, it is due when fired, or missed when not, it is overdue when left unacknowledged
+/// past its grace period, and the schedule is expired once it has no more occurrences.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchedulerEvent {
+    /// An occurrence of a schedule was queued
+    Upcoming(UpcomingEvent),
+    /// An occurrence of a schedule is coming up
+    Reminder(ReminderEvent),
+    /// A schedule is due and has been fired
+    Due(DueEvent),
+    /// Occurrences of a schedule were missed and were not fired
+    Missed(MissedEvent),
+    /// A fired occurrence of a schedule was not acknowledged within its grace period
+    Overdue(OverdueEvent),
+    /// A schedule has no more occurrences
+    Expired(ExpiredEvent),
+}
+
+/// The details of a schedule's occurrence that was queued
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpcomingEvent {
+    /// The ID of the schedule the occurrence belongs to
+    pub id: String,
+    /// The time the occurrence is planned to fire at
+    pub planned_at: Timestamp,
+    /// The planned time as a wall time in the schedule's timezone
+    pub local_time: NaiveDateTime,
+    /// The timezone of the schedule
+    pub timezone: Tz,
+}
+
+/// The details of a fired occurrence that was not acknowledged within its grace period
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverdueEvent {
+    /// The ID of the schedule the occurrence belongs to
+    pub id: String,
+    /// The time the occurrence was planned to fire at
+    pub planned_at: Timestamp,
+    /// The grace period the occurrence was not acknowledged within
+    pub grace: Timestamp,
+    /// The time the occurrence was dispatched as overdue at
+    pub dispatched_at: Timestamp,
+}
+
+/// The details of a schedule that has no more occurrences
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpiredEvent {
+    /// The ID of the schedule that expired
+    pub id: String,
+    /// The time the last occurrence of the schedule was planned to fire at
+    pub last_planned_at: Timestamp,
+    /// The time until when the schedule's frequency was valid, one-off schedules having none
+    pub until: Option<Timestamp>,
+    /// The number of occurrences the schedule's frequency was limited to, if any
+    pub count: Option<u32>,
+    /// The time the schedule was found to be expired at
+    pub detected_at: Timestamp,
+}
+
+/// The details of a schedule's occurrence that is due
+#[derive(Debug, Clone, PartialEq)]
+pub struct DueEvent {
+    /// The ID of the schedule that is due
+    pub id: String,
+    /// The time the occurrence was planned to fire at
+    pub planned_at: Timestamp,
+    /// The time the occurrence was actually dispatched at
+    pub dispatched_at: Timestamp,
+    /// How late the dispatch was relative to the planned time, never negative
+    pub lateness: Timestamp,
+    /// The zero-based index of the occurrence, the anchor being the first, or `None` once it is no longer tracked,
+    /// e.g. for a schedule added long after its anchor, whose index would have to be evaluated from it
+    pub occurrence: Option<u64>,
+    /// The priority of the schedule
+    pub priority: Option<StPriority>,
+    /// The planned time as a wall time in the schedule's timezone
+    pub local_time: NaiveDateTime,
+    /// The timezone of the schedule
+    pub timezone: Tz,
+    /// Whether this is the final occurrence of the schedule, e.g. the last one before the frequency's `until`
+    pub is_final: bool,
+    /// The planned times of the missed occurrences folded into this one by [`StCatchUpPolicy::Coalesce`]
+    pub coalesced: Vec<Timestamp>,
+    /// Whether the occurrence was snoozed and is being re-fired, [`DueEvent::planned_at`] being its original
+    /// planned time
+    pub snoozed: bool,
+}
+
+/// The details of the occurrences of a schedule that were missed and not fired
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissedEvent {
+    /// The ID of the schedule whose occurrences were missed
+    pub id: String,
+    /// The planned times of the missed occurrences, in ascending order
+    pub missed: Vec<Timestamp>,
+    /// The number of missed occurrences before the ones in [`MissedEvent::missed`], whose planned times are not
+    /// kept as they are past the most occurrences that are caught up on at once. When far more were missed, the
+    /// number is estimated from the spacing of the latest ones rather than counted one by one
+    pub dropped: u64,
+    /// The time the missed occurrences were detected at
+    pub detected_at: Timestamp,
+    /// The policy the missed occurrences were handled with
+    pub policy: StCatchUpPolicy,
+}
+
+/// The details of a reminder ahead of a schedule's occurrence
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReminderEvent {
+    /// The ID of the schedule the reminder is for
+    pub id: String,
+    /// The time the occurrence is planned to fire at
+    pub planned_at: Timestamp,
+    /// How long before the occurrence the reminder is
+    pub offset: Timestamp,
+    /// The time the reminder was actually dispatched at
+    pub dispatched_at: Timestamp,
+    /// The planned time as a wall time in the schedule's timezone
+    pub local_time: NaiveDateTime,
+    /// The timezone of the schedule
+    pub timezone: Tz,
+}
+
+impl UpcomingEvent {
+    /// Creates the event for the current deadline of the given schedule
+    pub fn new(schedule: &StSchedule) -> Self {
+        UpcomingEvent {
+            id: schedule.get_id(),
+            planned_at: Timestamp::Millis(schedule.get_deadline_millis()),
+            local_time: schedule.get_local_deadline(),
+            timezone: schedule.get_timezone(),
+        }
+    }
+}
+
+impl OverdueEvent {
+    /// Creates the event for the current deadline of the given schedule, dispatched at the given time
+    pub fn new(schedule: &StSchedule, grace: Timestamp, dispatched_at: Timestamp) -> Self {
+        OverdueEvent {
+            id: schedule.get_id(),
+            planned_at: Timestamp::Millis(schedule.get_deadline_millis()),
+            grace,
+            dispatched_at,
+        }
+    }
+}
+
+impl ExpiredEvent {
+    /// Creates the event for the given schedule, whose current deadline is its last occurrence
+    pub fn new(schedule: &StSchedule, detected_at: Timestamp) -> Self {
+        ExpiredEvent {
+            id: schedule.get_id(),
+            last_planned_at: Timestamp::Millis(schedule.get_deadline_millis()),
+            until: schedule.get_until(),
+            count: schedule.get_count(),
+            detected_at,
+        }
+    }
+}
+
+impl ReminderEvent {
+    /// Creates the reminder for the current deadline of the given schedule, dispatched at the given time
+    pub fn new(schedule: &StSchedule, offset: Timestamp, dispatched_at: Timestamp) -> Self {
+        ReminderEvent {
+            id: schedule.get_id(),
+            planned_at: Timestamp::Millis(schedule.get_deadline_millis()),
+            offset,
+            dispatched_at,
+            local_time: schedule.get_local_deadline(),
+            timezone: schedule.get_timezone(),
+        }
+    }
+}
+
+impl DueEvent {
+    /// Creates the event for the current deadline of the given schedule, dispatched at the given time
+    pub fn new(schedule: &StSchedule, dispatched_at: Timestamp) -> Self {
+        let planned_at = Timestamp::Millis(schedule.get_deadline_millis());
+
+        DueEvent {
+            id: schedule.get_id(),
+            planned_at,
+            dispatched_at,
+            lateness: (dispatched_at - planned_at).max(Timestamp::Millis(0)),
+            occurrence: schedule.get_tracked_occurrence_index(),
+            priority: schedule.get_priority(),
+            local_time: schedule.get_local_deadline(),
+            timezone: schedule.get_timezone(),
+            is_final: schedule.is_final_occurrence(),
+            coalesced: vec![],
+            snoozed: false,
+        }
+    }
+}
+
+/// Creates a plain JavaScript object from the given entries
+pub(crate) fn to_js_object(entries: &[(&str, JsValue)]) -> JsValue {
+    let object = Object::new();
+
+    for (key, value) in entries.iter() {
+        // Setting a property on a fresh plain object cannot fail
+        Reflect::set(&object, &JsValue::from_str(key), value).unwrap();
+    }
+
+    object.into()
+}
+
+/// Creates a JavaScript array of the given timestamps in milliseconds
+fn to_js_millis(timestamps: &[Timestamp]) -> JsValue {
+    timestamps
+        .iter()
+        .map(|ts| JsValue::from(ts.as_ms_f64()))
+        .collect::<Array>()
+        .into()
+}
+
+impl From<&DueEvent> for JsValue {
+    /// Converts the event into a plain JavaScript object of the shape of `StDueEvent`
+    fn from(event: &DueEvent) -> Self {
+        to_js_object(&[
+            ("kind", "due".into()),
+            ("id", event.id.as_str().into()),
+            ("plannedAt", event.planned_at.as_ms_f64().into()),
+            ("dispatchedAt", event.dispatched_at.as_ms_f64().into()),
+            ("lateness", event.lateness.as_ms_f64().into()),
+            (
+                "occurrence",
+                event
+                    .occurrence
+                    .map_or(JsValue::UNDEFINED, |index| (index as f64).into()),
+            ),
+            (
+                "priority",
+                event.priority.map_or(JsValue::UNDEFINED, JsValue::from),
+            ),
+            (
+                "localTime",
+                event
+                    .local_time
+                    .format(LOCAL_TIME_FORMAT)
+                    .to_string()
+                    .into(),
+            ),
+            ("timezone", event.timezone.name().into()),
+            ("isFinal", event.is_final.into()),
+            ("coalesced", to_js_millis(&event.coalesced)),
+            ("snoozed", event.snoozed.into()),
+        ])
+    }
+}
+
+impl From<&MissedEvent> for JsValue {
+    /// Converts the event into a plain JavaScript object of the shape of `StMissedEvent`
+    fn from(event: &MissedEvent) -> Self {
+        to_js_object(&[
+            ("kind", "missed".into()),
+            ("id", event.id.as_str().into()),
+            ("missed", to_js_millis(&event.missed)),
+            ("dropped", (event.dropped as f64).into()),
+            ("detectedAt", event.detected_at.as_ms_f64().into()),
+            ("policy", event.policy.into()),
+        ])
+    }
+}
+
+impl From<&ReminderEvent> for JsValue {
+    /// Converts the event into a plain JavaScript object of the shape of `StReminderEvent`
+    fn from(event: &ReminderEvent) -> Self {
+        to_js_object(&[
+            ("kind", "reminder".into()),
+            ("id", event.id.as_str().into()),
+            ("plannedAt", event.planned_at.as_ms_f64().into()),
+            ("offset", event.offset.as_ms_f64().into()),
+            ("dispatchedAt", event.dispatched_at.as_ms_f64().into()),
+            (
+                "localTime",
+                event
+                    .local_time
+                    .format(LOCAL_TIME_FORMAT)
+                    .to_string()
+                    .into(),
+            ),
+            ("timezone", event.timezone.name().into()),
+        ])
+    }
+}
+
+impl From<&UpcomingEvent> for JsValue {
+    /// Converts the event into a plain JavaScript object of the shape of `StUpcomingEvent`
+    fn from(event: &UpcomingEvent) -> Self {
+        to_js_object(&[
+            ("kind", "upcoming".into()),
+            ("id", event.id.as_str().into()),
+            ("plannedAt", event.planned_at.as_ms_f64().into()),
+            (
+                "localTime",
+                event
+                    .local_time
+                    .format(LOCAL_TIME_FORMAT)
+                    .to_string()
+                    .into(),
+            ),
+            ("timezone", event.timezone.name().into()),
+        ])
+    }
+}
+
+impl From<&OverdueEvent> for JsValue {
+    /// Converts the event into a plain JavaScript object of the shape of `StOverdueEvent`
+    fn from(event: &OverdueEvent) -> Self {
+        to_js_object(&[
+            ("kind", "overdue".into()),
+            ("id", event.id.as_str().into()),
+            ("plannedAt", event.planned_at.as_ms_f64().into()),
+            ("grace", event.grace.as_ms_f64().into()),
+            ("dispatchedAt", event.dispatched_at.as_ms_f64().into()),
+        ])
+    }
+}
+
+impl From<&ExpiredEvent> for JsValue {
+    /// Converts the event into a plain JavaScript object of the shape of `StExpiredEvent`
+    fn from(event: &ExpiredEvent) -> Self {
+        to_js_object(&[
+            ("kind", "expired".into()),
+            ("id", event.id.as_str().into()),
+            ("lastPlannedAt", event.last_planned_at.as_ms_f64().into()),
+            (
+                "until",
+                event
+                    .until
+                    .map_or(JsValue::UNDEFINED, |u| u.as_ms_f64().into()),
+            ),
+            (
+                "count",
+                event.count.map_or(JsValue::UNDEFINED, JsValue::from),
+            ),
+            ("detectedAt", event.detected_at.as_ms_f64().into()),
+        ])
+    }
+}
+
+impl From<&SchedulerEvent> for JsValue {
+    /// Converts the event into a plain JavaScript object of the shape of `StSchedulerEvent`
+    fn from(event: &SchedulerEvent) -> Self {
+        match event {
+            SchedulerEvent::Upcoming(upcoming) => upcoming.into(),
+            SchedulerEvent::Reminder(reminder) => reminder.into(),
+            SchedulerEvent::Due(due) => due.into(),
+            SchedulerEvent::Missed(missed) => missed.into(),
+            SchedulerEvent::Overdue(overdue) => overdue.into(),
+            SchedulerEvent::Expired(expired) => expired.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    use crate::core::clock::FixedClock;
+    use crate::core::frequency::{StFrequencyType, StHourlyExpression, StRegularFrequency};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    const ANCHOR_MILLIS: i64 = 1730145955025; // 2024-10-28T21:05:55.025 Africa/Lagos
+
+    fn hourly_schedule(until: Option<u64>, now: Timestamp) -> StSchedule {
+        let expr = StHourlyExpression::new(1);
+        let freq = StRegularFrequency::new(StFrequencyType::Hour, expr, until);
+
+        StSchedule::with_regular_and_clock(
+            "id",
+            "2024-10-28T21:05:55.025",
+            "Africa/Lagos",
+            freq,
+            Some(StPriority::High),
+            &FixedClock::new(now),
+        )
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_due_event() {
+        let planned_at = Timestamp::Millis(ANCHOR_MILLIS) + Timestamp::from_hours(2.0);
+        let schedule = hourly_schedule(None, planned_at);
+        let event = DueEvent::new(&schedule, planned_at + Timestamp::Millis(30_000));
+
+        assert_eq!(event.id, "id");
+        assert_eq!(event.planned_at, planned_at);
+        assert_eq!(event.lateness, Timestamp::Millis(30_000));
+        assert_eq!(event.occurrence, Some(2));
+        assert_eq!(event.priority, Some(StPriority::High));
+        assert_eq!(
+            event.local_time.format(LOCAL_TIME_FORMAT).to_string(),
+            "2024-10-28T23:05:55.025"
+        );
+        assert_eq!(event.timezone, Tz::Africa__Lagos);
+        assert!(!event.is_final);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_due_event_final_occurrence() {
+        let planned_at = Timestamp::Millis(ANCHOR_MILLIS) + Timestamp::from_hours(2.0);
+        let until = planned_at + Timestamp::from_hours(1.0);
+        let schedule = hourly_schedule(Some(until.as_ms() as u64), planned_at);
+
+        // Dispatched ahead of the planned time, which is never considered late
+        let event = DueEvent::new(&schedule, planned_at - Timestamp::Millis(5));
+
+        assert_eq!(event.lateness, Timestamp::Millis(0));
+        assert!(event.is_final);
+    }
+}
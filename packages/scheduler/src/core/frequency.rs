@@ -3,6 +3,8 @@ use core::convert::TryInto;
 use core::convert::{Into, TryFrom};
 use core::fmt;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
 use crate::core::errors::ConversionError;
@@ -12,14 +14,30 @@ use crate::utils::{filter_unique, map_unique};
 
 use super::time::INDEXED_MONTH_DAYS;
 
+/// The most cron expressions of a custom frequency that are evaluated, the rest are redundant
+pub(crate) const MAX_CRON_EXPRESSIONS: usize = 3;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "lowercase"))]
 pub enum StFrequency {
     Regular(StRegularFrequency),
     Custom(StCustomFrequency),
 }
 
+impl StFrequency {
+    /// Returns the number of occurrences after which the frequency stops repeating, if it has one
+    pub fn get_count(&self) -> Option<u32> {
+        match self {
+            StFrequency::Regular(reg) => reg.count,
+            StFrequency::Custom(cstm) => cstm.count,
+        }
+    }
+}
+
 #[wasm_bindgen]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum StFrequencyType {
     Hour,
     Day,
@@ -31,6 +49,7 @@ pub enum StFrequencyType {
 
 #[wasm_bindgen]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum StOrdinals {
     First,
     Second,
@@ -42,6 +61,7 @@ pub enum StOrdinals {
 
 #[wasm_bindgen]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum StMonth {
     Jan,
     Feb,
@@ -59,6 +79,7 @@ pub enum StMonth {
 
 #[wasm_bindgen]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// Constant weekdays that are statically known and require no computation
 pub enum StConstWeekday {
     Sun,
@@ -72,6 +93,7 @@ pub enum StConstWeekday {
 
 #[wasm_bindgen]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// Varaible weekdays that require dynamic computation when combined with ordinals
 pub enum StVarWeekday {
     /// Any weekday
@@ -83,12 +105,16 @@ pub enum StVarWeekday {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum StWeekday {
     Const(StConstWeekday),
     Var(StVarWeekday),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "lowercase"))]
 pub enum StFrequencyExpression {
     Hourly(StHourlyExpression),
     Daily(StDailyExpression),
@@ -98,6 +124,8 @@ pub enum StFrequencyExpression {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
 pub enum StMonthlySubExpression {
     OnDays(StMonthlyOnDaysSubExpression),
     OnThe(StMonthlyOnTheSubExpression),
@@ -105,24 +133,28 @@ pub enum StMonthlySubExpression {
 
 #[wasm_bindgen]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StHourlyExpression {
     pub every: u32,
 }
 
 #[wasm_bindgen]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StDailyExpression {
     pub every: u32,
 }
 
 #[wasm_bindgen]
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StWeeklySubExpression {
     pub(crate) weekdays: Vec<StConstWeekday>,
 }
 
 #[wasm_bindgen]
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StWeeklyExpression {
     pub every: u32,
     pub(crate) subexpr: StWeeklySubExpression,
@@ -130,12 +162,14 @@ pub struct StWeeklyExpression {
 
 #[wasm_bindgen]
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StMonthlyOnDaysSubExpression {
     pub(crate) days: Vec<u32>,
 }
 
 #[wasm_bindgen]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StMonthlyOnTheSubExpression {
     pub ordinal: StOrdinals,
     pub weekday: StConstWeekday,
@@ -143,6 +177,7 @@ pub struct StMonthlyOnTheSubExpression {
 
 #[wasm_bindgen]
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StMonthlyExpression {
     pub every: u32,
     pub(crate) subexpr: StMonthlySubExpression,
@@ -150,12 +185,14 @@ pub struct StMonthlyExpression {
 
 #[wasm_bindgen]
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StYearlyInSubExpression {
     months: Vec<StMonth>,
 }
 
 #[wasm_bindgen]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StYearlyOnTheSubExpression {
     pub(crate) ordinal: StOrdinals,
     pub(crate) weekday: StWeekday,
@@ -163,6 +200,7 @@ pub struct StYearlyOnTheSubExpression {
 
 #[wasm_bindgen]
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StYearlySubExpression {
     pub(crate) months: Vec<StMonth>,
     pub(crate) on: Option<StYearlyOnTheSubExpression>,
@@ -170,6 +208,7 @@ pub struct StYearlySubExpression {
 
 #[wasm_bindgen]
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StYearlyExpression {
     pub every: u32,
     pub(crate) subexpr: StYearlySubExpression,
@@ -177,17 +216,25 @@ pub struct StYearlyExpression {
 
 #[wasm_bindgen]
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StRegularFrequency {
     pub ftype: StFrequencyType,
     pub(crate) until: Option<Timestamp>,
+    /// The number of occurrences, from the anchor, after which the frequency stops repeating
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) count: Option<u32>,
     pub(crate) expr: StFrequencyExpression,
 }
 
 #[wasm_bindgen]
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StCustomFrequency {
     pub ftype: StFrequencyType,
     pub(crate) until: Option<Timestamp>,
+    /// The number of occurrences, from the anchor, after which the frequency stops repeating
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) count: Option<u32>,
     pub(crate) cron_expressions: Vec<String>,
 }
 
@@ -365,6 +412,25 @@ impl fmt::Display for StConstWeekday {
     }
 }
 
+impl fmt::Display for StVarWeekday {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            StVarWeekday::Day => "Day",
+            StVarWeekday::Weekday => "Weekday",
+            StVarWeekday::Weekend => "Weekend day",
+        })
+    }
+}
+
+impl fmt::Display for StWeekday {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StWeekday::Const(weekday) => weekday.fmt(f),
+            StWeekday::Var(weekday) => weekday.fmt(f),
+        }
+    }
+}
+
 impl Ord for StConstWeekday {
     fn cmp(&self, other: &Self) -> Ordering {
         let self_value: u32 = (*self).into();
@@ -411,17 +477,17 @@ impl From<u32> for StMonth {
     fn from(value: u32) -> Self {
         match value % 12 {
             0 => StMonth::Jan,
-            1 => StMonth::Jan,
-            2 => StMonth::Jan,
-            3 => StMonth::Jan,
-            4 => StMonth::Jan,
-            5 => StMonth::Jan,
-            6 => StMonth::Jan,
-            7 => StMonth::Jan,
-            8 => StMonth::Jan,
-            9 => StMonth::Jan,
-            10 => StMonth::Jan,
-            11 => StMonth::Jan,
+            1 => StMonth::Feb,
+            2 => StMonth::Mar,
+            3 => StMonth::Apr,
+            4 => StMonth::May,
+            5 => StMonth::Jun,
+            6 => StMonth::Jul,
+            7 => StMonth::Aug,
+            8 => StMonth::Sep,
+            9 => StMonth::Oct,
+            10 => StMonth::Nov,
+            11 => StMonth::Dec,
             _ => panic!("This should never happen"),
         }
     }
@@ -516,7 +582,7 @@ impl StWeeklySubExpression {
         let weekdays = map_unique(&weekdays, |w| w % 7)
             .into_iter()
             .take(7)
-            .map(|w| w.try_into().unwrap())
+            .map(|w| w.into())
             .collect::<Vec<_>>();
 
         StWeeklySubExpression { weekdays }
@@ -648,7 +714,6 @@ impl StYearlyInSubExpression {
         StYearlyInSubExpression { months }
     }
 
-    #[inline]
     pub fn get_months(&self) -> Vec<StMonth> {
         self.months.to_owned()
     }
@@ -675,7 +740,6 @@ impl StYearlyOnTheSubExpression {
         }
     }
 
-    #[inline]
     pub fn get_ordinal(&self) -> StOrdinals {
         self.ordinal
     }
@@ -744,12 +808,10 @@ impl StYearlySubExpression {
         }
     }
 
-    #[inline]
     pub fn get_in_expr(&self) -> Vec<StMonth> {
         self.months.to_owned()
     }
 
-    #[inline]
     pub fn get_on_expr(&self) -> Option<StYearlyOnTheSubExpression> {
         self.on.to_owned()
     }
@@ -805,6 +867,7 @@ impl StRegularFrequency {
         StRegularFrequency {
             ftype,
             until: until.map(|ms| Timestamp::Millis(ms as i64)),
+            count: None,
             expr: StFrequencyExpression::Hourly(expr),
         }
     }
@@ -817,6 +880,7 @@ impl StRegularFrequency {
         StRegularFrequency {
             ftype,
             until: until.map(|ms| Timestamp::Millis(ms as i64)),
+            count: None,
             expr: StFrequencyExpression::Daily(expr),
         }
     }
@@ -829,6 +893,7 @@ impl StRegularFrequency {
         StRegularFrequency {
             ftype,
             until: until.map(|ms| Timestamp::Millis(ms as i64)),
+            count: None,
             expr: StFrequencyExpression::Weekly(expr),
         }
     }
@@ -841,6 +906,7 @@ impl StRegularFrequency {
         StRegularFrequency {
             ftype,
             until: until.map(|ms| Timestamp::Millis(ms as i64)),
+            count: None,
             expr: StFrequencyExpression::Monthly(expr),
         }
     }
@@ -853,10 +919,21 @@ impl StRegularFrequency {
         StRegularFrequency {
             ftype,
             until: until.map(|ms| Timestamp::Millis(ms as i64)),
+            count: None,
             expr: StFrequencyExpression::Yearly(expr),
         }
     }
 
+    pub fn get_count(&self) -> Option<u32> {
+        self.count
+    }
+
+    /// Sets the number of occurrences, counting the anchor, after which the frequency stops repeating, or
+    /// removes it when `None`. A count of zero is taken as one, since the anchor always occurs.
+    pub fn set_count(&mut self, count: Option<u32>) {
+        self.count = count.map(|count| count.max(1));
+    }
+
     #[inline]
     pub(crate) fn get_expr(&self) -> &StFrequencyExpression {
         &self.expr
@@ -905,6 +982,7 @@ impl StCustomFrequency {
         StCustomFrequency {
             ftype: StFrequencyType::Custom,
             until: until.map(|ms| Timestamp::Millis(ms as i64)),
+            count: None,
             cron_expressions,
         }
     }
@@ -912,4 +990,35 @@ impl StCustomFrequency {
     pub fn get_cron_expressions(&self) -> Vec<String> {
         self.cron_expressions.clone()
     }
+
+    pub fn get_count(&self) -> Option<u32> {
+        self.count
+    }
+
+    /// Same as [`StRegularFrequency::set_count`]
+    pub fn set_count(&mut self, count: Option<u32>) {
+        self.count = count.map(|count| count.max(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_month_from_value() {
+        for value in 0..12 {
+            assert_eq!(u32::from(StMonth::from(value)), value);
+        }
+
+        let subexpr = StYearlyInSubExpression::new(vec![6, 2, 18]);
+        let mut months = subexpr.get_months();
+        months.sort();
+
+        assert_eq!(months, vec![StMonth::Mar, StMonth::Jul]);
+    }
 }
@@ -0,0 +1,1283 @@
+use core::fmt;
+use core::fmt::Display;
+
+use chrono::prelude::*;
+use chrono::TimeDelta;
+use chrono_tz::{OffsetComponents, OffsetName, Tz, TzOffset};
+use wasm_bindgen::prelude::*;
+
+use crate::core::clock::{Clock, SystemClock};
+use crate::core::errors::{IcsError, RRuleError, TimingError};
+use crate::core::event::to_js_object;
+use crate::core::frequency::{StFrequency, StFrequencyExpression, StRegularFrequency};
+use crate::core::schedule::StSchedule;
+use crate::core::time::{Timestamp, Ts};
+
+/// The identifier of the product that writes the calendars
+const PRODID: &str = "-//Stitches//Scheduler//EN";
+
+/// The format of wall times, which are qualified by a `TZID`
+const LOCAL_FORMAT: &str = "%Y%m%dT%H%M%S";
+
+/// The format of times in UTC
+const UTC_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// The longest a line can be, in octets, before it is folded
+const LINE_LIMIT: usize = 75;
+
+/// How many years of timezone transitions are written past the later of a schedule's anchor and the export
+const TIMEZONE_YEARS: i32 = 10;
+
+/// How far past the export the occurrences of a schedule are listed when its frequency cannot be written as a
+/// rule, in days
+const RDATE_HORIZON_DAYS: f64 = 366.0;
+
+/// The most occurrences listed for a schedule whose frequency cannot be written as a rule
+const RDATE_LIMIT: usize = 1000;
+
+/// The properties of imported events that affect when they occur but have no equivalent in schedules
+const UNSUPPORTED_PROPERTIES: &[&str] = &["EXRULE", "RECURRENCE-ID"];
+
+#[wasm_bindgen]
+impl StSchedule {
+    /// Writes the schedule as an iCalendar file with a single event, see [`export_ics`]
+    pub fn to_ics(&self) -> String {
+        export_ics(core::slice::from_ref(self))
+    }
+}
+
+/// Writes the given schedules as an iCalendar file, for calendar apps to import, see [`export_ics`]
+#[wasm_bindgen(js_name = export_ics)]
+pub fn export_ics_js(schedules: Vec<StSchedule>) -> String {
+    export_ics(&schedules)
+}
+
+/// Writes the given schedules as an iCalendar (RFC 5545) file, with an event for each schedule and a timezone
+/// for each timezone they are in.
+///
+/// The event of a schedule is identified by its ID and starts at its anchor, in its timezone, and repeats with
+/// the `RRULE` of its frequency, along with the times it is excluded from and included at as `EXDATE` and `RDATE`.
+/// When its frequency cannot be written as a rule, e.g. because it is a cron, the event lists its occurrences from
+/// now up to a year from now with `RDATE` instead. So does a daily or weekly schedule in a timezone with daylight saving time,
+/// as calendar apps repeat a `DAILY` or `WEEKLY` rule at the same wall time while the schedule repeats every 24 hours
+/// or 7 days.
+///
+/// Times are written to the second, which is as precise as iCalendar gets, so the milliseconds of the anchor and of
+/// the excluded and included times are lost, and the export is imported back at the whole seconds before them.
+pub fn export_ics(schedules: &[StSchedule]) -> String {
+    export_ics_with(schedules, &SystemClock)
+}
+
+/// Same as [`export_ics`] but the time of the export is read from the given [`Clock`]
+pub fn export_ics_with(schedules: &[StSchedule], clock: &dyn Clock) -> String {
+    let now = clock.now();
+    let mut lines = vec![
+        String::from("BEGIN:VCALENDAR"),
+        String::from("VERSION:2.0"),
+        format!("PRODID:{}", PRODID),
+        String::from("CALSCALE:GREGORIAN"),
+    ];
+
+    // The years each timezone's transitions are needed for, by the name of the timezone
+    let mut timezones: Vec<(Tz, i32, i32)> = vec![];
+    for schedule in schedules {
+        let tz = schedule.get_timezone();
+        let first = schedule.get_local_anchor().year();
+        let last = first.max(now.to_datetime().year()) + TIMEZONE_YEARS;
+
+        match timezones.iter_mut().find(|(other, _, _)| *other == tz) {
+            Some((_, from, to)) => {
+                *from = (*from).min(first);
+                *to = (*to).max(last);
+            }
+            None => timezones.push((tz, first, last)),
+        }
+    }
+    timezones.sort_by_key(|(tz, _, _)| tz.name());
+
+    for (tz, first, last) in timezones {
+        write_timezone(&mut lines, tz, first, last);
+    }
+    for schedule in schedules {
+        write_event(&mut lines, schedule, now);
+    }
+
+    lines.push(String::from("END:VCALENDAR"));
+    lines.iter().map(|line| fold(line)).collect()
+}
+
+fn write_event(lines: &mut Vec<String>, schedule: &StSchedule, now: Timestamp) {
+    let tz = schedule.get_timezone();
+    let anchor = Timestamp::Millis(schedule.get_anchor_millis());
+    // A rule cannot roll its occurrences to business days, so a schedule with a holiday calendar starts at its
+    // first occurrence and lists the rest
+    let (start, rolled) = match schedule.get_business_days() {
+        Some(_) => match schedule.first_occurrence() {
+            Some(first) => (Timestamp::Millis(first.get_deadline_millis()), true),
+            None => (anchor, true),
+        },
+        None => (anchor, false),
+    };
+    let local_start = start.to_datetime().with_timezone(&tz).naive_local();
+
+    lines.push(String::from("BEGIN:VEVENT"));
+    lines.push(format!("UID:{}", escape_text(schedule.get_id_as_str())));
+    lines.push(format!("DTSTAMP:{}", now.to_datetime().format(UTC_FORMAT)));
+    lines.push(format!(
+        "DTSTART;TZID={}:{}",
+        tz.name(),
+        local_start.format(LOCAL_FORMAT)
+    ));
+
+    let rule = match schedule.get_frequency() {
+        Some(StFrequency::Regular(reg)) if !rolled && !drifts(reg, tz, start) => {
+            reg.to_rrule().ok()
+        }
+        _ => None,
+    };
+
+    match (&rule, schedule.get_frequency()) {
+        (Some(rule), _) => lines.push(format!("RRULE:{}", rule)),
+        (None, Some(_)) => {
+            // The occurrences that already passed by the export are left out, so an old anchor does not use up
+            // the occurrences listed
+            let end = now.max(start) + Timestamp::from_days(RDATE_HORIZON_DAYS);
+            let dates = schedule
+                .occurrences((start + Timestamp::Millis(1)).max(now), end)
+                .take(RDATE_LIMIT)
+                .map(|occurrence| {
+                    occurrence
+                        .get_local_deadline()
+                        .format(LOCAL_FORMAT)
+                        .to_string()
+                })
+                .collect::<Vec<_>>();
+
+            if !dates.is_empty() {
+                lines.push(format!("RDATE;TZID={}:{}", tz.name(), dates.join(",")));
+            }
+        }
+        (None, None) => {}
+    }
+
+    // The occurrences listed in place of a rule already include the included times
+    let dates = schedule.get_dates();
+    if rule.is_some() || schedule.get_frequency().is_none() {
+        push_date_times(lines, "RDATE", tz, dates.get_included());
+    }
+    push_date_times(lines, "EXDATE", tz, dates.get_excluded());
+
+    lines.push(String::from("END:VEVENT"));
+}
+
+/// Whether the occurrences of the frequency drift off the wall time of a rule written for it, because it repeats in
+/// fixed steps of days while the offset of the timezone changes within the years its transitions are written for
+fn drifts(freq: &StRegularFrequency, tz: Tz, start: Timestamp) -> bool {
+    match freq.expr {
+        StFrequencyExpression::Daily(_) | StFrequencyExpression::Weekly(_) => {}
+        _ => return false,
+    }
+
+    let start = start.to_datetime();
+    let offset = tz.offset_from_utc_datetime(&start.naive_utc()).fix();
+    let end = start + TimeDelta::days(365 * TIMEZONE_YEARS as i64);
+
+    let mut at = start;
+    while at < end {
+        at += TimeDelta::days(1);
+        if tz.offset_from_utc_datetime(&at.naive_utc()).fix() != offset {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Writes a property listing the given times as wall times in the timezone, unless there are none
+fn push_date_times(lines: &mut Vec<String>, name: &str, tz: Tz, times: &[Timestamp]) {
+    if times.is_empty() {
+        return;
+    }
+
+    let times = times
+        .iter()
+        .map(|time| {
+            let local = time.to_datetime().with_timezone(&tz).naive_local();
+            local.format(LOCAL_FORMAT).to_string()
+        })
+        .collect::<Vec<_>>();
+    lines.push(format!("{};TZID={}:{}", name, tz.name(), times.join(",")));
+}
+
+/// Writes the timezone with its transitions from the start of the first year through the end of the last
+fn write_timezone(lines: &mut Vec<String>, tz: Tz, first_year: i32, last_year: i32) {
+    let start = Utc.with_ymd_and_hms(first_year, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(last_year + 1, 1, 1, 0, 0, 0).unwrap();
+    let offset_at = |ts: i64| {
+        tz.offset_from_utc_datetime(&DateTime::from_timestamp(ts, 0).unwrap().naive_utc())
+    };
+
+    lines.push(String::from("BEGIN:VTIMEZONE"));
+    lines.push(format!("TZID:{}", tz.name()));
+
+    // The offset in effect at the start, as an observance of its own
+    let mut offset = offset_at(start.timestamp());
+    write_observance(lines, start.timestamp(), &offset, &offset);
+
+    let mut day = start.timestamp();
+    while day < end.timestamp() {
+        let next_day = day + TimeDelta::days(1).num_seconds();
+
+        if !same_offset(&offset_at(next_day), &offset) {
+            // The transition is the first second with the new offset
+            let (mut before, mut after) = (day, next_day);
+            while after - before > 1 {
+                let middle = before + (after - before) / 2;
+                match same_offset(&offset_at(middle), &offset) {
+                    true => before = middle,
+                    false => after = middle,
+                }
+            }
+
+            let next = offset_at(after);
+            write_observance(lines, after, &offset, &next);
+            offset = next;
+        }
+
+        day = next_day;
+    }
+
+    lines.push(String::from("END:VTIMEZONE"));
+}
+
+/// Writes the observance of the offset that comes into effect at the given time, in seconds, replacing another
+fn write_observance(lines: &mut Vec<String>, at: i64, from: &TzOffset, to: &TzOffset) {
+    let kind = match to.dst_offset().is_zero() {
+        true => "STANDARD",
+        false => "DAYLIGHT",
+    };
+    // The onset is written as a wall time in the offset that was in effect before it
+    let onset = DateTime::from_timestamp(at, 0).unwrap().naive_utc()
+        + TimeDelta::seconds(from.fix().local_minus_utc() as i64);
+
+    lines.push(format!("BEGIN:{}", kind));
+    lines.push(format!("DTSTART:{}", onset.format(LOCAL_FORMAT)));
+    lines.push(format!("TZOFFSETFROM:{}", format_offset(from)));
+    lines.push(format!("TZOFFSETTO:{}", format_offset(to)));
+    if let Some(name) = to.abbreviation() {
+        lines.push(format!("TZNAME:{}", escape_text(name)));
+    }
+    lines.push(format!("END:{}", kind));
+}
+
+fn same_offset(offset: &TzOffset, other: &TzOffset) -> bool {
+    offset.fix() == other.fix() && offset.abbreviation() == other.abbreviation()
+}
+
+/// Formats the offset from UTC as e.g. "+0100" or "-0430", with seconds only when it has some
+fn format_offset(offset: &TzOffset) -> String {
+    let seconds = offset.fix().local_minus_utc();
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.abs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    match seconds {
+        0 => format!("{}{:02}{:02}", sign, hours, minutes),
+        _ => format!("{}{:02}{:02}{:02}", sign, hours, minutes, seconds),
+    }
+}
+
+/// Escapes the characters of a text value that have a meaning in iCalendar
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Ends the line with CRLF, and folds it onto lines that start with a space when it is too long, without
+/// splitting a character
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 2);
+    let mut length = 0;
+
+    for char in line.chars() {
+        if length + char.len_utf8() > LINE_LIMIT {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(char);
+        length += char.len_utf8();
+    }
+
+    folded.push_str("\r\n");
+    folded
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_ST_ICS_EVENT_REPORT: &str = r#"
+/**
+ * What became of an event of an imported iCalendar file
+ */
+export interface StIcsEventReport {
+  /** The UID of the event, if it has one */
+  uid?: string;
+  /** The ID of the schedule imported from the event, if one could be */
+  scheduleId?: string;
+  /** What of the event could not be imported, in the order it was found */
+  issues: { kind: string; message: string }[];
+}
+"#;
+
+/// The schedules imported from an iCalendar file with [`import_ics`], along with a report for each event
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct StIcsImport {
+    schedules: Vec<StSchedule>,
+    reports: Vec<IcsEventReport>,
+}
+
+/// What became of an event of an imported iCalendar file
+#[derive(Debug)]
+pub struct IcsEventReport {
+    /// The UID of the event, if it has one
+    pub uid: Option<String>,
+    /// The ID of the schedule imported from the event, if one could be
+    pub schedule_id: Option<String>,
+    /// What of the event could not be imported, in the order it was found
+    pub issues: Vec<IcsIssue>,
+}
+
+/// Something in an event of an imported iCalendar file that schedules have no equivalent for
+#[derive(Debug)]
+pub enum IcsIssue {
+    /// The event has no such property, e.g. no `UID`, in which case an ID is made up from its position.
+    /// `MissingProperty(name)`
+    MissingProperty(&'static str),
+    /// The property has a value that is not valid for it.
+    /// `InvalidValue(name, value)`
+    InvalidValue(String, String),
+    /// The `TZID` is not the name of a known IANA timezone.
+    /// `UnknownTimezone(tzid)`
+    UnknownTimezone(String),
+    /// The property has no equivalent in schedules, e.g. `EXRULE`, so it is left out.
+    /// `UnsupportedProperty(name)`
+    UnsupportedProperty(String),
+    /// The component nested in the event has no equivalent in schedules, e.g. `VALARM`, so it is left out.
+    /// `UnsupportedComponent(name)`
+    UnsupportedComponent(String),
+    /// The `RRULE` of the event cannot be read as a frequency.
+    /// `UnsupportedRule(error)`
+    UnsupportedRule(RRuleError),
+    /// Another event has the same UID, so they would make schedules with the same ID.
+    /// `DuplicateId(id)`
+    DuplicateId(String),
+    /// The schedule could not be timed, e.g. because its rule ended before now.
+    /// `Timing(error)`
+    Timing(TimingError),
+}
+
+impl Display for IcsIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            IcsIssue::MissingProperty(name) => write!(f, "The event has no {} property", name),
+            IcsIssue::InvalidValue(name, value) => {
+                write!(f, "'{}' is not a valid value for {}", value, name)
+            }
+            IcsIssue::UnknownTimezone(tzid) => write!(f, "The timezone '{}' is not known", tzid),
+            IcsIssue::UnsupportedProperty(name) => {
+                write!(f, "The {} property is not supported", name)
+            }
+            IcsIssue::UnsupportedComponent(name) => {
+                write!(f, "The {} component is not supported", name)
+            }
+            IcsIssue::UnsupportedRule(err) => write!(f, "{}", err),
+            IcsIssue::DuplicateId(id) => write!(f, "Another event has the UID '{}'", id),
+            IcsIssue::Timing(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl IcsIssue {
+    /// Gets the name of the kind of issue, as given to JavaScript
+    pub fn kind(&self) -> &'static str {
+        match self {
+            IcsIssue::MissingProperty(_) => "missingProperty",
+            IcsIssue::InvalidValue(_, _) => "invalidValue",
+            IcsIssue::UnknownTimezone(_) => "unknownTimezone",
+            IcsIssue::UnsupportedProperty(_) => "unsupportedProperty",
+            IcsIssue::UnsupportedComponent(_) => "unsupportedComponent",
+            IcsIssue::UnsupportedRule(_) => "unsupportedRule",
+            IcsIssue::DuplicateId(_) => "duplicateId",
+            IcsIssue::Timing(_) => "timing",
+        }
+    }
+}
+
+impl From<&IcsEventReport> for JsValue {
+    /// Converts the report into a plain JavaScript object of the shape of `StIcsEventReport`
+    fn from(report: &IcsEventReport) -> Self {
+        let issues = report
+            .issues
+            .iter()
+            .map(|issue| {
+                to_js_object(&[
+                    ("kind", issue.kind().into()),
+                    ("message", issue.to_string().into()),
+                ])
+            })
+            .collect::<js_sys::Array>();
+
+        to_js_object(&[
+            (
+                "uid",
+                report
+                    .uid
+                    .as_deref()
+                    .map_or(JsValue::UNDEFINED, JsValue::from),
+            ),
+            (
+                "scheduleId",
+                report
+                    .schedule_id
+                    .as_deref()
+                    .map_or(JsValue::UNDEFINED, JsValue::from),
+            ),
+            ("issues", issues.into()),
+        ])
+    }
+}
+
+#[wasm_bindgen]
+impl StIcsImport {
+    /// Gets the schedules imported, in the order of their events
+    pub fn get_schedules(&self) -> Vec<StSchedule> {
+        self.schedules.clone()
+    }
+
+    /// Gets the reports of the events, in order, as an array of `StIcsEventReport` objects
+    pub fn get_reports(&self) -> js_sys::Array {
+        self.reports.iter().map(JsValue::from).collect()
+    }
+}
+
+impl StIcsImport {
+    pub fn schedules(&self) -> &[StSchedule] {
+        &self.schedules
+    }
+
+    pub fn reports(&self) -> &[IcsEventReport] {
+        &self.reports
+    }
+
+    pub fn into_schedules(self) -> Vec<StSchedule> {
+        self.schedules
+    }
+}
+
+/// Reads the events and to-dos of an iCalendar file as schedules, see [`import_ics`]
+#[wasm_bindgen(js_name = import_ics)]
+pub fn import_ics_js(ics: &str, timezone: &str) -> Result<StIcsImport, IcsError> {
+    import_ics(ics, timezone)
+}
+
+/// Reads the events and to-dos of an iCalendar (RFC 5545) file as schedules, e.g. ones exported by another
+/// calendar or to-do app.
+///
+/// Each schedule is identified by the UID of its event and anchored at its `DTSTART`, or the `DUE` of a to-do
+/// without one, and repeats with the frequency read from its `RRULE`, excluded from the times of its `EXDATE` and
+/// included at the ones of its `RDATE`. Starts that are dates or floating times are read in the given timezone.
+///
+/// Whatever schedules have no equivalent for is left out and listed in the report of its event, and so are the
+/// reasons an event could not be imported at all, e.g. a rule that cannot be read as a frequency.
+///
+/// # Error
+///
+/// Returns an [`IcsError`] when the file is not an iCalendar file, or the given timezone is not known.
+pub fn import_ics(ics: &str, timezone: &str) -> Result<StIcsImport, IcsError> {
+    import_ics_with(ics, timezone, &SystemClock)
+}
+
+/// Same as [`import_ics`] but the schedules are timed relative to the current timestamp of the given [`Clock`]
+pub fn import_ics_with(
+    ics: &str,
+    timezone: &str,
+    clock: &dyn Clock,
+) -> Result<StIcsImport, IcsError> {
+    let floating_tz = timezone
+        .parse::<Tz>()
+        .map_err(|_| IcsError::UnknownTimezone(timezone.into()))?;
+    let calendar = Component::parse(ics)?;
+
+    let mut schedules: Vec<StSchedule> = vec![];
+    let mut reports = vec![];
+    let events = calendar
+        .components
+        .iter()
+        .filter(|component| matches!(component.name.as_str(), "VEVENT" | "VTODO"));
+
+    for (position, event) in events.enumerate() {
+        let mut report = IcsEventReport {
+            uid: event.get("UID").map(|uid| unescape_text(&uid.value)),
+            schedule_id: None,
+            issues: vec![],
+        };
+
+        match import_event(event, position, floating_tz, clock, &mut report.issues) {
+            Some(schedule)
+                if schedules
+                    .iter()
+                    .any(|other| other.get_id_as_str() == schedule.get_id_as_str()) =>
+            {
+                report
+                    .issues
+                    .push(IcsIssue::DuplicateId(schedule.get_id_as_str().into()));
+            }
+            Some(schedule) => {
+                report.schedule_id = Some(schedule.get_id_as_str().into());
+                schedules.push(schedule);
+            }
+            None => {}
+        }
+
+        reports.push(report);
+    }
+
+    Ok(StIcsImport { schedules, reports })
+}
+
+/// Reads the event as a schedule, recording what could not be read, or why there is no schedule
+fn import_event(
+    event: &Component,
+    position: usize,
+    floating_tz: Tz,
+    clock: &dyn Clock,
+    issues: &mut Vec<IcsIssue>,
+) -> Option<StSchedule> {
+    let id = match event.get("UID") {
+        Some(uid) => unescape_text(&uid.value),
+        None => {
+            issues.push(IcsIssue::MissingProperty("UID"));
+            format!("event-{}", position + 1)
+        }
+    };
+
+    for component in &event.components {
+        issues.push(IcsIssue::UnsupportedComponent(component.name.clone()));
+    }
+
+    // Only the first rule is read, as a frequency cannot combine several
+    let mut rules = event.properties.iter().filter(|p| p.name == "RRULE");
+    let rule = rules.next();
+    let unsupported = event
+        .properties
+        .iter()
+        .filter(|p| UNSUPPORTED_PROPERTIES.contains(&p.name.as_str()));
+    for property in rules.chain(unsupported) {
+        issues.push(IcsIssue::UnsupportedProperty(property.name.clone()));
+    }
+    // An event with a `RECURRENCE-ID` only overrides an occurrence of another event with the same UID
+    if event.get("RECURRENCE-ID").is_some() {
+        return None;
+    }
+
+    // A to-do may only have a due date instead of a start
+    let start = match event.name.as_str() {
+        "VTODO" => event.get("DTSTART").or_else(|| event.get("DUE")),
+        _ => event.get("DTSTART"),
+    };
+    let start = match start {
+        Some(start) => start,
+        None => {
+            issues.push(IcsIssue::MissingProperty("DTSTART"));
+            return None;
+        }
+    };
+    let (anchor, tz) = match read_date_time(start, &start.value, floating_tz) {
+        Ok(start) => start,
+        Err(issue) => {
+            issues.push(issue);
+            return None;
+        }
+    };
+
+    let frequency = match rule {
+        Some(rule) => match StRegularFrequency::from_rrule(&localize_until(&rule.value, tz)) {
+            Ok(frequency) => Some(StFrequency::Regular(frequency)),
+            Err(err) => {
+                issues.push(IcsIssue::UnsupportedRule(err));
+                return None;
+            }
+        },
+        None => None,
+    };
+
+    let excluded = read_occurrence_dates(event, "EXDATE", anchor.time(), tz, issues);
+    let included = read_occurrence_dates(event, "RDATE", anchor.time(), tz, issues);
+
+    let anchor = anchor.format("%Y-%m-%dT%H:%M:%S").to_string();
+    let mut schedule =
+        StSchedule::try_with_frequency(&id, &anchor, tz.name(), frequency, None, clock)
+            .map_err(|err| issues.push(IcsIssue::Timing(err)))
+            .ok()?;
+
+    for at in excluded {
+        schedule.add_exclusion_with(at, clock);
+    }
+    for at in included {
+        schedule.add_inclusion_with(at, clock);
+    }
+
+    Some(schedule)
+}
+
+/// Reads the times listed by the event's properties with the given name, e.g. `EXDATE`, recording the ones that
+/// could not be read.
+///
+/// Dates are taken to be at the time of day of the start, and floating times to be in the timezone of the start.
+fn read_occurrence_dates(
+    event: &Component,
+    name: &str,
+    start_time: NaiveTime,
+    tz: Tz,
+    issues: &mut Vec<IcsIssue>,
+) -> Vec<Timestamp> {
+    let mut times = vec![];
+
+    for property in event.properties.iter().filter(|p| p.name == name) {
+        // Periods have a duration, which occurrences do not
+        if property.param("VALUE") == Some("PERIOD") {
+            issues.push(IcsIssue::UnsupportedProperty(format!(
+                "{};VALUE=PERIOD",
+                name
+            )));
+            continue;
+        }
+
+        for value in property.value.split(',') {
+            let time = read_date_time(property, value, tz).and_then(|(local, tz)| {
+                let local = match value.contains('T') {
+                    true => local,
+                    false => local.date().and_time(start_time),
+                };
+                tz.from_local_datetime(&local)
+                    .earliest()
+                    .map(|time| time.to_utc().to_timestamp())
+                    .ok_or_else(|| IcsIssue::InvalidValue(name.into(), value.into()))
+            });
+
+            match time {
+                Ok(time) => times.push(time),
+                Err(issue) => issues.push(issue),
+            }
+        }
+    }
+
+    times
+}
+
+/// Reads a value of a date-time property as a wall time in its timezone, which is UTC for a time that ends with
+/// "Z", or the given timezone for a date or a floating time
+fn read_date_time(
+    property: &Property,
+    value: &str,
+    floating_tz: Tz,
+) -> Result<(NaiveDateTime, Tz), IcsIssue> {
+    let value = value.trim();
+    let invalid = || IcsIssue::InvalidValue(property.name.clone(), value.into());
+
+    if let Some(utc) = value.strip_suffix('Z') {
+        let utc = NaiveDateTime::parse_from_str(utc, LOCAL_FORMAT).map_err(|_| invalid())?;
+        return Ok((utc, Tz::UTC));
+    }
+
+    let local = NaiveDateTime::parse_from_str(value, LOCAL_FORMAT)
+        .or_else(|_| {
+            NaiveDate::parse_from_str(value, "%Y%m%d").map(|date| date.and_time(NaiveTime::MIN))
+        })
+        .map_err(|_| invalid())?;
+    let tz = match property.param("TZID") {
+        Some(tzid) => {
+            resolve_timezone(tzid).ok_or_else(|| IcsIssue::UnknownTimezone(tzid.into()))?
+        }
+        None => floating_tz,
+    };
+
+    Ok((local, tz))
+}
+
+/// Finds the IANA timezone a `TZID` names, which some apps prefix with a path of their own, e.g.
+/// "/mozilla.org/20050126_1/Europe/Berlin"
+fn resolve_timezone(tzid: &str) -> Option<Tz> {
+    let tzid = tzid.trim();
+    core::iter::once(tzid)
+        .chain(tzid.match_indices('/').map(|(i, _)| &tzid[i + 1..]))
+        .find_map(|name| name.parse::<Tz>().ok())
+}
+
+/// Turns an `UNTIL` that is a wall time, which some apps write despite the start having a timezone, into the UTC
+/// time rules require
+fn localize_until(rule: &str, tz: Tz) -> String {
+    let parts = rule.split(';').map(|part| match part.split_once('=') {
+        Some((name, value)) if name.trim().eq_ignore_ascii_case("UNTIL") => {
+            let until = NaiveDateTime::parse_from_str(value.trim(), LOCAL_FORMAT)
+                .ok()
+                .and_then(|local| tz.from_local_datetime(&local).earliest());
+            match until {
+                Some(until) => format!("{}={}", name, until.with_timezone(&Utc).format(UTC_FORMAT)),
+                None => part.into(),
+            }
+        }
+        _ => part.into(),
+    });
+
+    parts.collect::<Vec<_>>().join(";")
+}
+
+/// A content line of an iCalendar file, e.g. "DTSTART;TZID=Africa/Lagos:20250115T090000"
+#[derive(Debug)]
+struct Property {
+    /// The name, in upper case
+    name: String,
+    /// The parameters, with their names in upper case and their values unquoted
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+/// A component of an iCalendar file, from its `BEGIN` to its `END`
+#[derive(Debug)]
+struct Component {
+    /// The name, in upper case
+    name: String,
+    properties: Vec<Property>,
+    components: Vec<Component>,
+}
+
+impl Property {
+    /// Reads a content line, which was unfolded, or `None` when it has no value
+    fn parse(line: &str) -> Option<Property> {
+        // The value starts at the first colon that is not quoted in a parameter
+        let mut quoted = false;
+        let colon = line.char_indices().find_map(|(i, c)| match c {
+            '"' => {
+                quoted = !quoted;
+                None
+            }
+            ':' if !quoted => Some(i),
+            _ => None,
+        })?;
+
+        let (head, value) = (&line[..colon], &line[colon + 1..]);
+        let mut quoted = false;
+        let mut head = head.split(|c| {
+            quoted ^= c == '"';
+            c == ';' && !quoted
+        });
+
+        let name = head.next()?.trim().to_ascii_uppercase();
+        let params = head
+            .filter_map(|param| param.split_once('='))
+            .map(|(name, value)| {
+                (
+                    name.trim().to_ascii_uppercase(),
+                    value.trim().trim_matches('"').into(),
+                )
+            })
+            .collect();
+
+        Some(Property {
+            name,
+            params,
+            value: value.into(),
+        })
+    }
+
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(other, _)| other == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl Component {
+    /// Reads the `VCALENDAR` of an iCalendar file
+    fn parse(ics: &str) -> Result<Component, IcsError> {
+        // Unfolds the lines, keeping the number each starts at
+        let mut lines: Vec<(usize, String)> = vec![];
+        for (number, line) in ics.lines().enumerate() {
+            match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+                (Some(continuation), Some((_, previous))) => previous.push_str(continuation),
+                _ if line.trim().is_empty() => {}
+                _ => lines.push((number + 1, line.into())),
+            }
+        }
+
+        let mut stack: Vec<Component> = vec![];
+        for (number, line) in lines {
+            let malformed = |reason: String| IcsError::Malformed(number, reason);
+            let property = Property::parse(&line)
+                .ok_or_else(|| malformed(String::from("the line has no value")))?;
+
+            match property.name.as_str() {
+                "BEGIN"
+                    if stack.is_empty() && !property.value.eq_ignore_ascii_case("VCALENDAR") =>
+                {
+                    return Err(malformed(String::from(
+                        "the file does not start with BEGIN:VCALENDAR",
+                    )));
+                }
+                "BEGIN" => stack.push(Component {
+                    name: property.value.trim().to_ascii_uppercase(),
+                    properties: vec![],
+                    components: vec![],
+                }),
+                "END" => {
+                    let component = stack
+                        .pop()
+                        .ok_or_else(|| malformed(String::from("END without BEGIN")))?;
+                    if !property.value.trim().eq_ignore_ascii_case(&component.name) {
+                        return Err(malformed(format!(
+                            "END:{} does not close BEGIN:{}",
+                            property.value, component.name
+                        )));
+                    }
+
+                    match stack.last_mut() {
+                        Some(parent) => parent.components.push(component),
+                        None => return Ok(component),
+                    }
+                }
+                _ => match stack.last_mut() {
+                    Some(component) => component.properties.push(property),
+                    None => {
+                        return Err(malformed(String::from(
+                            "the file does not start with BEGIN:VCALENDAR",
+                        )))
+                    }
+                },
+            }
+        }
+
+        Err(IcsError::Malformed(
+            ics.lines().count(),
+            String::from("the file does not end with END:VCALENDAR"),
+        ))
+    }
+
+    /// Gets the first property with the given name
+    fn get(&self, name: &str) -> Option<&Property> {
+        self.properties
+            .iter()
+            .find(|property| property.name == name)
+    }
+}
+
+/// Reads a text value, undoing [`escape_text`]
+fn unescape_text(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' => match chars.next() {
+                Some('n') | Some('N') => unescaped.push('\n'),
+                Some(escaped) => unescaped.push(escaped),
+                None => unescaped.push(char),
+            },
+            _ => unescaped.push(char),
+        }
+    }
+
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    use crate::core::calendar::{StHolidayCalendar, StRollConvention};
+    use crate::core::clock::FixedClock;
+    use crate::core::errors::{IcsError, RRuleError};
+    use crate::core::frequency::{
+        StCustomFrequency, StDailyExpression, StFrequencyType, StRegularFrequency,
+        StWeeklyExpression,
+    };
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    /// 2025-01-10T12:00:00.000Z
+    static NOW: Timestamp = Timestamp::Millis(1736510400000);
+
+    fn schedule(id: &str, timezone: &str, frequency: Option<StFrequency>) -> StSchedule {
+        let clock = FixedClock::new(NOW);
+        StSchedule::try_with_frequency(
+            id,
+            "2025-01-15T09:00:00.000",
+            timezone,
+            frequency,
+            None,
+            &clock,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_export_weekly_schedule() {
+        let expr = StWeeklyExpression::with_weekdays(2, vec![1, 3]);
+        let freq = StRegularFrequency::with_weekly_expr(StFrequencyType::Week, expr, None);
+        let schedule = schedule("task;1", "Africa/Lagos", Some(StFrequency::Regular(freq)));
+
+        let ics = export_ics_with(&[schedule], &FixedClock::new(NOW));
+        let lines = ics.split("\r\n").collect::<Vec<_>>();
+
+        assert_eq!(
+            lines,
+            vec![
+                "BEGIN:VCALENDAR",
+                "VERSION:2.0",
+                "PRODID:-//Stitches//Scheduler//EN",
+                "CALSCALE:GREGORIAN",
+                "BEGIN:VTIMEZONE",
+                "TZID:Africa/Lagos",
+                "BEGIN:STANDARD",
+                "DTSTART:20250101T010000",
+                "TZOFFSETFROM:+0100",
+                "TZOFFSETTO:+0100",
+                "TZNAME:WAT",
+                "END:STANDARD",
+                "END:VTIMEZONE",
+                "BEGIN:VEVENT",
+                "UID:task\\;1",
+                "DTSTAMP:20250110T120000Z",
+                "DTSTART;TZID=Africa/Lagos:20250115T090000",
+                "RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;WKST=SU",
+                "END:VEVENT",
+                "END:VCALENDAR",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_export_timezone_transitions() {
+        let ics = export_ics_with(
+            &[schedule("a", "America/New_York", None)],
+            &FixedClock::new(NOW),
+        );
+
+        assert!(ics.contains(
+            "BEGIN:DAYLIGHT\r\nDTSTART:20250309T020000\r\nTZOFFSETFROM:-0500\r\nTZOFFSETTO:-0400\r\nTZNAME:EDT\r\n"
+        ));
+        assert!(ics.contains(
+            "BEGIN:STANDARD\r\nDTSTART:20251102T020000\r\nTZOFFSETFROM:-0400\r\nTZOFFSETTO:-0500\r\nTZNAME:EST\r\n"
+        ));
+        // Ten years of transitions past the anchor, besides the offset at the start
+        assert_eq!(ics.matches("BEGIN:DAYLIGHT").count(), 11);
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+        assert!(!ics.contains("RRULE") && !ics.contains("RDATE"));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_export_cron_schedule_occurrences() {
+        let mut freq = StCustomFrequency::new(vec![String::from("0 9 * * 1-5")], None);
+        freq.set_count(Some(40));
+        let schedule = schedule("b", "Africa/Lagos", Some(StFrequency::Custom(freq)));
+
+        let ics = export_ics_with(&[schedule], &FixedClock::new(NOW));
+        let rdate = ics
+            .split("\r\nEND:VEVENT")
+            .next()
+            .and_then(|event| event.split("\r\nRDATE").nth(1))
+            .unwrap()
+            .replace("\r\n ", "");
+
+        assert!(ics.split("\r\n").all(|line| line.len() <= LINE_LIMIT));
+        assert!(rdate
+            .starts_with(";TZID=Africa/Lagos:20250116T090000,20250117T090000,20250120T090000,"));
+        assert_eq!(rdate.matches(',').count(), 38);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_export_rolled_schedule_occurrences() {
+        let mut freq = StRegularFrequency::with_daily_expr(
+            StFrequencyType::Day,
+            StDailyExpression::new(1),
+            None,
+        );
+        freq.set_count(Some(4));
+        let mut schedule = schedule("c", "Africa/Lagos", Some(StFrequency::Regular(freq)));
+        let mut calendar = StHolidayCalendar::new();
+        calendar.add_holiday("2025-01-15").unwrap();
+        let clock = FixedClock::new(NOW);
+        schedule.set_holiday_calendar_with(Some(calendar), StRollConvention::Following, &clock);
+
+        let ics = export_ics_with(&[schedule], &clock);
+
+        // The anchor on a holiday is rolled to the next day, and so is the Saturday to the Monday
+        assert!(ics.contains(
+            "DTSTART;TZID=Africa/Lagos:20250116T090000\r\nRDATE;TZID=Africa/Lagos:20250117T090000,20250120T090000\r\n"
+        ));
+        assert!(!ics.contains("RRULE"));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_export_daily_schedule_across_daylight_saving_time() {
+        // 2025-03-01T12:00:00.000Z
+        let clock = FixedClock::new(Timestamp::Millis(1740830400000));
+        let mut freq = StRegularFrequency::with_daily_expr(
+            StFrequencyType::Day,
+            StDailyExpression::new(1),
+            None,
+        );
+        freq.set_count(Some(6));
+        let schedule = StSchedule::try_with_frequency(
+            "d",
+            "2025-03-05T09:00:00.000",
+            "America/New_York",
+            Some(StFrequency::Regular(freq)),
+            None,
+            &clock,
+        )
+        .unwrap();
+
+        let ics = export_ics_with(&[schedule], &clock).replace("\r\n ", "");
+
+        // The schedule repeats every 24 hours, so it occurs at 10:00 once daylight saving time starts on the 9th
+        assert!(ics.contains(
+            "DTSTART;TZID=America/New_York:20250305T090000\r\nRDATE;TZID=America/New_York:20250306T090000,20250307T090000,20250308T090000,20250309T100000,20250310T100000\r\n"
+        ));
+        assert!(!ics.contains("RRULE"));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_export_listed_schedule_with_old_anchor() {
+        // 2025-03-01T12:00:00.000Z
+        let clock = FixedClock::new(Timestamp::Millis(1740830400000));
+        let freq = StRegularFrequency::with_daily_expr(
+            StFrequencyType::Day,
+            StDailyExpression::new(1),
+            None,
+        );
+        let schedule = StSchedule::try_with_frequency(
+            "d",
+            "2022-03-01T09:00:00.000",
+            "America/New_York",
+            Some(StFrequency::Regular(freq)),
+            None,
+            &clock,
+        )
+        .unwrap();
+
+        let ics = export_ics_with(&[schedule], &clock).replace("\r\n ", "");
+        let rdate = ics
+            .split("\r\n")
+            .find_map(|line| line.strip_prefix("RDATE;TZID=America/New_York:"))
+            .unwrap();
+        let dates = rdate.split(',').collect::<Vec<_>>();
+
+        // The occurrences of the three years before the export are left out rather than listed in place of the
+        // ones to come
+        assert!(ics.contains("DTSTART;TZID=America/New_York:20220301T090000\r\n"));
+        assert_eq!(dates.len(), 366);
+        assert_eq!(dates[0], "20250301T090000");
+        assert_eq!(dates[365], "20260301T090000");
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_import_events() {
+        let ics = [
+            "BEGIN:VCALENDAR",
+            "VERSION:2.0",
+            "PRODID:-//Other//App//EN",
+            "BEGIN:VEVENT",
+            "UID:standup\\;1",
+            "SUMMARY:Stand-up",
+            "DTSTART;TZID=\"/mozilla.org/20050126_1/Africa/Lagos\":20250115T090000",
+            "RRULE:FREQ=WEEKLY;BYDAY=MO,WE;UN",
+            " TIL=20250331T090000",
+            "EXDATE;TZID=Africa/Lagos:20250120T090000,20250122T090000",
+            "RDATE;VALUE=DATE:20250125",
+            "RDATE;VALUE=PERIOD:20250126T090000Z/PT1H",
+            "BEGIN:VALARM",
+            "TRIGGER:-PT15M",
+            "END:VALARM",
+            "END:VEVENT",
+            "BEGIN:VTODO",
+            "DUE;VALUE=DATE:20250201",
+            "RRULE:FREQ=MONTHLY;COUNT=3",
+            "END:VTODO",
+            "BEGIN:VEVENT",
+            "UID:review",
+            "DTSTART:20250115T080000Z",
+            "RRULE:FREQ=DAILY;BYHOUR=9,17",
+            "END:VEVENT",
+            "BEGIN:VEVENT",
+            "UID:standup;1",
+            "DTSTART;TZID=Mars/Olympus_Mons:20250115T090000",
+            "END:VEVENT",
+            "END:VCALENDAR",
+        ]
+        .join("\r\n");
+
+        let import = import_ics_with(&ics, "America/New_York", &FixedClock::new(NOW)).unwrap();
+        let schedules = import.schedules();
+        let reports = import.reports();
+
+        assert_eq!(schedules.len(), 2);
+        assert_eq!(schedules[0].get_id_as_str(), "standup;1");
+        assert_eq!(schedules[0].get_timezone(), Tz::Africa__Lagos);
+        assert_eq!(
+            schedules[0].get_local_anchor().to_string(),
+            "2025-01-15 09:00:00"
+        );
+        assert_eq!(
+            schedules[0]
+                .get_regular_frequency()
+                .unwrap()
+                .to_rrule()
+                .unwrap(),
+            "FREQ=WEEKLY;BYDAY=MO,WE;UNTIL=20250331T080000Z"
+        );
+        assert_eq!(schedules[1].get_id_as_str(), "event-2");
+        assert_eq!(schedules[1].get_timezone(), Tz::America__New_York);
+        assert_eq!(schedules[1].get_count(), Some(3));
+
+        // 2025-01-20T08:00:00.000Z, 2025-01-22T08:00:00.000Z and 2025-01-25T08:00:00.000Z
+        assert_eq!(
+            schedules[0].get_exclusions(),
+            vec![1737360000000, 1737532800000]
+        );
+        assert_eq!(schedules[0].get_inclusions(), vec![1737792000000]);
+
+        assert_eq!(reports.len(), 4);
+        assert_eq!(reports[0].schedule_id.as_deref(), Some("standup;1"));
+        assert!(matches!(
+            reports[0].issues.as_slice(),
+            [IcsIssue::UnsupportedComponent(alarm), IcsIssue::UnsupportedProperty(period)]
+                if alarm == "VALARM" && period == "RDATE;VALUE=PERIOD"
+        ));
+        assert!(matches!(
+            reports[1].issues.as_slice(),
+            [IcsIssue::MissingProperty("UID")]
+        ));
+        assert_eq!(reports[2].schedule_id, None);
+        assert!(matches!(
+            reports[2].issues.as_slice(),
+            [IcsIssue::UnsupportedRule(RRuleError::UnsupportedPart(name, _))] if name == "BYHOUR"
+        ));
+        assert!(matches!(
+            reports[3].issues.as_slice(),
+            [IcsIssue::UnknownTimezone(tzid)] if tzid == "Mars/Olympus_Mons"
+        ));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_import_exported_schedules() {
+        let clock = FixedClock::new(NOW);
+        let expr = StWeeklyExpression::with_weekdays(2, vec![1, 3]);
+        let mut freq = StRegularFrequency::with_weekly_expr(StFrequencyType::Week, expr, None);
+        freq.set_count(Some(10));
+        // A weekly schedule written as a rule and a schedule that does not repeat, both with dates of their own
+        let mut schedules = vec![
+            schedule("a,b", "Africa/Lagos", Some(StFrequency::Regular(freq))),
+            schedule("c", "America/New_York", None),
+        ];
+        // 2025-01-20T14:00:00.000Z and 2025-01-25T14:00:00.000Z
+        schedules[0].add_exclusion_with(Timestamp::Millis(1737381600000), &clock);
+        schedules[0].add_inclusion_with(Timestamp::Millis(1737813600000), &clock);
+        schedules[1].add_inclusion_with(Timestamp::Millis(1737813600000), &clock);
+
+        let import = import_ics_with(&export_ics_with(&schedules, &clock), "UTC", &clock).unwrap();
+
+        assert_eq!(import.into_schedules(), schedules);
+
+        // A daily schedule in a timezone with daylight saving time is listed rather than written as a rule, so it
+        // comes back as a schedule that does not repeat but is included at the listed occurrences
+        let freq = StRegularFrequency::with_daily_expr(
+            StFrequencyType::Day,
+            StDailyExpression::new(1),
+            None,
+        );
+        let daily = schedule("d", "America/New_York", Some(StFrequency::Regular(freq)));
+        let mut listed = schedule("d", "America/New_York", None);
+        let anchor = Timestamp::Millis(daily.get_anchor_millis());
+        let end = anchor + Timestamp::from_days(RDATE_HORIZON_DAYS);
+        for occurrence in daily.occurrences(anchor + Timestamp::Millis(1), end) {
+            listed.add_inclusion_with(Timestamp::Millis(occurrence.get_deadline_millis()), &clock);
+        }
+
+        let import = import_ics_with(&export_ics_with(&[daily], &clock), "UTC", &clock).unwrap();
+
+        assert!((1..=RDATE_LIMIT).contains(&listed.get_dates().get_included().len()));
+        assert_eq!(import.into_schedules(), vec![listed]);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_import_exported_schedule_to_the_second() {
+        let clock = FixedClock::new(NOW);
+        let freq = StRegularFrequency::with_daily_expr(
+            StFrequencyType::Day,
+            StDailyExpression::new(1),
+            None,
+        );
+        let schedule = |anchor: &str| {
+            let frequency = Some(StFrequency::Regular(freq.clone()));
+            StSchedule::try_with_frequency("s", anchor, "Africa/Lagos", frequency, None, &clock)
+                .unwrap()
+        };
+        // 2025-01-16T08:00:00.250Z
+        let mut precise = schedule("2025-01-15T09:00:00.250");
+        precise.add_exclusion_with(Timestamp::Millis(1737014400250), &clock);
+        let mut expected = schedule("2025-01-15T09:00:00.000");
+        expected.add_exclusion_with(Timestamp::Millis(1737014400000), &clock);
+
+        // The milliseconds of the anchor and of the excluded time are lost on the way
+        let import = import_ics_with(&export_ics_with(&[precise], &clock), "UTC", &clock).unwrap();
+
+        assert_eq!(import.into_schedules(), vec![expected]);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_import_malformed_calendars() {
+        let import = |ics: &str| import_ics_with(ics, "UTC", &FixedClock::new(NOW)).map(|_| ());
+
+        assert_eq!(
+            import("BEGIN:VEVENT\nEND:VEVENT"),
+            Err(IcsError::Malformed(
+                1,
+                String::from("the file does not start with BEGIN:VCALENDAR")
+            ))
+        );
+        assert_eq!(
+            import("BEGIN:VCALENDAR\nBEGIN:VEVENT\nEND:VCALENDAR"),
+            Err(IcsError::Malformed(
+                3,
+                String::from("END:VCALENDAR does not close BEGIN:VEVENT")
+            ))
+        );
+        assert_eq!(
+            import("BEGIN:VCALENDAR\nVERSION:2.0"),
+            Err(IcsError::Malformed(
+                2,
+                String::from("the file does not end with END:VCALENDAR")
+            ))
+        );
+        assert_eq!(import("BEGIN:VCALENDAR\nEND:VCALENDAR"), Ok(()));
+        assert_eq!(
+            import_ics("BEGIN:VCALENDAR\nEND:VCALENDAR", "Mars/Olympus_Mons").map(|_| ()),
+            Err(IcsError::UnknownTimezone(String::from("Mars/Olympus_Mons")))
+        );
+    }
+}
@@ -1,7 +1,20 @@
+pub mod calendar;
+pub mod clock;
+pub mod command;
 pub mod cron;
+pub mod describe;
 pub mod errors;
+pub mod event;
 pub mod frequency;
+pub mod ics;
+pub mod policy;
 pub mod priority;
+pub mod rrule;
 pub mod schedule;
 pub mod scheduler;
+pub mod snapshot;
+pub mod subscriber;
+#[cfg(feature = "serde")]
+pub mod task;
 pub mod time;
+mod trigger;
@@ -0,0 +1,23 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+/// How the scheduler handles the occurrences of a schedule it missed, e.g. while the runner was asleep.
+///
+/// An occurrence is missed when it is dispatched later than the scheduler's late tolerance allows, and every
+/// missed occurrence that is not fired is reported to subscribers in a missed event. Only so many of the latest
+/// occurrences are caught up on at once, and the ones before them are reported as missed by their number.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum StCatchUpPolicy {
+    /// Fires every missed occurrence that is caught up on, one after the other
+    FireAll,
+    /// Fires only the latest missed occurrence and reports the rest as missed
+    #[default]
+    FireLatest,
+    /// Fires none of the missed occurrences and reports all of them as missed
+    SkipAll,
+    /// Fires the latest missed occurrence once, with the rest folded into it
+    Coalesce,
+}
@@ -1,9 +1,12 @@
 use core::cmp::Ordering;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum StPriority {
     Low,
     Medium,
@@ -18,8 +21,8 @@ impl Ord for StPriority {
             Self::Low => 1,
         };
 
-        let self_priority = value_of(&self);
-        let other_priority = value_of(&other);
+        let self_priority = value_of(self);
+        let other_priority = value_of(other);
 
         // place other before self for an inverse ordering
         other_priority.cmp(&self_priority)
@@ -0,0 +1,745 @@
+use chrono::prelude::*;
+use wasm_bindgen::prelude::*;
+
+use crate::core::errors::RRuleError;
+use crate::core::frequency::{
+    StConstWeekday, StDailyExpression, StFrequencyExpression, StFrequencyType, StHourlyExpression,
+    StMonth, StMonthlyExpression, StMonthlyOnDaysSubExpression, StMonthlyOnTheSubExpression,
+    StMonthlySubExpression, StOrdinals, StRegularFrequency, StVarWeekday, StWeekday,
+    StWeeklyExpression, StWeeklySubExpression, StYearlyExpression, StYearlyOnTheSubExpression,
+    StYearlySubExpression,
+};
+use crate::core::time::Timestamp;
+use crate::utils::map_unique;
+
+/// The codes of the weekdays in rules, indexed by weekday from Sunday
+const WEEKDAY_CODES: [&str; 7] = ["SU", "MO", "TU", "WE", "TH", "FR", "SA"];
+
+/// The format of the `UNTIL` of rules written, which is always in UTC
+const UNTIL_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// The weekdays of a `BYDAY` along with their ordinals, if any
+type ByDay = Vec<(Option<i32>, StConstWeekday)>;
+
+#[wasm_bindgen]
+impl StRegularFrequency {
+    /// Writes the frequency as the value of an RFC 5545 `RRULE`, e.g. "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;WKST=SU".
+    ///
+    /// The `until` of a frequency is exclusive while the `UNTIL` of a rule is inclusive, so the rule ends on the
+    /// last whole second before it. Rules have no finer precision than seconds, so the milliseconds of an `until`
+    /// are lost, and it is read back as the whole second after the `UNTIL`. Weeks start on Sunday, which the rule
+    /// states whenever it matters.
+    ///
+    /// Throws when the frequency cannot be written as a single rule, that is when it has both an `until` and a
+    /// `count`, or occurs on the nth weekday or weekend day of more than one month of the year.
+    pub fn to_rrule(&self) -> Result<String, RRuleError> {
+        let mut parts = vec![];
+        let (freq, every) = match &self.expr {
+            StFrequencyExpression::Hourly(expr) => ("HOURLY", expr.every),
+            StFrequencyExpression::Daily(expr) => ("DAILY", expr.every),
+            StFrequencyExpression::Weekly(expr) => ("WEEKLY", expr.every),
+            StFrequencyExpression::Monthly(expr) => ("MONTHLY", expr.every),
+            StFrequencyExpression::Yearly(expr) => ("YEARLY", expr.every),
+        };
+
+        parts.push(format!("FREQ={}", freq));
+        if every > 1 {
+            parts.push(format!("INTERVAL={}", every));
+        }
+
+        match &self.expr {
+            StFrequencyExpression::Weekly(expr) if !expr.subexpr.weekdays.is_empty() => {
+                let weekdays = expr.subexpr.weekdays.iter().map(|w| weekday_code(*w));
+                parts.push(format!("BYDAY={}", join(weekdays)));
+                if every > 1 {
+                    parts.push(String::from("WKST=SU"));
+                }
+            }
+            StFrequencyExpression::Monthly(expr) => match &expr.subexpr {
+                StMonthlySubExpression::OnDays(subexpr) if !subexpr.days.is_empty() => {
+                    parts.push(format!("BYMONTHDAY={}", join(subexpr.days.iter())));
+                }
+                StMonthlySubExpression::OnThe(subexpr) => parts.push(format!(
+                    "BYDAY={}{}",
+                    ordinal_value(subexpr.ordinal),
+                    weekday_code(subexpr.weekday)
+                )),
+                StMonthlySubExpression::OnDays(_) => {}
+            },
+            StFrequencyExpression::Yearly(expr) => {
+                let months = &expr.subexpr.months;
+                if !months.is_empty() {
+                    let months = months.iter().map(|m| u32::from(*m) + 1);
+                    parts.push(format!("BYMONTH={}", join(months)));
+                }
+
+                if let Some(on) = expr.subexpr.on {
+                    let ordinal = ordinal_value(on.ordinal);
+                    match on.weekday {
+                        StWeekday::Const(weekday) => {
+                            parts.push(format!("BYDAY={}{}", ordinal, weekday_code(weekday)))
+                        }
+                        StWeekday::Var(StVarWeekday::Day) => {
+                            parts.push(format!("BYMONTHDAY={}", ordinal))
+                        }
+                        StWeekday::Var(weekday) => {
+                            // The position is among the days of the whole year, so only one month can be given
+                            if map_unique(months, |m| u32::from(*m)).len() > 1 {
+                                return Err(RRuleError::Unrepresentable(
+                                    "the nth weekday or weekend day is only written for a single month",
+                                ));
+                            }
+
+                            let weekdays = match weekday {
+                                StVarWeekday::Weekend => "SA,SU",
+                                _ => "MO,TU,WE,TH,FR",
+                            };
+                            parts.push(format!("BYDAY={}", weekdays));
+                            parts.push(format!("BYSETPOS={}", ordinal));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        match (self.until, self.count) {
+            (Some(_), Some(_)) => {
+                return Err(RRuleError::Unrepresentable(
+                    "a rule cannot have both an UNTIL and a COUNT",
+                ))
+            }
+            (Some(until), None) => {
+                let last_second = (until.as_ms() - 1).div_euclid(1000);
+                let until = DateTime::from_timestamp(last_second, 0).ok_or(
+                    RRuleError::Unrepresentable("the until is out of the range of dates"),
+                )?;
+                parts.push(format!("UNTIL={}", until.format(UNTIL_FORMAT)));
+            }
+            (None, Some(count)) => parts.push(format!("COUNT={}", count)),
+            (None, None) => {}
+        }
+
+        Ok(parts.join(";"))
+    }
+
+    /// Reads a frequency from the value of an RFC 5545 `RRULE`, with or without its "RRULE:" prefix.
+    ///
+    /// Rules written by [`StRegularFrequency::to_rrule`] are read back as the same frequency. Since weeks start on
+    /// Sunday, a weekly rule with an `INTERVAL` and a `BYDAY` with `SU` is only read when its `WKST` is `SU`.
+    ///
+    /// Throws naming the part of the rule that is invalid, or that frequencies have no equivalent for, e.g.
+    /// `BYHOUR` or `FREQ=MINUTELY`.
+    pub fn from_rrule(rule: &str) -> Result<StRegularFrequency, RRuleError> {
+        let rule = rule.trim();
+        let rule = match rule.get(..6) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("RRULE:") => &rule[6..],
+            _ => rule,
+        };
+        let mut parts = Parts::parse(rule)?;
+
+        let freq = parts.take("FREQ").ok_or(RRuleError::MissingFrequency)?;
+        let every = match parts.take("INTERVAL") {
+            Some(value) => parse_positive("INTERVAL", &value)?,
+            None => 1,
+        };
+        let until = parts.take("UNTIL").map(|v| parse_until(&v)).transpose()?;
+        let count = parts
+            .take("COUNT")
+            .map(|value| parse_positive("COUNT", &value))
+            .transpose()?;
+        let wkst = parts.take("WKST").map(|v| parse_wkst(&v)).transpose()?;
+        let by_day = parts.take("BYDAY").map(|v| parse_by_day(&v)).transpose()?;
+        let by_month_day = parts.take("BYMONTHDAY");
+        let by_month = parts
+            .take("BYMONTH")
+            .map(|v| parse_by_month(&v))
+            .transpose()?;
+        let by_set_pos = parts.take("BYSETPOS");
+
+        if let Some((name, value)) = parts.0.into_iter().next() {
+            return Err(RRuleError::UnsupportedPart(name, value));
+        }
+        if until.is_some() && count.is_some() {
+            return Err(unsupported("UNTIL and COUNT cannot both be given"));
+        }
+
+        // The parts that are given but have no meaning for the rule's frequency
+        let unexpected = |allowed: &[&str]| {
+            let given = [
+                ("BYDAY", by_day.is_some()),
+                ("BYMONTHDAY", by_month_day.is_some()),
+                ("BYMONTH", by_month.is_some()),
+                ("BYSETPOS", by_set_pos.is_some()),
+            ];
+            given
+                .iter()
+                .find(|(name, given)| *given && !allowed.contains(name))
+                .map(|(name, _)| {
+                    unsupported(&format!("{} is not supported with FREQ={}", name, freq))
+                })
+        };
+
+        let (ftype, expr) = match freq.as_str() {
+            "HOURLY" | "DAILY" => {
+                if let Some(err) = unexpected(&[]) {
+                    return Err(err);
+                }
+
+                match freq.as_str() {
+                    "HOURLY" => (
+                        StFrequencyType::Hour,
+                        StFrequencyExpression::Hourly(StHourlyExpression::new(every)),
+                    ),
+                    _ => (
+                        StFrequencyType::Day,
+                        StFrequencyExpression::Daily(StDailyExpression::new(every)),
+                    ),
+                }
+            }
+            "WEEKLY" => {
+                if let Some(err) = unexpected(&["BYDAY"]) {
+                    return Err(err);
+                }
+
+                let weekdays = match &by_day {
+                    Some((value, days)) => days
+                        .iter()
+                        .map(|(ordinal, weekday)| match ordinal {
+                            Some(_) => Err(RRuleError::InvalidValue(
+                                String::from("BYDAY"),
+                                value.clone(),
+                                "weekdays without ordinals, such as MO,WE, with FREQ=WEEKLY",
+                            )),
+                            None => Ok(*weekday),
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                    None => vec![],
+                };
+
+                let starts_on_sunday = wkst == Some(StConstWeekday::Sun);
+                if every > 1 && weekdays.contains(&StConstWeekday::Sun) && !starts_on_sunday {
+                    return Err(unsupported(
+                        "FREQ=WEEKLY with an INTERVAL and SU in BYDAY is only supported with WKST=SU",
+                    ));
+                }
+
+                let subexpr = StWeeklySubExpression { weekdays };
+                (
+                    StFrequencyType::Week,
+                    StFrequencyExpression::Weekly(StWeeklyExpression::new(every, subexpr)),
+                )
+            }
+            "MONTHLY" => {
+                if let Some(err) = unexpected(&["BYDAY", "BYMONTHDAY"]) {
+                    return Err(err);
+                }
+
+                let subexpr = match (by_day, by_month_day) {
+                    (Some(_), Some(_)) => {
+                        return Err(unsupported(
+                            "BYDAY and BYMONTHDAY cannot both be given with FREQ=MONTHLY",
+                        ))
+                    }
+                    (Some((value, days)), None) => {
+                        let (ordinal, weekday) = match days.as_slice() {
+                            [(Some(ordinal), weekday)] => (*ordinal, *weekday),
+                            [(None, _)] => {
+                                return Err(unsupported(
+                                    "BYDAY with FREQ=MONTHLY is only supported with an ordinal, such as 1MO",
+                                ))
+                            }
+                            _ => {
+                                return Err(unsupported(
+                                    "BYDAY with FREQ=MONTHLY is only supported with a single weekday",
+                                ))
+                            }
+                        };
+                        let ordinal = to_ordinal(ordinal)
+                            .ok_or_else(|| RRuleError::UnsupportedPart("BYDAY".into(), value))?;
+                        StMonthlySubExpression::OnThe(StMonthlyOnTheSubExpression::new(
+                            ordinal, weekday,
+                        ))
+                    }
+                    (None, Some(value)) => {
+                        let days = parse_by_month_day(&value)?;
+                        if days.iter().any(|day| *day < 0) {
+                            return Err(RRuleError::UnsupportedPart("BYMONTHDAY".into(), value));
+                        }
+
+                        let days = days.into_iter().map(|day| day as u32).collect();
+                        StMonthlySubExpression::OnDays(StMonthlyOnDaysSubExpression { days })
+                    }
+                    (None, None) => StMonthlySubExpression::OnDays(StMonthlyOnDaysSubExpression {
+                        days: vec![],
+                    }),
+                };
+
+                (
+                    StFrequencyType::Month,
+                    StFrequencyExpression::Monthly(StMonthlyExpression { every, subexpr }),
+                )
+            }
+            "YEARLY" => {
+                let months = match by_month {
+                    Some(months) => months,
+                    None => return Err(unsupported("FREQ=YEARLY is only supported with BYMONTH")),
+                };
+                let on = read_yearly_on(&months, by_day, by_month_day, by_set_pos)?;
+
+                let subexpr = StYearlySubExpression { months, on };
+                (
+                    StFrequencyType::Year,
+                    StFrequencyExpression::Yearly(StYearlyExpression::new(every, subexpr)),
+                )
+            }
+            "SECONDLY" | "MINUTELY" => {
+                return Err(RRuleError::UnsupportedPart(String::from("FREQ"), freq))
+            }
+            _ => {
+                return Err(RRuleError::InvalidValue(
+                    String::from("FREQ"),
+                    freq,
+                    "one of HOURLY, DAILY, WEEKLY, MONTHLY or YEARLY",
+                ))
+            }
+        };
+
+        Ok(StRegularFrequency {
+            ftype,
+            until,
+            count,
+            expr,
+        })
+    }
+}
+
+/// The `NAME=VALUE` parts of a rule, in the order they are given, with their names and values in upper case
+struct Parts(Vec<(String, String)>);
+
+impl Parts {
+    fn parse(rule: &str) -> Result<Self, RRuleError> {
+        let mut parts: Vec<(String, String)> = vec![];
+
+        for part in rule.split(';').filter(|part| !part.trim().is_empty()) {
+            let (name, value) = part.split_once('=').ok_or_else(|| {
+                RRuleError::Malformed(format!("'{}' is not of the form NAME=VALUE", part))
+            })?;
+            let (name, value) = (name.trim().to_uppercase(), value.trim().to_uppercase());
+
+            if parts.iter().any(|(other, _)| *other == name) {
+                return Err(RRuleError::Malformed(format!(
+                    "{} is given more than once",
+                    name
+                )));
+            }
+            parts.push((name, value));
+        }
+
+        Ok(Parts(parts))
+    }
+
+    /// Removes the value of the part with the given name, if given
+    fn take(&mut self, name: &str) -> Option<String> {
+        let index = self.0.iter().position(|(other, _)| other == name)?;
+        Some(self.0.remove(index).1)
+    }
+}
+
+/// Reads the nth day of the months of a yearly rule from its `BYDAY`, `BYMONTHDAY` and `BYSETPOS`, if any
+fn read_yearly_on(
+    months: &[StMonth],
+    by_day: Option<(String, ByDay)>,
+    by_month_day: Option<String>,
+    by_set_pos: Option<String>,
+) -> Result<Option<StYearlyOnTheSubExpression>, RRuleError> {
+    let on = |ordinal, weekday| Some(StYearlyOnTheSubExpression { ordinal, weekday });
+
+    match (by_day, by_month_day, by_set_pos) {
+        (None, None, None) => Ok(None),
+        (Some((value, days)), None, None) => match days.as_slice() {
+            [(Some(ordinal), weekday)] => {
+                let ordinal = to_ordinal(*ordinal)
+                    .ok_or_else(|| RRuleError::UnsupportedPart("BYDAY".into(), value))?;
+                Ok(on(ordinal, StWeekday::Const(*weekday)))
+            }
+            _ => Err(unsupported(
+                "BYDAY with FREQ=YEARLY is only supported as a single weekday with an ordinal, such as 1MO, or \
+                 with BYSETPOS",
+            )),
+        },
+        (None, Some(value), None) => match parse_by_month_day(&value)?.as_slice() {
+            [day] => match to_ordinal(*day) {
+                Some(ordinal) => Ok(on(ordinal, StWeekday::Var(StVarWeekday::Day))),
+                None => Err(RRuleError::UnsupportedPart("BYMONTHDAY".into(), value)),
+            },
+            _ => Err(unsupported(
+                "BYMONTHDAY with FREQ=YEARLY is only supported as a single day",
+            )),
+        },
+        (Some((_, days)), None, Some(value)) => {
+            let mut weekdays = map_unique(&days, |(ordinal, weekday)| {
+                ordinal.map_or(u32::from(*weekday), |_| u32::MAX)
+            });
+            weekdays.sort_unstable();
+
+            let weekday = match weekdays.as_slice() {
+                [1, 2, 3, 4, 5] => StVarWeekday::Weekday,
+                [0, 6] => StVarWeekday::Weekend,
+                _ => {
+                    return Err(unsupported(
+                        "BYSETPOS with FREQ=YEARLY is only supported with BYDAY=MO,TU,WE,TH,FR or BYDAY=SA,SU",
+                    ))
+                }
+            };
+            if map_unique(months, |m| u32::from(*m)).len() > 1 {
+                return Err(unsupported(
+                    "BYSETPOS with FREQ=YEARLY is only supported with a single month in BYMONTH",
+                ));
+            }
+
+            let position = value.parse::<i32>().map_err(|_| {
+                RRuleError::InvalidValue("BYSETPOS".into(), value.clone(), "an integer")
+            })?;
+            let ordinal = to_ordinal(position)
+                .ok_or(RRuleError::UnsupportedPart("BYSETPOS".into(), value))?;
+            Ok(on(ordinal, StWeekday::Var(weekday)))
+        }
+        (None, _, Some(_)) | (_, Some(_), Some(_)) => Err(unsupported(
+            "BYSETPOS with FREQ=YEARLY is only supported with BYDAY=MO,TU,WE,TH,FR or BYDAY=SA,SU",
+        )),
+        (Some(_), Some(_), None) => Err(unsupported(
+            "BYDAY and BYMONTHDAY cannot both be given with FREQ=YEARLY",
+        )),
+    }
+}
+
+fn unsupported(reason: &str) -> RRuleError {
+    RRuleError::UnsupportedCombination(String::from(reason))
+}
+
+fn parse_positive(name: &str, value: &str) -> Result<u32, RRuleError> {
+    value
+        .parse::<u32>()
+        .ok()
+        .filter(|n| *n > 0)
+        .ok_or_else(|| RRuleError::InvalidValue(name.into(), value.into(), "a positive integer"))
+}
+
+/// Reads the exclusive end of a frequency from the inclusive `UNTIL` of a rule, in UTC or as a date
+fn parse_until(value: &str) -> Result<Timestamp, RRuleError> {
+    let last_second = NaiveDateTime::parse_from_str(value, UNTIL_FORMAT)
+        .map(|until| until.and_utc().timestamp())
+        .or_else(|_| {
+            NaiveDate::parse_from_str(value, "%Y%m%d")
+                .map(|until| until.and_time(NaiveTime::MIN).and_utc().timestamp() + 86_399)
+        })
+        .map_err(|_| {
+            RRuleError::InvalidValue(
+                "UNTIL".into(),
+                value.into(),
+                "a UTC date-time such as 20250131T090000Z, or a date such as 20250131",
+            )
+        })?;
+
+    Ok(Timestamp::Millis((last_second + 1) * 1000))
+}
+
+fn parse_wkst(value: &str) -> Result<StConstWeekday, RRuleError> {
+    parse_weekday(value).ok_or_else(|| {
+        RRuleError::InvalidValue("WKST".into(), value.into(), "a weekday such as MO")
+    })
+}
+
+/// Reads the weekdays of a `BYDAY` along with their ordinals, if any, keeping the value for errors
+fn parse_by_day(value: &str) -> Result<(String, ByDay), RRuleError> {
+    let invalid = || {
+        RRuleError::InvalidValue(
+            "BYDAY".into(),
+            value.into(),
+            "a list of weekdays such as MO,WE, each optionally with an ordinal such as 1MO or -1FR",
+        )
+    };
+
+    let days = value
+        .split(',')
+        .map(|day| {
+            let split = day.len().checked_sub(2).ok_or_else(invalid)?;
+            let (ordinal, weekday) = (day.get(..split), day.get(split..));
+            let weekday = weekday.and_then(parse_weekday).ok_or_else(invalid)?;
+            let ordinal = match ordinal.ok_or_else(invalid)? {
+                "" => None,
+                ordinal => Some(ordinal.parse::<i32>().map_err(|_| invalid())?),
+            };
+            Ok((ordinal, weekday))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((value.into(), days))
+}
+
+fn parse_by_month_day(value: &str) -> Result<Vec<i32>, RRuleError> {
+    value
+        .split(',')
+        .map(|day| {
+            day.parse::<i32>()
+                .ok()
+                .filter(|day| (1..=31).contains(&day.abs()))
+        })
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| {
+            RRuleError::InvalidValue(
+                "BYMONTHDAY".into(),
+                value.into(),
+                "a list of days of the month from 1 to 31, or -31 to -1",
+            )
+        })
+}
+
+fn parse_by_month(value: &str) -> Result<Vec<StMonth>, RRuleError> {
+    value
+        .split(',')
+        .map(|month| {
+            month
+                .parse::<u32>()
+                .ok()
+                .filter(|month| (1..=12).contains(month))
+                .map(|month| StMonth::from(month - 1))
+        })
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| {
+            RRuleError::InvalidValue(
+                "BYMONTH".into(),
+                value.into(),
+                "a list of months from 1 to 12",
+            )
+        })
+}
+
+fn parse_weekday(code: &str) -> Option<StConstWeekday> {
+    WEEKDAY_CODES
+        .iter()
+        .position(|other| *other == code)
+        .map(|weekday| StConstWeekday::from(weekday as u32))
+}
+
+fn weekday_code(weekday: StConstWeekday) -> &'static str {
+    WEEKDAY_CODES[u32::from(weekday) as usize]
+}
+
+/// Returns the ordinal of a rule, 1 to 5 from the first or -1 for the last
+fn ordinal_value(ordinal: StOrdinals) -> i32 {
+    match ordinal {
+        StOrdinals::Last => -1,
+        ordinal => u32::from(ordinal) as i32 + 1,
+    }
+}
+
+fn to_ordinal(value: i32) -> Option<StOrdinals> {
+    match value {
+        1 => Some(StOrdinals::First),
+        2 => Some(StOrdinals::Second),
+        3 => Some(StOrdinals::Third),
+        4 => Some(StOrdinals::Fourth),
+        5 => Some(StOrdinals::Fifth),
+        -1 => Some(StOrdinals::Last),
+        _ => None,
+    }
+}
+
+fn join<T: ToString>(values: impl Iterator<Item = T>) -> String {
+    values.map(|v| v.to_string()).collect::<Vec<_>>().join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn regular(ftype: StFrequencyType, expr: StFrequencyExpression) -> StRegularFrequency {
+        StRegularFrequency {
+            ftype,
+            until: None,
+            count: None,
+            expr,
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_rrule_round_trips() {
+        use StFrequencyExpression::*;
+
+        let mut until = StRegularFrequency::with_daily_expr(
+            StFrequencyType::Day,
+            StDailyExpression::new(3),
+            Some(1743465600000), // 2025-04-01T00:00:00.000Z
+        );
+        let mut count =
+            StRegularFrequency::new(StFrequencyType::Hour, StHourlyExpression::new(1), None);
+        count.set_count(Some(10));
+
+        let cases = [
+            (
+                until.clone(),
+                "FREQ=DAILY;INTERVAL=3;UNTIL=20250331T235959Z",
+            ),
+            (count, "FREQ=HOURLY;COUNT=10"),
+            (
+                regular(
+                    StFrequencyType::Week,
+                    Weekly(StWeeklyExpression::with_weekdays(2, vec![1, 3, 0])),
+                ),
+                "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE,SU;WKST=SU",
+            ),
+            (
+                regular(
+                    StFrequencyType::Week,
+                    Weekly(StWeeklyExpression::with_weekdays(1, vec![])),
+                ),
+                "FREQ=WEEKLY",
+            ),
+            (
+                regular(
+                    StFrequencyType::Month,
+                    Monthly(StMonthlyExpression::with_days(1, vec![31, 1, 15])),
+                ),
+                "FREQ=MONTHLY;BYMONTHDAY=31,1,15",
+            ),
+            (
+                regular(
+                    StFrequencyType::Month,
+                    Monthly(StMonthlyExpression::with_ordinal_weekday(
+                        2,
+                        StOrdinals::Last,
+                        StConstWeekday::Fri,
+                    )),
+                ),
+                "FREQ=MONTHLY;INTERVAL=2;BYDAY=-1FR",
+            ),
+            (
+                regular(
+                    StFrequencyType::Year,
+                    Yearly(StYearlyExpression::with_months(1, vec![6, 2])),
+                ),
+                "FREQ=YEARLY;BYMONTH=7,3",
+            ),
+            (
+                regular(
+                    StFrequencyType::Year,
+                    Yearly(StYearlyExpression::with_months_ordinal_const_weekday(
+                        1,
+                        vec![10],
+                        StOrdinals::Fourth,
+                        StConstWeekday::Thu,
+                    )),
+                ),
+                "FREQ=YEARLY;BYMONTH=11;BYDAY=4TH",
+            ),
+            (
+                regular(
+                    StFrequencyType::Year,
+                    Yearly(StYearlyExpression::with_months_ordinal_var_weekday(
+                        1,
+                        vec![0, 6],
+                        StOrdinals::Second,
+                        StVarWeekday::Day,
+                    )),
+                ),
+                "FREQ=YEARLY;BYMONTH=1,7;BYMONTHDAY=2",
+            ),
+            (
+                regular(
+                    StFrequencyType::Year,
+                    Yearly(StYearlyExpression::with_months_ordinal_var_weekday(
+                        4,
+                        vec![11],
+                        StOrdinals::Last,
+                        StVarWeekday::Weekday,
+                    )),
+                ),
+                "FREQ=YEARLY;INTERVAL=4;BYMONTH=12;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1",
+            ),
+        ];
+
+        for (frequency, rule) in cases {
+            assert_eq!(frequency.to_rrule().as_deref(), Ok(rule));
+            assert_eq!(StRegularFrequency::from_rrule(rule), Ok(frequency));
+        }
+
+        // The milliseconds of an until are lost, as it is written to the second and read back as the next one
+        let mut precise = until.clone();
+        precise.until = Some(Timestamp::Millis(1743465600250));
+        let rule = "FREQ=DAILY;INTERVAL=3;UNTIL=20250401T000000Z";
+        assert_eq!(precise.to_rrule().as_deref(), Ok(rule));
+        precise.until = Some(Timestamp::Millis(1743465601000));
+        assert_eq!(StRegularFrequency::from_rrule(rule), Ok(precise));
+
+        // Rules are read regardless of case, prefix and order of their parts
+        until.until = Some(Timestamp::Millis(1743465600000));
+        assert_eq!(
+            StRegularFrequency::from_rrule("RRULE:until=20250331;interval=3;freq=daily"),
+            Ok(until)
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_rrule_errors() {
+        let cases = [
+            ("INTERVAL=2", "The rule has no FREQ part"),
+            ("FREQ=DAILY;FREQ=DAILY", "The rule is malformed: FREQ is given more than once"),
+            ("FREQ=DAILY;BYHOUR", "The rule is malformed: 'BYHOUR' is not of the form NAME=VALUE"),
+            ("FREQ=MINUTELY", "'FREQ=MINUTELY' is not supported"),
+            ("FREQ=DAILY;BYHOUR=9", "'BYHOUR=9' is not supported"),
+            ("FREQ=DAILY;INTERVAL=0", "'INTERVAL=0' should be a positive integer"),
+            ("FREQ=DAILY;BYDAY=MO", "The rule is not supported: BYDAY is not supported with FREQ=DAILY"),
+            (
+                "FREQ=WEEKLY;INTERVAL=2;BYDAY=SU,MO",
+                "The rule is not supported: FREQ=WEEKLY with an INTERVAL and SU in BYDAY is only supported \
+                 with WKST=SU",
+            ),
+            ("FREQ=MONTHLY;BYDAY=2XX", "'BYDAY=2XX' should be a list of weekdays such as MO,WE, each optionally \
+              with an ordinal such as 1MO or -1FR"),
+            ("FREQ=MONTHLY;BYDAY=-2MO", "'BYDAY=-2MO' is not supported"),
+            ("FREQ=MONTHLY;BYMONTHDAY=-1", "'BYMONTHDAY=-1' is not supported"),
+            ("FREQ=YEARLY", "The rule is not supported: FREQ=YEARLY is only supported with BYMONTH"),
+            (
+                "FREQ=YEARLY;BYMONTH=3,7;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=1",
+                "The rule is not supported: BYSETPOS with FREQ=YEARLY is only supported with a single month \
+                 in BYMONTH",
+            ),
+            (
+                "FREQ=DAILY;UNTIL=20250101T000000Z;COUNT=2",
+                "The rule is not supported: UNTIL and COUNT cannot both be given",
+            ),
+            (
+                "FREQ=DAILY;UNTIL=20250101T000000",
+                "'UNTIL=20250101T000000' should be a UTC date-time such as 20250131T090000Z, or a date such \
+                 as 20250131",
+            ),
+        ];
+
+        for (rule, message) in cases {
+            let error = StRegularFrequency::from_rrule(rule).unwrap_err();
+            assert_eq!(error.to_string(), message, "{}", rule);
+        }
+
+        let frequency = StRegularFrequency::with_yearly_expr(
+            StFrequencyType::Year,
+            StYearlyExpression::with_months_ordinal_var_weekday(
+                1,
+                vec![2, 6],
+                StOrdinals::First,
+                StVarWeekday::Weekend,
+            ),
+            None,
+        );
+        assert!(matches!(
+            frequency.to_rrule(),
+            Err(RRuleError::Unrepresentable(_))
+        ));
+    }
+}
@@ -3,25 +3,37 @@ use core::fmt;
 
 use chrono::offset::LocalResult;
 use chrono::prelude::*;
-use chrono::Months;
 use chrono_tz::Tz;
 use cron_parser::parse;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
+use crate::console_error;
+use crate::core::calendar::{BusinessDays, StHolidayCalendar, StRollConvention, ROLL_LIMIT_DAYS};
+use crate::core::clock::{Clock, FixedClock, SystemClock};
 use crate::core::errors::ParseError;
 use crate::core::errors::TimingError;
 use crate::core::frequency::StCustomFrequency;
 use crate::core::frequency::StFrequencyExpression;
 use crate::core::frequency::StMonthlySubExpression;
-use crate::core::frequency::StOrdinals;
 use crate::core::frequency::{StConstWeekday, StMonth, StWeekday};
-use crate::core::frequency::{StFrequency, StRegularFrequency};
+use crate::core::frequency::{StFrequency, StRegularFrequency, MAX_CRON_EXPRESSIONS};
+use crate::core::policy::StCatchUpPolicy;
 use crate::core::priority::StPriority;
-use crate::core::time::{timestamp, Timestamp};
+use crate::core::time::{Timestamp, DAY_MILLIS};
 use crate::traits::{Ts, ID};
 
+/// The most occurrences of a frequency after the current timestamp that are skipped for not falling on a business
+/// day before the next occurrence is taken to never come
+const MAX_SKIPPED_OCCURRENCES: u32 = 10_000;
+/// The most occurrences counted while refreshing a timing without a count, past which the index of its deadline is
+/// left to be located when it is needed
+const MAX_INDEXED_STEPS: u32 = 100;
+
 #[wasm_bindgen]
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StSchedule {
     /// The identifier for this schedule
     id: String,
@@ -35,9 +47,38 @@ pub struct StSchedule {
     priority: Option<StPriority>,
     /// The frequency of repetition for this schedule, one-off schedules can be assigned a `None` frequency
     frequency: Option<StFrequency>,
+    /// The policy for the occurrences of this schedule that are missed, overriding the scheduler's policy
+    #[cfg_attr(feature = "serde", serde(default))]
+    catch_up: Option<StCatchUpPolicy>,
+    /// How long before each occurrence reminders are dispatched, in descending order
+    #[cfg_attr(feature = "serde", serde(default))]
+    reminders: Vec<Timestamp>,
+    /// How long after its planned time an occurrence becomes overdue, overriding the scheduler's grace period
+    #[cfg_attr(feature = "serde", serde(default))]
+    grace_period: Option<Timestamp>,
+    /// The times the schedule is excluded from and included at, besides the occurrences of its frequency
+    #[cfg_attr(feature = "serde", serde(default))]
+    dates: OccurrenceDates,
+    /// The business days the schedule occurs on, with its occurrences on other days rolled to one, if any
+    #[cfg_attr(feature = "serde", serde(default))]
+    business_days: Option<BusinessDays>,
+}
+
+/// The times that amend the occurrences of a schedule's frequency, e.g. every Monday except the 25th of
+/// December, plus one extra session on a Saturday
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OccurrenceDates {
+    /// The times the schedule does not occur at, even when its frequency or an included time does, sorted
+    #[cfg_attr(feature = "serde", serde(default))]
+    excluded: Vec<Timestamp>,
+    /// The times the schedule occurs at besides the occurrences of its frequency, sorted
+    #[cfg_attr(feature = "serde", serde(default))]
+    included: Vec<Timestamp>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Timing {
     /// The timezone information for the naive anchor which altogether makes the anchor
     timezone: Tz,
@@ -47,6 +88,17 @@ pub struct Timing {
     deadline: Timestamp,
     /// The naive anchor, that is the anchor time without timezone information
     naive_anchor: NaiveDateTime,
+    /// The zero-based index of the deadline among the occurrences, advanced as the timing is refreshed, or `None`
+    /// when it is yet to be located, e.g. after a refresh past too many occurrences to count
+    #[cfg_attr(feature = "serde", serde(default))]
+    index: Option<u64>,
+    /// Whether the deadline is an included time rather than an occurrence of the frequency, in which case the
+    /// index is that of the frequency's first occurrence after it
+    #[cfg_attr(feature = "serde", serde(default))]
+    extra: bool,
+    /// The occurrence of the frequency the deadline was rolled from to reach a business day, if it was
+    #[cfg_attr(feature = "serde", serde(default))]
+    rolled_from: Option<Timestamp>,
 }
 
 impl ID for StSchedule {
@@ -59,7 +111,12 @@ impl Timing {
     /// Checks if a [`Timing`] has passed by comparing the [`Timing`]'s anchor timestamp
     /// with a current timestamp.
     pub fn is_passed(&self) -> bool {
-        self.anchor < timestamp()
+        self.is_passed_with(&SystemClock)
+    }
+
+    /// Same as [`Timing::is_passed`] but the current timestamp is read from the given [`Clock`]
+    pub fn is_passed_with(&self, clock: &dyn Clock) -> bool {
+        self.anchor < clock.now()
     }
 
     /// Checks if a [`Timing`] has passed it's deadline or due time by comparing the [`Timing`]'s deadline
@@ -72,7 +129,12 @@ impl Timing {
     /// check whether the deadline (initially the anchor) has passed and can be used to re-evaluate the exact
     /// deadline for repeating events.
     pub fn is_passed_due(&self) -> bool {
-        self.deadline < timestamp()
+        self.is_passed_due_with(&SystemClock)
+    }
+
+    /// Same as [`Timing::is_passed_due`] but the current timestamp is read from the given [`Clock`]
+    pub fn is_passed_due_with(&self, clock: &dyn Clock) -> bool {
+        self.deadline < clock.now()
     }
 
     /// Create a [`Timing`] from a naive anchor, seemingly ISO-8601 or RFC-3339, datetime string and a valid
@@ -138,6 +200,9 @@ impl Timing {
             naive_anchor: ndt,
             anchor: anchor.to_utc().to_timestamp(),
             deadline: anchor.to_utc().to_timestamp(),
+            index: Some(0),
+            extra: false,
+            rolled_from: None,
         })
     }
 
@@ -160,37 +225,308 @@ impl Timing {
     /// timing still would be in America/New_York time rather than Africa/Lagos time, meaning when it's 10am in
     /// Africa/Lagos nothing happens for that event until it is 10am in America/New_York or 3pm-5pm in
     /// Africa/Lagos depending on DST.
-    /// 
+    ///
     /// # Frequency Expression Validity.
-    /// 
+    ///
     /// Some frequency expressions have a date until when they are no longer valid. When the `until` of a frequency
-    /// is not `None`, the evaluated timestamp is comapred with the declared validity timestamp, and while the 
+    /// is not `None`, the evaluated timestamp is comapred with the declared validity timestamp, and while the
     /// evaluated timestamp is less than the validity timestamp a [`Result::Ok`] is returned with the refreshed timing,
     /// otherwise a [`Result::Err`] is returned with a [`TimingError::FrequencyExpired`]
     ///
+    /// The occurrences from the deadline to the evaluated timestamp are counted to advance the index of the deadline,
+    /// and when the frequency has a `count` of occurrences, once the count is used up a [`Result::Err`] is returned
+    /// with a [`TimingError::CountExhausted`]. Without a count, only so many occurrences are counted, past which the
+    /// index is left to be located when it is needed.
+    ///
     /// Timing generated from regular frequency expressions are relative to the anchor timestmap, meanwhile timing
     /// generated from custom frequency expressions are relative to the current timestamp.
     pub fn refresh(self, frequency: &StFrequency) -> Result<Self, TimingError> {
-        let timing_opts = &timing::TimingOptions::default();
-        let timing_factory: &dyn Fn(_) -> _ = &|deadline| Timing { deadline, ..self };
+        self.refresh_with(frequency, &SystemClock)
+    }
+
+    /// Same as [`Timing::refresh`] but the current timestamp is read from the given [`Clock`]
+    pub fn refresh_with(
+        self,
+        frequency: &StFrequency,
+        clock: &dyn Clock,
+    ) -> Result<Self, TimingError> {
+        if !self.is_passed_due_with(clock) {
+            return Ok(self);
+        }
+
+        let next = self.next_with(frequency, clock)?;
+        let count = frequency.get_count();
+        // The index is needed to check the count, so it is located right away
+        let timing = match (self.index, count) {
+            (None, Some(_)) => self.located(frequency)?,
+            _ => self,
+        };
+
+        timing.counted(next, frequency, count)
+    }
+
+    /// Returns the given next timing with the index of its deadline, counting the occurrences skipped on the way
+    /// from the current deadline, or a [`TimingError::CountExhausted`] once they are more than the given count
+    fn counted(
+        self,
+        next: Timing,
+        frequency: &StFrequency,
+        count: Option<u32>,
+    ) -> Result<Self, TimingError> {
+        let mut occurrence = self;
+        let mut steps = 0;
+
+        while occurrence.deadline < next.deadline {
+            if occurrence.index.is_none() || (count.is_none() && steps == MAX_INDEXED_STEPS) {
+                return Ok(Timing {
+                    index: None,
+                    ..next
+                });
+            }
+
+            let clock = FixedClock::new(occurrence.deadline + Timestamp::Millis(1));
+            let step = occurrence.next_with(frequency, &clock)?;
+
+            if step.deadline <= occurrence.deadline {
+                break;
+            }
+
+            // An included time is not an occurrence of the frequency, so it is not counted
+            occurrence = Timing {
+                index: occurrence
+                    .index
+                    .map(|index| index + u64::from(!occurrence.extra)),
+                ..step
+            };
+            steps += 1;
+
+            if let (Some(count), Some(index)) = (count, occurrence.index) {
+                if index >= count as u64 {
+                    return Err(TimingError::CountExhausted);
+                }
+            }
+        }
+
+        Ok(Timing {
+            index: occurrence.index,
+            ..next
+        })
+    }
+
+    /// Same as [`Timing::refresh_with`] but the occurrences of the frequency, if any, are amended with the given
+    /// dates, skipping the excluded times and also occurring at the included ones.
+    ///
+    /// The times included before the anchor are ignored. Like the `RDATE` of calendars, the included times are not
+    /// bound by the `until` or `count` of the frequency, nor are they counted among its occurrences, and the timing
+    /// is only expired once both run out, with the error of the frequency or a [`TimingError::MissingExpression`]
+    /// when there is none.
+    ///
+    /// The occurrences of the frequency are rolled by the given business days, if any, before they are amended, see
+    /// [`Timing::refresh_with_business_days`].
+    pub fn refresh_with_dates(
+        self,
+        frequency: Option<&StFrequency>,
+        dates: &OccurrenceDates,
+        business_days: Option<&BusinessDays>,
+        clock: &dyn Clock,
+    ) -> Result<Self, TimingError> {
+        if !self.is_passed_due_with(clock) {
+            return Ok(self);
+        }
+
+        // The occurrences of the frequency that are excluded on the way to the next one, which are kept to index an
+        // included time that comes before it
+        let mut skipped: Vec<Timing> = vec![];
+        let mut next = match frequency {
+            Some(frequency) => self.refresh_with_business_days(frequency, business_days, clock),
+            None => Err(TimingError::MissingExpression),
+        };
+
+        while let (Ok(step), Some(frequency)) = (next.as_ref(), frequency) {
+            if !dates.is_excluded(step.deadline) {
+                break;
+            }
+
+            let clock = FixedClock::new(step.deadline + Timestamp::Millis(1));
+            let step = *step;
+            next = match step.refresh_with_business_days(frequency, business_days, &clock) {
+                // Guards against a frequency that fails to move forward, which would otherwise never end
+                Ok(following) if following.deadline <= step.deadline => {
+                    Err(TimingError::NonDeterministic)
+                }
+                following => following,
+            };
+            skipped.push(step);
+        }
+
+        let included = dates.next_included(clock.now().max(self.anchor - Timestamp::Millis(1)));
+
+        match (next, included) {
+            (Ok(next), Some(at)) if at >= next.deadline => Ok(next),
+            // Only a frequency that ran out leaves the included times to go on with
+            (Err(err), _)
+                if !matches!(
+                    err,
+                    TimingError::FrequencyExpired
+                        | TimingError::CountExhausted
+                        | TimingError::MissingExpression
+                ) =>
+            {
+                Err(err)
+            }
+            (next, Some(at)) => {
+                // The index of the frequency's first occurrence after the included time
+                let following = skipped
+                    .iter()
+                    .chain(next.as_ref().ok())
+                    .find(|step| step.deadline > at)
+                    .map(|step| step.index);
+                let previous = skipped.last().unwrap_or(&self);
+                let index = following.unwrap_or(
+                    previous
+                        .index
+                        .map(|index| index + u64::from(!previous.extra)),
+                );
+
+                Ok(Timing {
+                    deadline: at,
+                    index,
+                    extra: true,
+                    rolled_from: None,
+                    ..self
+                })
+            }
+            (next, None) => next,
+        }
+    }
+
+    /// Same as [`Timing::refresh_with`] but the occurrences of the frequency that do not fall on one of the given
+    /// business days, if any, are rolled to one in the timezone of the anchor, at the same time of the day, or
+    /// skipped, by the days' roll convention.
+    ///
+    /// An occurrence rolled to the same time as the one before it is the same occurrence, and the index of an
+    /// occurrence that is rolled is that of the occurrence of the frequency it was rolled from. A
+    /// [`TimingError::NonDeterministic`] is returned when the frequency does not occur on a business day for too
+    /// many occurrences, e.g. when all of them are skipped.
+    pub fn refresh_with_business_days(
+        self,
+        frequency: &StFrequency,
+        business_days: Option<&BusinessDays>,
+        clock: &dyn Clock,
+    ) -> Result<Self, TimingError> {
+        let business_days = match business_days {
+            Some(business_days) => business_days,
+            None => return self.refresh_with(frequency, clock),
+        };
+
+        if !self.is_passed_due_with(clock) {
+            return Ok(self);
+        }
+
+        let now = clock.now();
+        let mut skipped = 0;
+        let mut occurrence = Timing {
+            deadline: self.rolled_from.unwrap_or(self.deadline),
+            rolled_from: None,
+            ..self
+        };
+        // An occurrence of the frequency before the current timestamp can be rolled after it, so the occurrences are
+        // evaluated from as far back as a roll goes
+        let mut from = (now - roll_window()).max(occurrence.deadline + Timestamp::Millis(1));
+
+        loop {
+            let step = occurrence.refresh_with(frequency, &FixedClock::new(from))?;
+
+            // Guards against a frequency that fails to move forward, which would otherwise never end
+            if step.deadline <= occurrence.deadline {
+                return Err(TimingError::NonDeterministic);
+            }
+
+            match timing::roll_to_business_day(step.deadline, self.timezone, business_days) {
+                Some(deadline) if deadline > now && deadline > self.deadline => {
+                    return Ok(Timing {
+                        deadline,
+                        rolled_from: (deadline != step.deadline).then_some(step.deadline),
+                        ..step
+                    });
+                }
+                _ if step.deadline > now => {
+                    skipped += 1;
+
+                    if skipped > MAX_SKIPPED_OCCURRENCES {
+                        return Err(TimingError::NonDeterministic);
+                    }
+                }
+                _ => {}
+            }
+
+            from = step.deadline + Timestamp::Millis(1);
+            occurrence = step;
+        }
+    }
+
+    /// Returns the timing with the index of its deadline located among the occurrences of the frequency, which are
+    /// counted from the anchor, and whether the deadline is not one of them, e.g. because it is an included time
+    fn located(self, frequency: &StFrequency) -> Result<Self, TimingError> {
+        let mut index = 0;
+        let mut occurrence = Timing {
+            deadline: self.anchor,
+            index: Some(0),
+            extra: false,
+            rolled_from: None,
+            ..self
+        };
+
+        while occurrence.deadline < self.deadline {
+            let clock = FixedClock::new(occurrence.deadline + Timestamp::Millis(1));
+            let next = occurrence.next_with(frequency, &clock)?;
+
+            if next.deadline <= occurrence.deadline {
+                break;
+            }
+
+            occurrence = next;
+            index += 1;
+        }
+
+        Ok(Timing {
+            index: Some(index),
+            extra: occurrence.deadline != self.deadline,
+            ..self
+        })
+    }
+
+    /// Evaluates the timing of the frequency's first occurrence after the current timestamp of the given [`Clock`],
+    /// see [`Timing::refresh`]
+    fn next_with(self, frequency: &StFrequency, clock: &dyn Clock) -> Result<Self, TimingError> {
+        let timing_opts = &timing::TimingOptions::with_clock(clock);
+        let timing_factory: &dyn Fn(_) -> _ = &|deadline| Timing {
+            deadline,
+            extra: false,
+            rolled_from: None,
+            ..self
+        };
         let checked_validity: &dyn Fn(&Option<_>, _) -> Result<_, _> = &|u, n| {
             u.map(|u| (u <= n).then_some(Err(TimingError::FrequencyExpired)))
                 .flatten()
                 .unwrap_or(Ok(timing_factory(n)))
         };
 
-        if !self.is_passed_due() {
-            return Ok(self);
-        }
-
         match frequency {
             StFrequency::Custom(cstm) => {
-                let itr = (&cstm.cron_expressions).iter().take(3).map(|cron| {
-                    // TODO: Test to see that it reps time from the perspective of tz ✅
-                    let start = Local::now().with_timezone(&self.timezone);
-                    let result = parse(cron.as_str(), &start);
-                    result.map(|v| v.to_utc().to_timestamp())
-                });
+                let itr = cstm
+                    .cron_expressions
+                    .iter()
+                    .take(MAX_CRON_EXPRESSIONS)
+                    .map(|cron| {
+                        // TODO: Test to see that it reps time from the perspective of tz ✅
+                        let start = timing_opts
+                            .curtime
+                            .to_datetime()
+                            .with_timezone(&self.timezone);
+                        let result = parse(cron.as_str(), &start);
+                        result.map(|v| v.to_utc().to_timestamp())
+                    });
 
                 let (values, errors): (Vec<_>, Vec<_>) = itr.partition(|v| v.is_ok());
                 let values = values.into_iter().filter_map(|v| v.ok());
@@ -198,15 +534,15 @@ impl Timing {
                 let first_error = errors.take(1).last();
                 let first_value = values.min();
 
-                if first_error.is_some() {
-                    return Err(first_error.unwrap().into());
+                if let Some(error) = first_error {
+                    return Err(error.into());
                 }
 
                 match first_value {
                     Some(value) => cstm.until.map_or_else(
                         || Ok(timing_factory(value)),
                         |until| {
-                            if value.min(until) == value {
+                            if value < until {
                                 Ok(timing_factory(value))
                             } else {
                                 Err(TimingError::FrequencyExpired)
@@ -228,40 +564,43 @@ impl Timing {
                 StFrequencyExpression::Weekly(expr) => {
                     let next = Self::next_weekly_timestamp(
                         self.anchor,
+                        self.timezone,
                         expr.every,
                         Some(&expr.subexpr.weekdays),
                         timing_opts,
                     );
                     checked_validity(&reg.until, next)
                 }
-                StFrequencyExpression::Monthly(expr) => match &expr.subexpr {
-                    StMonthlySubExpression::OnDays(subexpr) => {
-                        let next = Self::next_monthly_schedule(
-                            self.anchor,
-                            expr.every,
-                            Some(&subexpr.days),
-                            None,
-                            timing_opts,
-                        )?;
-                        checked_validity(&reg.until, next)
-                    }
-                    StMonthlySubExpression::OnThe(subexpr) => {
-                        let next = Self::next_monthly_schedule(
-                            self.anchor,
-                            expr.every,
-                            None,
-                            Some((subexpr.ordinal, subexpr.weekday)),
-                            timing_opts,
-                        )?;
-                        checked_validity(&reg.until, next)
-                    }
-                },
+                StFrequencyExpression::Monthly(expr) => {
+                    let days = match &expr.subexpr {
+                        StMonthlySubExpression::OnDays(subexpr) => {
+                            timing::MonthDays::Days(&subexpr.days)
+                        }
+                        StMonthlySubExpression::OnThe(subexpr) => timing::MonthDays::Ordinal(
+                            subexpr.ordinal,
+                            StWeekday::Const(subexpr.weekday),
+                        ),
+                    };
+                    let next = Self::next_monthly_timestamp(
+                        self.anchor,
+                        self.timezone,
+                        expr.every,
+                        &days,
+                        timing_opts,
+                    )?;
+                    checked_validity(&reg.until, next)
+                }
                 StFrequencyExpression::Yearly(expr) => {
+                    let days = match expr.subexpr.on {
+                        Some(on) => timing::MonthDays::Ordinal(on.ordinal, on.weekday),
+                        None => timing::MonthDays::Days(&[]),
+                    };
                     let next = Self::next_yearly_timestamp(
                         self.anchor,
+                        self.timezone,
                         expr.every,
                         &expr.subexpr.months,
-                        expr.subexpr.on.map(|o| (o.ordinal, o.weekday)),
+                        &days,
                         timing_opts,
                     )?;
                     checked_validity(&reg.until, next)
@@ -290,6 +629,7 @@ impl Timing {
 
     fn next_weekly_timestamp(
         anchor: Timestamp,
+        timezone: Tz,
         every: u32,
         weekdays: Option<&Vec<StConstWeekday>>,
         options: &timing::TimingOptions,
@@ -299,7 +639,7 @@ impl Timing {
 
         let mut current_ts = options.curtime;
         let mut result = postprocess(
-            timing::get_next_weekly_timestamp(anchor, every, weekdays, &options),
+            timing::get_next_weekly_timestamp(anchor, timezone, every, weekdays, options),
             &current_ts,
         );
 
@@ -308,12 +648,12 @@ impl Timing {
             result = postprocess(
                 timing::get_next_weekly_timestamp(
                     anchor,
+                    timezone,
                     every,
                     // only pass `Some(Vec<_> v)` if v is not empty otherwise `None`
-                    weekdays.map(|v| (!v.is_empty()).then_some(v)).flatten(),
+                    weekdays.and_then(|v| (!v.is_empty()).then_some(v)),
                     &timing::TimingOptions {
                         curtime: current_ts,
-                        ..*options
                     },
                 ),
                 &current_ts,
@@ -323,60 +663,22 @@ impl Timing {
         result.unwrap()
     }
 
-    fn next_monthly_schedule(
+    fn next_monthly_timestamp(
         anchor: Timestamp,
+        timezone: Tz,
         every: u32,
-        days: Option<&Vec<u32>>,
-        ordinals: Option<(StOrdinals, StConstWeekday)>,
+        days: &timing::MonthDays,
         options: &timing::TimingOptions,
     ) -> Result<Timestamp, TimingError> {
-        let postprocess: &dyn Fn(Vec<Timestamp>, &_) -> Option<_> =
-            &|v, c| v.into_iter().filter(|t| t > c).min();
-
-        if let Some(ordinals) = ordinals {
-            return timing::get_next_monthly_ordinal_timestamp(anchor, every, ordinals, options);
-        };
-
-        if let Some(days) = days {
-            let mut current_ts = options.curtime;
-            let mut result = postprocess(
-                timing::get_next_monthly_days_timestamp(anchor, every, days, options),
-                &current_ts,
-            );
-
-            while result.is_none() {
-                current_ts = current_ts
-                    .to_datetime()
-                    .checked_add_months(Months::new(1))
-                    .as_ref()
-                    .map(DateTime::<Utc>::to_timestamp)
-                    .ok_or(ParseError::ResolutionFailed)?;
-                result = postprocess(
-                    timing::get_next_monthly_days_timestamp(
-                        anchor,
-                        every,
-                        days.as_ref(),
-                        &timing::TimingOptions {
-                            curtime: current_ts,
-                            ..*options
-                        },
-                    ),
-                    &current_ts,
-                )
-            }
-
-            return Ok(result.unwrap());
-        }
-
-        // days.xor(ordinals).is_none();
-        Err(TimingError::MissingExpression)
+        timing::get_next_monthly_timestamp(anchor, timezone, every, days, options)
     }
 
     fn next_yearly_timestamp(
         anchor: Timestamp,
+        timezone: Tz,
         every: u32,
-        months: &Vec<StMonth>,
-        ordinals: Option<(StOrdinals, StWeekday)>,
+        months: &[StMonth],
+        days: &timing::MonthDays,
         options: &timing::TimingOptions,
     ) -> Result<Timestamp, TimingError> {
         // consider returning an error, MissingExpression, if months is empty
@@ -384,62 +686,7 @@ impl Timing {
             return Err(TimingError::MissingExpression);
         }
 
-        let postprocess: &dyn Fn(Vec<_>, &_) -> (_, _) = &|v, c| {
-            let (values, errors): (Vec<_>, Vec<_>) = v
-                .into_iter()
-                .partition(|p: &Result<Timestamp, TimingError>| p.is_ok());
-            let values = values.into_iter().filter_map(|v| v.ok());
-            let errors = errors.into_iter().filter_map(|v| v.err());
-            (errors.take(1).last(), values.filter(|t| t > c).min())
-        };
-
-        let mut current_ts = options.curtime;
-        let mut results = postprocess(
-            timing::get_next_yearly_timestamp(anchor, every, months, ordinals, options),
-            &current_ts,
-        );
-
-        {
-            let month = months
-                .iter()
-                .min()
-                .map(|f| (*f).into())
-                // we can unwrap so long as we guarantee that months is not empty as done above, up! up!
-                .unwrap(); // _or_else(|| anchor.to_datetime().month0());
-                           // This most likely would happen when the months supplied to this fn are behind the current datetime month
-                           // so we want to shift the current datetime one year forward starting from the minimum of the months
-            while results.1.is_none() && results.0.is_none() {
-                current_ts = {
-                    let dt = current_ts.to_datetime();
-                    let (year, month) = (dt.year() + 1, month);
-                    let day = dt.day().min(timing::get_days_in_year_month(year, month));
-                    let ndt = NaiveDate::from_ymd_opt(year, month, day)
-                        .unwrap()
-                        .and_time(dt.time());
-                    DateTime::<Utc>::from_naive_utc_and_offset(ndt, dt.offset().clone())
-                        .to_timestamp()
-                };
-                results = postprocess(
-                    timing::get_next_yearly_timestamp(
-                        anchor,
-                        every,
-                        months,
-                        ordinals,
-                        &timing::TimingOptions {
-                            curtime: current_ts,
-                            ..*options
-                        },
-                    ),
-                    &current_ts,
-                );
-            }
-        }
-
-        if results.1.is_none() && results.0.is_some() {
-            Err(results.0.unwrap())
-        } else {
-            Ok(results.1.unwrap())
-        }
+        timing::get_next_yearly_timestamp(anchor, timezone, every, months, days, options)
     }
 }
 
@@ -468,12 +715,7 @@ impl StSchedule {
         timezone: &str,
         priority: Option<StPriority>,
     ) -> StSchedule {
-        StSchedule {
-            id: String::from(id),
-            frequency: None,
-            priority,
-            timing: Timing::with_naive_anchor_tz(naive_anchor, timezone).unwrap(),
-        }
+        Self::try_with_frequency(id, naive_anchor, timezone, None, priority, &SystemClock).unwrap()
     }
 
     /// Initializes a structure for schedule representations
@@ -500,18 +742,7 @@ impl StSchedule {
         freq: StRegularFrequency,
         priority: Option<StPriority>,
     ) -> StSchedule {
-        let frequency = StFrequency::Regular(freq);
-        let timing = Timing::with_naive_anchor_tz(naive_anchor, timezone)
-            .unwrap()
-            .refresh(&frequency)
-            .unwrap();
-
-        StSchedule {
-            id: String::from(id),
-            frequency: Some(frequency),
-            priority,
-            timing,
-        }
+        Self::with_regular_and_clock(id, naive_anchor, timezone, freq, priority, &SystemClock)
     }
 
     /// Initializes a structure for schedule representations
@@ -538,32 +769,7 @@ impl StSchedule {
         freq: StCustomFrequency,
         priority: Option<StPriority>,
     ) -> StSchedule {
-        let frequency = StFrequency::Custom(freq);
-        let timing = Timing::with_naive_anchor_tz(naive_anchor, timezone)
-            .unwrap()
-            .refresh(&frequency)
-            .unwrap();
-
-        StSchedule {
-            id: String::from(id),
-            frequency: Some(frequency),
-            priority,
-            timing,
-        }
-    }
-
-    pub(crate) fn with_frequency_and_timing(
-        id: &str,
-        timing: Timing,
-        frequency: StFrequency,
-        priority: Option<StPriority>,
-    ) -> StSchedule {
-        StSchedule {
-            id: String::from(id),
-            frequency: Some(frequency),
-            timing,
-            priority,
-        }
+        Self::with_custom_and_clock(id, naive_anchor, timezone, freq, priority, &SystemClock)
     }
 
     pub(crate) fn get_id_as_str(&self) -> &str {
@@ -574,139 +780,772 @@ impl StSchedule {
         self.timing.anchor.as_ms()
     }
 
+    pub fn get_deadline_millis(&self) -> i64 {
+        self.timing.deadline.as_ms()
+    }
+
+    /// Gets the times of the occurrences of the schedule from the given time until before the given end, in
+    /// milliseconds, earliest first
+    pub fn get_occurrences(&self, from: i64, to: i64) -> Vec<i64> {
+        self.occurrences(Timestamp::Millis(from), Timestamp::Millis(to))
+            .map(|occurrence| occurrence.get_deadline_millis())
+            .collect()
+    }
+
+    /// Gets the times of the given number of occurrences of the schedule after the given time, in milliseconds,
+    /// earliest first, which are fewer when the schedule's frequency expires before then
+    pub fn get_occurrences_after(&self, after: i64, count: u32) -> Vec<i64> {
+        self.occurrences_after(Timestamp::Millis(after))
+            .take(count as usize)
+            .map(|occurrence| occurrence.get_deadline_millis())
+            .collect()
+    }
+
+    /// Gets the time of the latest occurrence of the schedule before the given time, in milliseconds, if any
+    pub fn get_previous_occurrence(&self, before: i64) -> Option<i64> {
+        self.previous_occurrence(Timestamp::Millis(before))
+            .map(|occurrence| occurrence.get_deadline_millis())
+    }
+
+    /// Checks if the schedule occurs at exactly the given time, in milliseconds
+    pub fn is_occurrence_millis(&self, ts: i64) -> bool {
+        self.is_occurrence(Timestamp::Millis(ts))
+    }
+
     pub fn get_priority(&self) -> Option<StPriority> {
         self.priority
     }
 
     pub fn get_custom_frequency(&self) -> Option<StCustomFrequency> {
-        if let Some(freq) = &self.frequency {
-            if let StFrequency::Custom(cstm_freq) = freq {
-                return Some(cstm_freq.to_owned());
-            }
+        if let Some(StFrequency::Custom(cstm_freq)) = &self.frequency {
+            return Some(cstm_freq.to_owned());
         }
 
         None
     }
 
     pub fn get_regular_frequency(&self) -> Option<StRegularFrequency> {
-        if let Some(freq) = &self.frequency {
-            if let StFrequency::Regular(reg_freq) = freq {
-                return Some(reg_freq.to_owned());
-            }
+        if let Some(StFrequency::Regular(reg_freq)) = &self.frequency {
+            return Some(reg_freq.to_owned());
         }
 
         None
     }
 
     pub fn is_passed(&self) -> bool {
-        self.timing.deadline < timestamp()
+        self.is_passed_with(&SystemClock)
     }
-}
-
-impl StSchedule {
-    /// Calculate the upcoming schedule for the given schedule
-    pub fn get_upcoming_schedule(&self) -> Result<StSchedule, TimingError> {
-        // Check if the current schedule has passed and generate the next
-        // schedule relative to now from the StSchedule struct
-        if !self.is_passed() {
-            return Err(TimingError::Conflict);
-        }
-
-        if let Some(frequency) = &self.frequency {
-            let timing = self.timing.refresh(frequency)?;
 
-            Ok(StSchedule::with_frequency_and_timing(
-                &self.id,
-                timing,
-                frequency.clone(),
-                self.priority,
-            ))
-        } else {
-            Err(TimingError::MissingExpression)
-        }
+    pub fn get_catch_up_policy(&self) -> Option<StCatchUpPolicy> {
+        self.catch_up
     }
-}
 
-impl fmt::Display for StSchedule {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:#?}", &self)
+    /// Sets the policy for the missed occurrences of this schedule, overriding the scheduler's policy.
+    /// Passing `undefined` falls back to the scheduler's policy.
+    pub fn set_catch_up_policy(&mut self, policy: Option<StCatchUpPolicy>) {
+        self.catch_up = policy;
     }
-}
 
-impl Ord for StSchedule {
-    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
-        let order = self.timing.deadline.cmp(&other.timing.deadline);
+    pub fn get_grace_period(&self) -> Option<u32> {
+        self.grace_period.map(|g| g.as_ms() as u32)
+    }
 
-        match order {
-            Ordering::Equal => self.priority.cmp(&other.priority),
-            _ => order,
-        }
+    /// Sets how long, in milliseconds, after its planned time an occurrence becomes overdue unless it is
+    /// acknowledged, overriding the scheduler's grace period. Passing `undefined` falls back to the
+    /// scheduler's grace period.
+    pub fn set_grace_period(&mut self, millis: Option<u32>) {
+        self.grace_period = millis.map(|ms| Timestamp::Millis(ms as i64));
     }
-}
 
-impl PartialOrd for StSchedule {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    /// Returns the reminder offsets of this schedule in milliseconds, in descending order
+    pub fn get_reminders(&self) -> Vec<u32> {
+        self.reminders.iter().map(|r| r.as_ms() as u32).collect()
     }
-}
 
-mod timing {
-    use core::convert::TryInto;
+    /// Sets how long, in milliseconds, before each occurrence reminders are dispatched,
+    /// e.g. `[86400000, 3600000, 600000]` for a day, an hour and ten minutes before.
+    ///
+    /// Duplicate offsets and offsets of zero are ignored.
+    pub fn set_reminders(&mut self, offsets: Vec<u32>) {
+        let mut reminders = offsets
+            .into_iter()
+            .filter(|offset| *offset > 0)
+            .map(|offset| Timestamp::Millis(offset as i64))
+            .collect::<Vec<_>>();
 
-    use chrono::prelude::*;
+        reminders.sort_by(|a, b| b.cmp(a));
+        reminders.dedup();
+        self.reminders = reminders;
+    }
 
-    use crate::core::errors::TimingError;
-    use crate::core::frequency::{StConstWeekday, StMonth, StOrdinals, StVarWeekday, StWeekday};
-    use crate::core::time::{timestamp, Timestamp, Ts};
-    use crate::core::time::{DAY_MILLIS, HOUR_MILLIS, INDEXED_MONTH_DAYS, WEEK_MILLIS};
-    use crate::utils::{filter_unique, map_unique};
+    /// Returns the times the schedule is excluded from in milliseconds, earliest first
+    pub fn get_exclusions(&self) -> Vec<i64> {
+        self.dates.excluded.iter().map(Timestamp::as_ms).collect()
+    }
 
-    const DOW: u32 = 7;
-    const MOY: u32 = 12;
-    const DOY: u32 = 365;
+    /// Returns the times the schedule is included at besides the occurrences of its frequency in milliseconds,
+    /// earliest first
+    pub fn get_inclusions(&self) -> Vec<i64> {
+        self.dates.included.iter().map(Timestamp::as_ms).collect()
+    }
 
-    #[derive(Debug, Clone, Copy)]
-    pub struct TimingOptions {
-        pub curtime: Timestamp,
+    /// Excludes the schedule from the given time in milliseconds, e.g. to skip one of its occurrences, moving
+    /// the schedule on to its next occurrence when it is the one excluded.
+    ///
+    /// Returns `false` when the time is already excluded.
+    pub fn add_exclusion(&mut self, at: i64) -> bool {
+        self.add_exclusion_with(Timestamp::Millis(at), &SystemClock)
     }
 
-    impl Default for TimingOptions {
-        fn default() -> Self {
-            TimingOptions {
-                curtime: timestamp(),
-            }
-        }
+    /// Removes the exclusion of the given time in milliseconds, so the schedule occurs at it again if it is one
+    /// of its occurrences.
+    ///
+    /// Returns `false` when the time is not excluded.
+    pub fn remove_exclusion(&mut self, at: i64) -> bool {
+        self.remove_exclusion_with(Timestamp::Millis(at), &SystemClock)
     }
 
-    // since we are asuming to be in a safe `float64` range, `2 ^ 53 - 1`, then it's guaranted that we
-    // can also safely convert down from a `uint64` to an `int64`.
+    /// Includes the given time in milliseconds among the occurrences of the schedule, e.g. for an extra
+    /// session, moving the schedule to it when it comes before its next occurrence.
+    ///
+    /// Returns `false` when the time is already included.
+    pub fn add_inclusion(&mut self, at: i64) -> bool {
+        self.add_inclusion_with(Timestamp::Millis(at), &SystemClock)
+    }
 
-    // The largest supported value that `every` can be is `2_501_999_792` any value higher than this
-    // will go beyond the floating point range of `2 ^ 53 - 1` and cause an overflow which leads to
-    // a panic in the execution of the following code.
-    // We may set up a validation that ensures supplied values are in the constrained ranges.
-    pub fn get_next_hourly_timestamp(
-        anchor_ts: Timestamp,
-        every: u32,
-        options: &TimingOptions,
-    ) -> Timestamp {
-        let current_ts = options.curtime;
-        let hours_ms = HOUR_MILLIS * every as u64;
-        let hours_ms_f64: f64 = num::cast(hours_ms).unwrap();
+    /// Removes the given time in milliseconds from the times included among the occurrences of the schedule.
+    ///
+    /// Returns `false` when the time is not included.
+    pub fn remove_inclusion(&mut self, at: i64) -> bool {
+        self.remove_inclusion_with(Timestamp::Millis(at), &SystemClock)
+    }
 
-        if anchor_ts >= current_ts {
-            return anchor_ts + Timestamp::Millis(hours_ms.try_into().unwrap());
-        }
+    /// Returns the calendar of the days that are not business days for the schedule, if it has one
+    pub fn get_holiday_calendar(&self) -> Option<StHolidayCalendar> {
+        self.business_days
+            .as_ref()
+            .map(|business_days| business_days.get_calendar().clone())
+    }
 
-        let elapsed_ts = current_ts - anchor_ts;
-        let elapsed_factor = elapsed_ts.as_ms_f64() / hours_ms_f64;
-        let next_hour_ms = hours_ms_f64 * elapsed_factor.ceil();
+    /// Returns what happens to the occurrences of the schedule that do not fall on a business day, if it has a
+    /// holiday calendar
+    pub fn get_roll_convention(&self) -> Option<StRollConvention> {
+        self.business_days.as_ref().map(BusinessDays::get_roll)
+    }
 
-        return anchor_ts + Timestamp::Millis(num::cast(next_hour_ms).unwrap());
+    /// Sets the calendar of the days that are not business days for the schedule, along with what happens to its
+    /// occurrences that fall on one of them, moving the schedule on to its next occurrence accordingly. Passing
+    /// `undefined` lets the schedule occur on any day.
+    pub fn set_holiday_calendar(
+        &mut self,
+        calendar: Option<StHolidayCalendar>,
+        roll: StRollConvention,
+    ) {
+        self.set_holiday_calendar_with(calendar, roll, &SystemClock)
     }
+}
 
-    // The largest supported value that `every` can be is `104_249_991` any value higher than this
-    // will go beyond the floating point range of `2 ^ 53 - 1` and cause an overflow which leads to
+impl StSchedule {
+    /// Same as [`StSchedule::with_regular`] but the timing is refreshed relative to the
+    /// current timestamp of the given [`Clock`]
+    ///
+    /// # Panics
+    ///
+    /// Panics when the `naive_anchor` date string could not be successfully parsed from string
+    /// or the `timezone` could not be successfully parsed from string.
+    pub fn with_regular_and_clock(
+        id: &str,
+        naive_anchor: &str,
+        timezone: &str,
+        freq: StRegularFrequency,
+        priority: Option<StPriority>,
+        clock: &dyn Clock,
+    ) -> StSchedule {
+        let frequency = Some(StFrequency::Regular(freq));
+        Self::try_with_frequency(id, naive_anchor, timezone, frequency, priority, clock).unwrap()
+    }
+
+    /// Same as [`StSchedule::with_custom`] but the timing is refreshed relative to the
+    /// current timestamp of the given [`Clock`]
+    ///
+    /// # Panics
+    ///
+    /// Panics when the `naive_anchor` date string could not be successfully parsed from string
+    /// or the `timezone` could not be successfully parsed from string.
+    pub fn with_custom_and_clock(
+        id: &str,
+        naive_anchor: &str,
+        timezone: &str,
+        freq: StCustomFrequency,
+        priority: Option<StPriority>,
+        clock: &dyn Clock,
+    ) -> StSchedule {
+        let frequency = Some(StFrequency::Custom(freq));
+        Self::try_with_frequency(id, naive_anchor, timezone, frequency, priority, clock).unwrap()
+    }
+
+    /// Initializes a schedule with any frequency, or none for a one-off schedule, with its timing refreshed
+    /// relative to the current timestamp of the given [`Clock`]
+    ///
+    /// # Error
+    ///
+    /// Returns a [`TimingError`] wrapped in an [`Err`] result when the `naive_anchor` or `timezone` could not be
+    /// parsed, or the timing could not be refreshed with the frequency, e.g. when it is already expired.
+    pub fn try_with_frequency(
+        id: &str,
+        naive_anchor: &str,
+        timezone: &str,
+        frequency: Option<StFrequency>,
+        priority: Option<StPriority>,
+        clock: &dyn Clock,
+    ) -> Result<StSchedule, TimingError> {
+        let timing = Timing::with_naive_anchor_tz(naive_anchor, timezone)?;
+        let timing = match &frequency {
+            Some(frequency) => timing.refresh_with(frequency, clock)?,
+            None => timing,
+        };
+
+        Ok(StSchedule {
+            id: String::from(id),
+            frequency,
+            priority,
+            timing,
+            catch_up: None,
+            reminders: vec![],
+            grace_period: None,
+            dates: OccurrenceDates::default(),
+            business_days: None,
+        })
+    }
+
+    /// Checks if the schedule's deadline has passed relative to the given [`Clock`]
+    pub fn is_passed_with(&self, clock: &dyn Clock) -> bool {
+        self.timing.is_passed_due_with(clock)
+    }
+
+    /// Calculate the upcoming schedule for the given schedule
+    pub fn get_upcoming_schedule(&self) -> Result<StSchedule, TimingError> {
+        self.get_upcoming_schedule_with(&SystemClock)
+    }
+
+    /// Same as [`StSchedule::get_upcoming_schedule`] but the upcoming schedule is calculated
+    /// relative to the current timestamp of the given [`Clock`]
+    pub fn get_upcoming_schedule_with(&self, clock: &dyn Clock) -> Result<StSchedule, TimingError> {
+        // Check if the current schedule has passed and generate the next
+        // schedule relative to now from the StSchedule struct
+        if !self.is_passed_with(clock) {
+            return Err(TimingError::Conflict);
+        }
+
+        let timing = self.timing.refresh_with_dates(
+            self.frequency.as_ref(),
+            &self.dates,
+            self.business_days.as_ref(),
+            clock,
+        )?;
+
+        Ok(StSchedule {
+            timing,
+            ..self.clone()
+        })
+    }
+
+    /// Returns how long after an occurrence is dispatched it becomes overdue, if the schedule overrides it
+    pub fn get_grace_period_override(&self) -> Option<Timestamp> {
+        self.grace_period
+    }
+
+    /// Returns the time until when the schedule's frequency is valid, if it repeats and has one
+    pub fn get_until(&self) -> Option<Timestamp> {
+        match &self.frequency {
+            Some(StFrequency::Regular(reg)) => reg.until,
+            Some(StFrequency::Custom(cstm)) => cstm.until,
+            None => None,
+        }
+    }
+
+    /// Returns the frequency the schedule repeats with, if any
+    pub fn get_frequency(&self) -> Option<&StFrequency> {
+        self.frequency.as_ref()
+    }
+
+    /// Returns how long before each occurrence reminders are dispatched, in descending order
+    pub fn get_reminder_offsets(&self) -> &[Timestamp] {
+        &self.reminders
+    }
+
+    /// Returns the times the schedule is excluded from and included at, besides the occurrences of its frequency
+    pub fn get_dates(&self) -> &OccurrenceDates {
+        &self.dates
+    }
+
+    /// Same as [`StSchedule::add_exclusion`] but the schedule is moved relative to the current timestamp of the
+    /// given [`Clock`]
+    pub fn add_exclusion_with(&mut self, at: Timestamp, clock: &dyn Clock) -> bool {
+        let added = insert_sorted(&mut self.dates.excluded, at);
+        self.settle_with(clock);
+        added
+    }
+
+    /// Same as [`StSchedule::remove_exclusion`] but the schedule is moved relative to the current timestamp of
+    /// the given [`Clock`]
+    pub fn remove_exclusion_with(&mut self, at: Timestamp, clock: &dyn Clock) -> bool {
+        let removed = remove_sorted(&mut self.dates.excluded, at);
+        self.settle_with(clock);
+        removed
+    }
+
+    /// Same as [`StSchedule::add_inclusion`] but the schedule is moved relative to the current timestamp of the
+    /// given [`Clock`]
+    pub fn add_inclusion_with(&mut self, at: Timestamp, clock: &dyn Clock) -> bool {
+        let added = insert_sorted(&mut self.dates.included, at);
+        self.settle_with(clock);
+        added
+    }
+
+    /// Same as [`StSchedule::remove_inclusion`] but the schedule is moved relative to the current timestamp of
+    /// the given [`Clock`]
+    pub fn remove_inclusion_with(&mut self, at: Timestamp, clock: &dyn Clock) -> bool {
+        let removed = remove_sorted(&mut self.dates.included, at);
+        self.settle_with(clock);
+        removed
+    }
+
+    /// Returns the business days the schedule occurs on, if it has a holiday calendar
+    pub fn get_business_days(&self) -> Option<&BusinessDays> {
+        self.business_days.as_ref()
+    }
+
+    /// Same as [`StSchedule::set_holiday_calendar`] but the schedule is moved relative to the current timestamp of
+    /// the given [`Clock`]
+    pub fn set_holiday_calendar_with(
+        &mut self,
+        calendar: Option<StHolidayCalendar>,
+        roll: StRollConvention,
+        clock: &dyn Clock,
+    ) {
+        self.business_days = calendar.map(|calendar| BusinessDays::new(calendar, roll));
+        self.settle_with(clock);
+    }
+
+    /// Moves the schedule's deadline, after its dates or business days were amended, to its first occurrence from the earliest of
+    /// its deadline and the current timestamp of the given [`Clock`], so an occurrence that is due but not fired
+    /// yet is kept. The deadline is left as it is when there is no occurrence left.
+    fn settle_with(&mut self, clock: &dyn Clock) {
+        let from = self.timing.deadline.min(clock.now() + Timestamp::Millis(1));
+
+        if let Some(occurrence) = self.first_occurrence_from(from) {
+            self.timing = occurrence.timing;
+        }
+    }
+
+    /// Returns the timezone the schedule's anchor was declared in
+    pub fn get_timezone(&self) -> Tz {
+        self.timing.timezone
+    }
+
+    /// Returns the schedule's anchor as the wall time it was declared with in the schedule's timezone
+    pub fn get_local_anchor(&self) -> NaiveDateTime {
+        self.timing.naive_anchor
+    }
+
+    /// Returns the schedule's deadline as a wall time in the schedule's timezone
+    pub fn get_local_deadline(&self) -> NaiveDateTime {
+        self.timing
+            .deadline
+            .to_datetime()
+            .with_timezone(&self.timing.timezone)
+            .naive_local()
+    }
+
+    /// Returns the occurrence of the schedule at the given deadline, which is taken to be one of its occurrences.
+    ///
+    /// The deadline is only located among the occurrences right away for a frequency with a count, which are few,
+    /// otherwise when its index is needed, see [`StSchedule::get_occurrence_index`].
+    pub(crate) fn at_deadline(&self, deadline: Timestamp) -> StSchedule {
+        let occurrence = StSchedule {
+            timing: Timing {
+                deadline,
+                index: None,
+                extra: false,
+                rolled_from: None,
+                ..self.timing
+            },
+            ..self.clone()
+        };
+
+        match self.get_count() {
+            Some(_) => occurrence.located(),
+            None => occurrence,
+        }
+    }
+
+    /// Returns the schedule with the index of its deadline located among its occurrences, if it is yet to be
+    pub(crate) fn located(self) -> StSchedule {
+        if self.timing.index.is_some() {
+            return self;
+        }
+
+        let timing = match &self.frequency {
+            Some(frequency) => self.timing.located(frequency),
+            None => Ok(Timing {
+                index: Some(0),
+                extra: self.timing.deadline != self.timing.anchor,
+                ..self.timing
+            }),
+        };
+
+        match timing {
+            Ok(timing) => StSchedule { timing, ..self },
+            Err(err) => {
+                console_error!(
+                    "Failed to evaluate the occurrence of schedule with ID '{}': {}",
+                    self.get_id_as_str(),
+                    err
+                );
+                self
+            }
+        }
+    }
+
+    /// Calculate the occurrence that comes right after the schedule's current deadline
+    pub fn get_next_occurrence(&self) -> Result<StSchedule, TimingError> {
+        let clock = FixedClock::new(self.timing.deadline + Timestamp::Millis(1));
+        self.get_upcoming_schedule_with(&clock)
+    }
+
+    /// Returns the number of occurrences after which the schedule's frequency stops repeating, if it has one
+    pub fn get_count(&self) -> Option<u32> {
+        self.frequency.as_ref().and_then(StFrequency::get_count)
+    }
+
+    /// Returns the zero-based index of the schedule's current deadline among its occurrences, that is the
+    /// number of occurrences from the anchor (the first occurrence) that come before the deadline.
+    ///
+    /// The index is advanced as the schedule is refreshed, and is only evaluated from the anchor when it is yet to
+    /// be located, see [`StSchedule::located`].
+    pub fn get_occurrence_index(&self) -> Result<u64, TimingError> {
+        match (self.timing.index, &self.frequency) {
+            (Some(index), _) => Ok(index),
+            (None, Some(frequency)) => Ok(self.timing.located(frequency)?.index.unwrap_or(0)),
+            (None, None) => Ok(0),
+        }
+    }
+
+    /// Same as [`StSchedule::get_occurrence_index`] but the index is only returned while it is tracked, rather than
+    /// evaluated from the anchor when it is not
+    pub(crate) fn get_tracked_occurrence_index(&self) -> Option<u64> {
+        match (self.timing.index, &self.frequency) {
+            (None, None) => Some(0),
+            (index, _) => index,
+        }
+    }
+
+    /// Returns a lazy iterator over the occurrences of the schedule from the given time until before the given
+    /// end, earliest first.
+    ///
+    /// The occurrences are those of the schedule's frequency from its anchor, which is the first occurrence,
+    /// regardless of the schedule's current deadline.
+    pub fn occurrences(&self, from: Timestamp, to: Timestamp) -> Occurrences {
+        Occurrences {
+            first: self.first_occurrence_from(from),
+            last: None,
+            end: Some(to),
+        }
+    }
+
+    /// Same as [`StSchedule::occurrences`] but the occurrences are the ones after the given time, which go on
+    /// for as long as the schedule's frequency does
+    pub fn occurrences_after(&self, after: Timestamp) -> Occurrences {
+        Occurrences {
+            first: self.first_occurrence_from(after + Timestamp::Millis(1)),
+            last: None,
+            end: None,
+        }
+    }
+
+    /// Returns the latest occurrence of the schedule before the given time, if any.
+    ///
+    /// The occurrences are searched backwards from the given time in windows that double in length, so the
+    /// ones far from it are not evaluated unless there is none closer.
+    pub fn previous_occurrence(&self, before: Timestamp) -> Option<StSchedule> {
+        let anchor = self.timing.anchor;
+        let mut window = Timestamp::Millis(DAY_MILLIS as i64);
+
+        if before <= anchor {
+            return None;
+        }
+
+        loop {
+            let from = (before - window).max(anchor);
+
+            if let Some(occurrence) = self.occurrences(from, before).last() {
+                return Some(occurrence);
+            }
+            if from == anchor {
+                return None;
+            }
+
+            window += window;
+        }
+    }
+
+    /// Checks if the schedule occurs at exactly the given time
+    pub fn is_occurrence(&self, ts: Timestamp) -> bool {
+        self.occurrences(ts, ts + Timestamp::Millis(1))
+            .next()
+            .is_some()
+    }
+
+    /// Returns the first occurrence of the schedule, which is at its anchor unless it is excluded or rolled to a
+    /// business day, if any
+    pub fn first_occurrence(&self) -> Option<StSchedule> {
+        let anchor = self.at_deadline(self.timing.anchor);
+        let deadline = match &self.business_days {
+            Some(business_days) => timing::roll_to_business_day(
+                self.timing.anchor,
+                self.timing.timezone,
+                business_days,
+            ),
+            None => Some(self.timing.anchor),
+        };
+
+        match deadline {
+            Some(deadline) if !self.dates.is_excluded(deadline) => Some(StSchedule {
+                timing: Timing {
+                    deadline,
+                    rolled_from: (deadline != self.timing.anchor).then_some(self.timing.anchor),
+                    ..anchor.timing
+                },
+                ..anchor
+            }),
+            _ => anchor.get_next_occurrence().ok(),
+        }
+    }
+
+    /// Returns the first occurrence of the schedule at or after the given time, if any
+    fn first_occurrence_from(&self, from: Timestamp) -> Option<StSchedule> {
+        // An occurrence of the frequency before the given time can be rolled to a business day after it
+        let lead = match self.business_days {
+            Some(_) => roll_window(),
+            None => Timestamp::Millis(0),
+        };
+
+        if self.timing.anchor >= from - lead {
+            let mut occurrences = Occurrences {
+                first: self.first_occurrence(),
+                last: None,
+                end: None,
+            };
+            return occurrences.find(|occurrence| occurrence.timing.deadline >= from);
+        }
+
+        // The occurrences of a frequency with a count are few, and are walked from the anchor so they are counted
+        if self.get_count().is_some() {
+            let mut occurrences = self.occurrences_after(self.timing.anchor - Timestamp::Millis(1));
+            return occurrences.find(|occurrence| occurrence.timing.deadline >= from);
+        }
+
+        // The next occurrence is evaluated as the first one after the clock, from the anchor for regular
+        // frequencies, so any deadline before the clock will do
+        let clock = FixedClock::new(from - Timestamp::Millis(1));
+        self.at_deadline(from - lead - Timestamp::Millis(2))
+            .get_upcoming_schedule_with(&clock)
+            .ok()
+    }
+
+    /// Checks if the schedule's current deadline is its final occurrence, either because it does not repeat
+    /// or because its next occurrence falls beyond the frequency's `until` or `count`, with no time included
+    /// after it either
+    pub fn is_final_occurrence(&self) -> bool {
+        matches!(
+            self.get_next_occurrence(),
+            Err(TimingError::FrequencyExpired
+                | TimingError::CountExhausted
+                | TimingError::MissingExpression)
+        )
+    }
+}
+
+/// How far back from a time the occurrences of a frequency can be that are rolled to a business day after it, which
+/// is a day more than the most days a roll goes, for the change of offset on the way
+fn roll_window() -> Timestamp {
+    Timestamp::from_days((ROLL_LIMIT_DAYS + 1) as f64)
+}
+
+impl OccurrenceDates {
+    /// Returns the times excluded, earliest first
+    pub fn get_excluded(&self) -> &[Timestamp] {
+        &self.excluded
+    }
+
+    /// Returns the times included, earliest first
+    pub fn get_included(&self) -> &[Timestamp] {
+        &self.included
+    }
+
+    pub fn is_excluded(&self, ts: Timestamp) -> bool {
+        self.excluded.binary_search(&ts).is_ok()
+    }
+
+    /// Returns the first time included after the given one that is not excluded, if any
+    fn next_included(&self, after: Timestamp) -> Option<Timestamp> {
+        let start = self.included.partition_point(|ts| *ts <= after);
+        self.included[start..]
+            .iter()
+            .copied()
+            .find(|ts| !self.is_excluded(*ts))
+    }
+}
+
+/// Inserts the time into the sorted times unless it is already there, returning whether it was inserted
+fn insert_sorted(times: &mut Vec<Timestamp>, ts: Timestamp) -> bool {
+    match times.binary_search(&ts) {
+        Ok(_) => false,
+        Err(position) => {
+            times.insert(position, ts);
+            true
+        }
+    }
+}
+
+/// Removes the time from the sorted times, returning whether it was there
+fn remove_sorted(times: &mut Vec<Timestamp>, ts: Timestamp) -> bool {
+    match times.binary_search(&ts) {
+        Ok(position) => {
+            times.remove(position);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// A lazy iterator over the occurrences of a schedule, earliest first, see [`StSchedule::occurrences`]
+#[derive(Debug, Clone)]
+pub struct Occurrences {
+    /// The first occurrence, until it is yielded
+    first: Option<StSchedule>,
+    /// The occurrence yielded last, the next one being evaluated from it
+    last: Option<StSchedule>,
+    /// The time the occurrences end before, if ever
+    end: Option<Timestamp>,
+}
+
+impl Iterator for Occurrences {
+    type Item = StSchedule;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let occurrence = match (self.first.take(), self.last.take()) {
+            (Some(first), _) => first,
+            (None, Some(last)) => last
+                .get_next_occurrence()
+                .ok()
+                // Guards against a frequency that fails to move forward, which would otherwise never end
+                .filter(|next| next.timing.deadline > last.timing.deadline)?,
+            (None, None) => return None,
+        };
+
+        if self
+            .end
+            .is_some_and(|end| occurrence.timing.deadline >= end)
+        {
+            return None;
+        }
+
+        self.last = Some(occurrence.clone());
+        Some(occurrence)
+    }
+}
+
+impl fmt::Display for StSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", &self)
+    }
+}
+
+impl Ord for StSchedule {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        let order = self.timing.deadline.cmp(&other.timing.deadline);
+
+        match order {
+            Ordering::Equal => self.priority.cmp(&other.priority),
+            _ => order,
+        }
+    }
+}
+
+impl PartialOrd for StSchedule {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+mod timing {
+    use core::convert::TryInto;
+
+    use chrono::offset::LocalResult;
+    use chrono::prelude::*;
+    use chrono_tz::Tz;
+
+    use crate::core::calendar::BusinessDays;
+    use crate::core::clock::{Clock, SystemClock};
+    use crate::core::errors::TimingError;
+    use crate::core::frequency::{StConstWeekday, StMonth, StOrdinals, StVarWeekday, StWeekday};
+    use crate::core::time::{Timestamp, Ts};
+    use crate::core::time::{DAY_MILLIS, HOUR_MILLIS, INDEXED_MONTH_DAYS, WEEK_MILLIS};
+    use crate::utils::{filter_unique, map_unique};
+
+    const DOW: u32 = 7;
+    const MOY: u32 = 12;
+
+    #[derive(Debug, Clone, Copy)]
+    pub struct TimingOptions {
+        pub curtime: Timestamp,
+    }
+
+    impl TimingOptions {
+        pub fn with_clock(clock: &dyn Clock) -> Self {
+            TimingOptions {
+                curtime: clock.now(),
+            }
+        }
+    }
+
+    impl Default for TimingOptions {
+        fn default() -> Self {
+            Self::with_clock(&SystemClock)
+        }
+    }
+
+    // since we are asuming to be in a safe `float64` range, `2 ^ 53 - 1`, then it's guaranted that we
+    // can also safely convert down from a `uint64` to an `int64`.
+
+    // The largest supported value that `every` can be is `2_501_999_792` any value higher than this
+    // will go beyond the floating point range of `2 ^ 53 - 1` and cause an overflow which leads to
+    // a panic in the execution of the following code.
+    // We may set up a validation that ensures supplied values are in the constrained ranges.
+    pub fn get_next_hourly_timestamp(
+        anchor_ts: Timestamp,
+        every: u32,
+        options: &TimingOptions,
+    ) -> Timestamp {
+        let current_ts = options.curtime;
+        let hours_ms = HOUR_MILLIS * every as u64;
+        let hours_ms_f64: f64 = num::cast(hours_ms).unwrap();
+
+        if anchor_ts >= current_ts {
+            return anchor_ts + Timestamp::Millis(hours_ms.try_into().unwrap());
+        }
+
+        let elapsed_ts = current_ts - anchor_ts;
+        let elapsed_factor = elapsed_ts.as_ms_f64() / hours_ms_f64;
+        let next_hour_ms = hours_ms_f64 * elapsed_factor.ceil();
+
+        anchor_ts + Timestamp::Millis(num::cast(next_hour_ms).unwrap())
+    }
+
+    // The largest supported value that `every` can be is `104_249_991` any value higher than this
+    // will go beyond the floating point range of `2 ^ 53 - 1` and cause an overflow which leads to
     // a panic in the execution of the following code.
     // We may set up a validation that ensures supplied values are in the constrained ranges.
     pub fn get_next_daily_timestamp(
@@ -726,7 +1565,7 @@ mod timing {
         let elapsed_factor = elapsed_ts.as_ms_f64() / days_ms_f64;
         let next_day_ms = days_ms_f64 * elapsed_factor.ceil();
 
-        return anchor_ts + Timestamp::Millis(num::cast(next_day_ms).unwrap());
+        anchor_ts + Timestamp::Millis(num::cast(next_day_ms).unwrap())
     }
 
     // The largest supported value that `every` can be is `14_892_855` any value higher than this
@@ -735,6 +1574,7 @@ mod timing {
     // We may set up a validation that ensures supplied values are in the constrained ranges.
     pub fn get_next_weekly_timestamp(
         anchor_ts: Timestamp,
+        timezone: Tz,
         every: u32,
         weekdays: Option<&Vec<StConstWeekday>>,
         options: &TimingOptions,
@@ -744,10 +1584,15 @@ mod timing {
             if weekdays.is_empty() {
                 vec![bring_wk_anchor_forward(current_ts, anchor_ts, every)]
             } else {
-                map_unique(&weekdays, |w| u32::from(*w) % DOW)
+                map_unique(weekdays, |w| u32::from(*w) % DOW)
                     .into_iter()
                     .map(|w| {
-                        bring_wk_anchor_forward(current_ts, set_day_of_week(&anchor_ts, &w), every)
+                        let day = set_day_of_week(&anchor_ts, timezone, &w);
+                        // A weekday later in the anchor's week than the current time is its own next occurrence
+                        match day > anchor_ts && day > current_ts {
+                            true => day,
+                            false => bring_wk_anchor_forward(current_ts, day, every),
+                        }
                     })
                     .collect::<Vec<_>>()
             }
@@ -772,431 +1617,188 @@ mod timing {
         let elapsed_factor = elapsed_ts.as_ms_f64() / weeks_ms_f64;
         let next_week_ms = weeks_ms_f64 * elapsed_factor.ceil();
 
-        return anchor_ts + Timestamp::Millis(num::cast(next_week_ms).unwrap());
+        anchor_ts + Timestamp::Millis(num::cast(next_week_ms).unwrap())
     }
 
-    pub fn get_next_monthly_days_timestamp(
-        anchor_ts: Timestamp,
-        every: u32,
-        days: &Vec<u32>,
-        options: &TimingOptions,
-    ) -> Vec<Timestamp> {
-        let current_ts = options.curtime;
-        let forwarded_ts = bring_mo_anchor_forward(current_ts, anchor_ts, every);
-        let forwarded_dt = forwarded_ts.to_datetime();
-        let (month, year) = (forwarded_dt.month0(), forwarded_dt.year());
-        let max_day_of_month = get_days_in_year_month(year, month);
-
-        // if days is not supplied or is empty, then assume the same day of month as anchor
-        if days.is_empty() {
-            // its quite likely the anchor dom does not exist for forwarded month
-            return forwarded_dt
-                .with_day(anchor_ts.to_datetime().day())
-                .map_or_else(|| vec![], |dt| vec![dt.to_timestamp()]);
-        }
+    /// How many periods of a monthly or yearly frequency are searched for an occurrence before giving up,
+    /// e.g. for the fifth Monday of every other month, which only some months have
+    const MAX_PERIODS: i64 = 100;
 
-        filter_unique(days, |d| d > &0 && d <= &max_day_of_month)
-            .into_iter()
-            .map(|d| {
-                let dom_offset = forwarded_dt.day() - d;
-                forwarded_ts - Timestamp::from_days(dom_offset as f64)
-            })
-            .collect::<Vec<Timestamp>>()
+    /// The days of the month a monthly or yearly frequency occurs on
+    #[derive(Debug, Clone, Copy)]
+    pub enum MonthDays<'a> {
+        /// The given days of the month, or the anchor's day of the month when there are none.
+        /// Days a month does not have are skipped for that month.
+        Days(&'a [u32]),
+        /// The day the given ordinal weekday falls on in the month, which some months may not have, e.g. the
+        /// fifth Monday
+        Ordinal(StOrdinals, StWeekday),
     }
 
-    pub fn get_next_monthly_ordinal_timestamp(
+    /// Returns the first occurrence after the current time of a frequency that repeats every given number of
+    /// months from the anchor's, on the given days of the month, at the anchor's time of the day.
+    ///
+    /// The months and days are those of the calendar in the given timezone, which is the anchor's.
+    pub fn get_next_monthly_timestamp(
         anchor_ts: Timestamp,
+        timezone: Tz,
         every: u32,
-        ordinals: (StOrdinals, StConstWeekday),
+        days: &MonthDays,
         options: &TimingOptions,
     ) -> Result<Timestamp, TimingError> {
-        let current_ts = options.curtime;
-        let forwarded_dt = bring_mo_anchor_forward(current_ts, anchor_ts, every).to_datetime();
-        let (month, year) = (forwarded_dt.month0(), forwarded_dt.year());
-        let last_day_of_month = get_days_in_year_month(year, month);
-
-        let (ordinal, weekday) = ordinals;
-
-        match ordinal {
-            StOrdinals::Last => {
-                let forwarded_dt = forwarded_dt.with_day(last_day_of_month).unwrap();
-                let dow_offset_rev = get_rev_dow_offset(
-                    forwarded_dt.weekday().num_days_from_sunday(),
-                    weekday.into(),
-                );
-                let result =
-                    forwarded_dt.to_timestamp() - Timestamp::from_days(dow_offset_rev as f64);
-                Ok(result)
+        let anchor = to_local(anchor_ts, timezone);
+        let current = to_local(options.curtime, timezone);
+        let every = every.max(1) as i64;
+        let month_index = |dt: &NaiveDateTime| dt.year() as i64 * MOY as i64 + dt.month0() as i64;
+        // The period the current time falls in may still have occurrences after it
+        let first_period = (month_index(&current) - month_index(&anchor)).max(0) / every;
+        let after = options.curtime.max(anchor_ts);
+
+        for period in first_period..first_period + MAX_PERIODS {
+            let index = month_index(&anchor) + period * every;
+            let (year, month0) = (index.div_euclid(12) as i32, index.rem_euclid(12) as u32);
+
+            if let Some(ts) = first_in_month(timezone, &anchor, (year, month0), days, after) {
+                return Ok(ts);
             }
-            _ => {
-                let days_offset = DOW * ordinal.to_value();
-                let mut retries = 100;
-                let mut last_dom = last_day_of_month;
-                let mut fwd = forwarded_dt.with_day(1).unwrap();
-                let mut dow_offset =
-                    get_dow_offset(fwd.weekday().num_days_from_sunday(), weekday.into());
-                let mut day_of_month = fwd.day() + days_offset + dow_offset;
-
-                while day_of_month > last_dom && retries > 0 {
-                    let anchor_ts = fwd.with_day(last_dom).unwrap().to_timestamp();
-                    fwd = bring_mo_anchor_forward(current_ts, anchor_ts, every)
-                        .to_datetime()
-                        .with_day(1)
-                        .unwrap();
-                    last_dom = get_days_in_year_month(fwd.year(), fwd.month0());
-                    dow_offset =
-                        get_dow_offset(fwd.weekday().num_days_from_sunday(), weekday.into());
-                    day_of_month = fwd.day() + days_offset + dow_offset;
-                    retries -= 1;
-                }
-
-                if day_of_month > last_dom {
-                    return Err(TimingError::NonDeterministic);
-                }
-
-                let result =
-                    fwd.to_timestamp() + Timestamp::from_days((days_offset + dow_offset) as f64);
-
-                Ok(result)
-            }
-        }
-    }
-
-    fn bring_mo_anchor_forward(
-        current_ts: Timestamp,
-        anchor_ts: Timestamp,
-        every: u32,
-    ) -> Timestamp {
-        let current_dt = current_ts.to_datetime();
-        let anchor_dt = anchor_ts.to_datetime();
-        let (current_month, current_year) = (current_dt.month0(), current_dt.year());
-        let (anchor_month, anchor_year) = (anchor_dt.month0(), anchor_dt.year());
-        let months_remaining = get_months_remaining(
-            every,
-            (anchor_year, anchor_month),
-            (current_year, current_month),
-        );
-
-        if months_remaining.is_none() || anchor_dt > current_dt {
-            let t = anchor_month + every;
-            // This is normally a floored division but since we are working with integers not floats, flooring is implicitly guaranteed
-            let y = anchor_year + (t / MOY) as i32;
-            let m = t % MOY;
-            let d = get_days_in_year_month(y, m);
-            let ndt = NaiveDate::from_ymd_opt(y, m, d)
-                .unwrap()
-                .and_time(anchor_dt.time());
-
-            // TODO: validate correctness ✅
-            return DateTime::<Utc>::from_naive_utc_and_offset(ndt, anchor_dt.offset().clone())
-                .to_timestamp();
         }
 
-        debug_assert!(
-            anchor_ts <= current_ts,
-            "anchor timestamp is expected to be less than or equal to current timestamp at this point"
-        );
-
-        let months_remaining = months_remaining.unwrap();
-        let rem_days_in_current_month =
-            get_days_in_year_month(current_year, current_month) - current_dt.day();
-
-        let days_to_rem_months = {
-            let years_to_rem_months = months_remaining / MOY;
-            let rem_months = months_remaining % MOY;
-            let a = if anchor_month >= 1 { 1 } else { 0 };
-
-            let leap_years_between = get_leap_years_between(
-                current_year + 1,
-                current_year + a + num::cast::<u32, i32>(years_to_rem_months).unwrap(),
-                Some(true),
-            );
-
-            let num_of_leap_years = leap_years_between.len();
-            let days_in_year_to_rem_months = DOY * years_to_rem_months + num_of_leap_years as u32;
-            let mut days_in_rem_months = 0;
-
-            {
-                let start_month = (current_month + 1) % MOY;
-                let end_month = start_month + rem_months;
-                let mut n = if start_month == 0 { 1 } else { 0 };
-                for m in start_month..end_month {
-                    if m >= MOY && m % MOY == 0 {
-                        n += 1;
-                    };
-                    days_in_rem_months += get_days_in_year_month(current_year + n, m % MOY);
-                }
-            }
-
-            days_in_year_to_rem_months + days_in_rem_months
-        };
-
-        let days_to_next_schedule = days_to_rem_months + rem_days_in_current_month;
-        let days_to_next_schedule_ms = DAY_MILLIS * days_to_next_schedule as u64;
-
-        Timestamp::Millis(num::cast(days_to_next_schedule_ms).unwrap())
+        Err(TimingError::NonDeterministic)
     }
 
+    /// Returns the first occurrence after the current time of a frequency that repeats every given number of
+    /// years from the anchor's, in the given months, on the given days of the month, at the anchor's time of
+    /// the day.
+    ///
+    /// The years, months and days are those of the calendar in the given timezone, which is the anchor's.
     pub fn get_next_yearly_timestamp(
         anchor_ts: Timestamp,
+        timezone: Tz,
         every: u32,
-        months: &Vec<StMonth>,
-        ordinals: Option<(StOrdinals, StWeekday)>,
+        months: &[StMonth],
+        days: &MonthDays,
         options: &TimingOptions,
-    ) -> Vec<Result<Timestamp, TimingError>> {
-        let current_ts = options.curtime;
-        let forwarded_ts = bring_yr_anchor_forward(current_ts, anchor_ts, every);
-        let forwarded_dt = forwarded_ts.to_datetime();
-        let forwarded_year = forwarded_dt.year();
-        let schedules: Vec<DateTime<Utc>> = map_unique(months, |m| u32::from(*m))
-            .into_iter()
-            .map(|m| {
-                let ndt = NaiveDate::from_ymd_opt(
-                    forwarded_year,
-                    m.into(),
-                    get_days_in_year_month(forwarded_year, m.into()),
-                )
-                .unwrap()
-                .and_time(forwarded_dt.time());
-
-                DateTime::<Utc>::from_naive_utc_and_offset(ndt, forwarded_dt.offset().clone())
-            })
-            .collect();
-
-        if ordinals.is_none() {
-            return schedules.iter().map(|v| Ok(v.to_timestamp())).collect();
-        };
-
-        let ordinals = ordinals.unwrap();
-        let mut result: Vec<Result<Timestamp, TimingError>> = Vec::with_capacity(schedules.len());
-
-        for s in schedules {
-            let (month, year) = (s.month0(), s.year());
-            let last_dom = get_days_in_year_month(year, month);
-
-            match ordinals.0 {
-                StOrdinals::First
-                | StOrdinals::Second
-                | StOrdinals::Third
-                | StOrdinals::Fourth
-                | StOrdinals::Fifth => {
-                    let s = s.with_day(1).unwrap();
-                    let ref_weekday = s.weekday().num_days_from_sunday();
-                    let ordinal: u32 = ordinals.0.into();
-                    match ordinals.1 {
-                        StWeekday::Const(weekday) => {
-                            let weekday: u32 = weekday.into();
-                            let dow_offset = get_dow_offset(ref_weekday, weekday.into());
-                            let days_offset = DOW * ordinal;
-                            let dom = s.day() + days_offset + dow_offset;
-                            if dom > last_dom {
-                                result.push(Err(TimingError::NonDeterministic))
-                            } else {
-                                result.push(Ok(s.to_timestamp()
-                                    + Timestamp::from_days((days_offset + dow_offset) as f64)))
-                            }
-                        }
-
-                        StWeekday::Var(weekday) => match weekday {
-                            StVarWeekday::Day => {
-                                result.push(Ok(
-                                    s.to_timestamp() + Timestamp::from_days(ordinal as f64)
-                                ));
-                            }
-                            StVarWeekday::Weekday => {
-                                let weekday = {
-                                    let weekdays = &[1, 2, 3, 4, 5];
-                                    if let Ok(index) = weekdays.binary_search(&ref_weekday) {
-                                        let index_of_weekday =
-                                            (index + ordinal as usize) % weekdays.len();
-                                        weekdays.get(index_of_weekday).unwrap()
-                                    } else {
-                                        weekdays.get(ordinal as usize).unwrap()
-                                    }
-                                };
-                                let dow_offset = get_dow_offset(ref_weekday, *weekday);
-                                result.push(Ok(
-                                    s.to_timestamp() + Timestamp::from_days(dow_offset as f64)
-                                ));
-                            }
+    ) -> Result<Timestamp, TimingError> {
+        let anchor = to_local(anchor_ts, timezone);
+        let current = to_local(options.curtime, timezone);
+        let every = every.max(1) as i64;
+        let first_period = (current.year() as i64 - anchor.year() as i64).max(0) / every;
+        let after = options.curtime.max(anchor_ts);
+
+        let mut months = months.iter().map(|m| u32::from(*m)).collect::<Vec<_>>();
+        months.sort_unstable();
+        months.dedup();
+
+        for period in first_period..first_period + MAX_PERIODS {
+            let year = (anchor.year() as i64 + period * every) as i32;
+            let next = months
+                .iter()
+                .find_map(|month0| first_in_month(timezone, &anchor, (year, *month0), days, after));
 
-                            StVarWeekday::Weekend => {
-                                let sun =
-                                    (get_dow_offset(ref_weekday, StConstWeekday::Sun.into()), {
-                                        let a = (ordinal as f32 / 2.0).ceil() as u32;
-                                        let b = ordinal % 2;
-                                        let c = DOW * a - b;
-                                        c
-                                    });
-                                let sat =
-                                    (get_dow_offset(ref_weekday, StConstWeekday::Sat.into()), {
-                                        let a = (ordinal as f32 / 2.0).floor() as u32;
-                                        let b = ordinal % 2;
-                                        let c = DOW * a + b;
-                                        c
-                                    });
-                                let closest = (sat.0 < sun.0).then_some(sat).unwrap_or(sun);
-                                let days_offset = closest.0 + closest.1;
-                                let dom = s.day() + days_offset;
-                                if dom > last_dom {
-                                    result.push(Err(TimingError::NonDeterministic))
-                                } else {
-                                    result
-                                        .push(Ok(s.to_timestamp()
-                                            + Timestamp::from_days(days_offset as f64)))
-                                }
-                            }
-                        },
-                    }
-                }
-                StOrdinals::Last => {
-                    let s = s.with_day(last_dom).unwrap();
-                    let ref_weekday = s.weekday().num_days_from_sunday();
-                    match ordinals.1 {
-                        StWeekday::Const(weekday) => {
-                            let dow_offset_rev = get_rev_dow_offset(ref_weekday, weekday.into());
-                            result.push(Ok(
-                                s.to_timestamp() - Timestamp::from_days(dow_offset_rev as f64)
-                            ));
-                        }
-                        StWeekday::Var(weekday) => match weekday {
-                            StVarWeekday::Day => result.push(Ok(s.to_timestamp())),
-                            StVarWeekday::Weekday => {
-                                if ref_weekday > StConstWeekday::Sun.into()
-                                    && ref_weekday < StConstWeekday::Sat.into()
-                                {
-                                    result.push(Ok(s.to_timestamp()));
-                                } else {
-                                    let days_offset = 2 - ((DOW - ref_weekday) % DOW);
-                                    result
-                                        .push(Ok(s.to_timestamp()
-                                            - Timestamp::from_days(days_offset as f64)));
-                                }
-                            }
-                            StVarWeekday::Weekend => {
-                                let sun_offset_rev = get_rev_dow_offset(ref_weekday, 0);
-                                let sat_offset_rev = get_rev_dow_offset(ref_weekday, 6);
-                                result.push(Ok(s.to_timestamp()
-                                    - Timestamp::from_days(
-                                        sun_offset_rev.min(sat_offset_rev) as f64
-                                    )));
-                            }
-                        },
-                    }
-                }
+            if let Some(ts) = next {
+                return Ok(ts);
             }
         }
 
-        result
+        Err(TimingError::NonDeterministic)
     }
 
-    pub fn bring_yr_anchor_forward(
-        current_ts: Timestamp,
-        anchor_ts: Timestamp,
-        every: u32,
-    ) -> Timestamp {
-        let current_dt = current_ts.to_datetime();
-        let anchor_dt = anchor_ts.to_datetime();
-        let (current_month, current_year) = (current_dt.month0(), current_dt.year());
-        let (anchor_month, anchor_year) = (anchor_dt.month0(), anchor_dt.year());
-        let years_remaining = get_years_remaining(every, anchor_dt.year(), current_year);
-
-        if years_remaining.is_none() || anchor_dt > current_dt {
-            let y = anchor_year + every as i32;
-            let m = anchor_month;
-            let d = get_days_in_year_month(y, m);
-            let ndt = NaiveDate::from_ymd_opt(y, m, d)
-                .unwrap()
-                .and_time(anchor_dt.time());
-            return DateTime::<Utc>::from_naive_utc_and_offset(ndt, anchor_dt.offset().clone())
-                .to_timestamp();
-        };
-
-        debug_assert!(
-            anchor_ts <= current_ts,
-            "anchor timestamp is expected to be less than or equal to current timestamp at this point"
-        );
+    /// Returns the first of the given days of the given month, at the anchor's time of the day, that comes after
+    /// the given time
+    fn first_in_month(
+        timezone: Tz,
+        anchor: &NaiveDateTime,
+        (year, month0): (i32, u32),
+        days: &MonthDays,
+        after: Timestamp,
+    ) -> Option<Timestamp> {
+        get_days_of_month(year, month0, anchor.day(), days)
+            .into_iter()
+            .filter_map(|day| NaiveDate::from_ymd_opt(year, month0 + 1, day))
+            .filter_map(|date| from_local(&date.and_time(anchor.time()), timezone))
+            .find(|ts| *ts > after)
+    }
 
-        let years_remaining = years_remaining.unwrap();
-        let days_to_rem_years = if years_remaining == 0 {
-            0
-        } else {
-            let leap_years_between = get_leap_years_between(
-                current_year + 1,
-                current_year + num::cast::<u32, i32>(years_remaining).unwrap(),
-                Some(true),
-            );
-            let num_of_leap_years = leap_years_between.len();
-            DOY * years_remaining + num_of_leap_years as u32
-        };
+    /// Returns the days of the given month the given days select, in ascending order
+    fn get_days_of_month(year: i32, month0: u32, anchor_day: u32, days: &MonthDays) -> Vec<u32> {
+        let last_day = get_days_in_year_month(year, month0);
 
-        let rem_days_in_current_year = {
-            let rem_days_in_current_month =
-                get_days_in_year_month(current_year, current_month) - current_dt.day();
-            let mut days_in_rem_months = 0;
-            let mut n = 1;
-            let end = MOY - (current_month + n);
-            while n < end {
-                days_in_rem_months += get_days_in_year_month(current_year, current_month + n);
-                n += 1;
+        let (ordinal, weekday) = match days {
+            MonthDays::Days([]) => {
+                return (anchor_day <= last_day)
+                    .then_some(anchor_day)
+                    .into_iter()
+                    .collect()
             }
-            days_in_rem_months + rem_days_in_current_month
-        };
-
-        let days_to_next = days_to_rem_years + rem_days_in_current_year;
-        let forwarded = align(current_ts, anchor_ts) + Timestamp::from_days(days_to_next as f64);
-
-        forwarded
-    }
-
-    fn get_years_remaining(every: u32, start_year: i32, end_year: i32) -> Option<u32> {
-        if end_year < start_year {
-            return None;
+            MonthDays::Days(days) => {
+                let mut days = filter_unique(days, |d| d > &0 && d <= &last_day);
+                days.sort_unstable();
+                return days;
+            }
+            MonthDays::Ordinal(ordinal, weekday) => (ordinal, weekday),
         };
 
-        let years_elapsed = (end_year - start_year) as u32;
-        let result = (every - (years_elapsed % every)) % every;
-
-        Some(result)
-    }
+        let mut matching = (1..=last_day).filter(|day| {
+            let dow = NaiveDate::from_ymd_opt(year, month0 + 1, *day)
+                .unwrap()
+                .weekday()
+                .num_days_from_sunday();
+
+            match weekday {
+                StWeekday::Const(weekday) => dow == u32::from(*weekday),
+                StWeekday::Var(StVarWeekday::Day) => true,
+                StWeekday::Var(StVarWeekday::Weekday) => dow != 0 && dow != 6,
+                StWeekday::Var(StVarWeekday::Weekend) => dow == 0 || dow == 6,
+            }
+        });
 
-    fn get_months_remaining(every: u32, start: (i32, u32), end: (i32, u32)) -> Option<u32> {
-        let max_month = 12;
-        // end year lt start year or (end year = start year and end month lt start month)
-        if end.0 < start.0 || (end.0 == start.0 && end.1 < start.1) {
-            return None;
+        let day = match ordinal {
+            StOrdinals::Last => matching.next_back(),
+            ordinal => matching.nth(u32::from(*ordinal) as usize),
         };
 
-        let month_diff: i32 =
-            num::cast::<u32, i32>(end.1).unwrap() - num::cast::<u32, i32>(start.1).unwrap();
-        let year_diff = end.0 - start.0;
-        let months_elapsed = (month_diff + max_month * year_diff) as u32;
-        let result = (every - (months_elapsed % every)) % every;
-
-        Some(result)
+        day.into_iter().collect()
     }
 
-    fn set_day_of_week(ts: &Timestamp, weekday: &u32) -> Timestamp {
-        let cf = ts.to_datetime().weekday().num_days_from_sunday() as i64 - (weekday % DOW) as i64;
-        *ts - Timestamp::from_days(cf as f64)
+    /// Returns the given timestamp moved to the same time of the day on the business day its date in the given
+    /// timezone is rolled to, if it is not skipped
+    pub fn roll_to_business_day(
+        ts: Timestamp,
+        timezone: Tz,
+        business_days: &BusinessDays,
+    ) -> Option<Timestamp> {
+        let local = to_local(ts, timezone);
+        let date = business_days.roll_date(local.date())?;
+
+        match date == local.date() {
+            true => Some(ts),
+            false => from_local(&date.and_time(local.time()), timezone),
+        }
     }
 
-    #[inline(always)]
-    fn get_dow_offset(ref_weekday: u32, target_weekday: u32) -> u32 {
-        (DOW - ref_weekday) + (target_weekday % DOW) % DOW
+    /// Returns the wall time of the given timestamp in the given timezone
+    fn to_local(ts: Timestamp, timezone: Tz) -> NaiveDateTime {
+        ts.to_datetime().with_timezone(&timezone).naive_local()
     }
 
-    #[inline(always)]
-    fn get_rev_dow_offset(ref_weekday: u32, target_weekday: u32) -> u32 {
-        (DOW - get_dow_offset(ref_weekday, target_weekday)) % DOW
+    /// Returns the timestamp of the given wall time in the given timezone, the later of the two when the wall time
+    /// occurs twice, and an hour later when it is skipped over by a change of offset
+    fn from_local(ndt: &NaiveDateTime, timezone: Tz) -> Option<Timestamp> {
+        match timezone.from_local_datetime(ndt) {
+            LocalResult::Single(dt) | LocalResult::Ambiguous(_, dt) => Some(dt.to_timestamp()),
+            LocalResult::None => timezone
+                .from_local_datetime(&(*ndt + chrono::Duration::hours(1)))
+                .latest()
+                .map(|dt| dt.to_timestamp()),
+        }
     }
 
-    fn align(x2: Timestamp, x1: Timestamp) -> Timestamp {
-        let c = Timestamp::from_days(1.0);
-        let a = (x2 - x1) % c;
-        let b = a.round_div(c) * c - a;
-        let x3 = x2 + b;
-        x3
+    /// Moves the given time to the given day of its week, the days being those of the calendar in the given
+    /// timezone, as the `BYDAY` of a rule is read against the local start of its event
+    fn set_day_of_week(ts: &Timestamp, timezone: Tz, weekday: &u32) -> Timestamp {
+        let day = to_local(*ts, timezone).weekday().num_days_from_sunday();
+        let cf = day as i64 - (weekday % DOW) as i64;
+        *ts - Timestamp::from_days(cf as f64)
     }
 
     #[inline(always)]
@@ -1225,40 +1827,6 @@ mod timing {
             *days
         }
     }
-
-    fn get_next_leap_year(year: i32) -> i32 {
-        let mut next_leap_year = year + 1;
-
-        if next_leap_year % 4 != 0 {
-            next_leap_year += 4 - (next_leap_year % 4)
-        };
-        if next_leap_year % 100 == 0 && next_leap_year % 400 != 0 {
-            next_leap_year += 4
-        };
-
-        next_leap_year
-    }
-
-    fn get_leap_years_between(start_year: i32, end_year: i32, inclusive: Option<bool>) -> Vec<i32> {
-        let mut leap_years: Vec<i32> = vec![];
-
-        if start_year == end_year {
-            return leap_years;
-        };
-
-        let (start, end) = inclusive
-            .map(|f| f.then_some((start_year - 1, end_year)))
-            .flatten()
-            .unwrap_or((start_year, end_year - 1));
-        let mut year = start;
-
-        while year <= end {
-            year = get_next_leap_year(year);
-            leap_years.push(year);
-        }
-
-        leap_years
-    }
 }
 
 #[cfg(test)]
@@ -1266,6 +1834,10 @@ mod tests {
     use super::*;
     use wasm_bindgen_test::*;
 
+    use crate::core::frequency::{
+        StDailyExpression, StFrequencyType, StMonthlyExpression, StOrdinals, StVarWeekday,
+        StWeeklyExpression, StYearlyExpression,
+    };
     use crate::core::time::{HOUR_MILLIS, WEEK_MILLIS};
 
     wasm_bindgen_test_configure!(run_in_browser);
@@ -1306,6 +1878,7 @@ mod tests {
             let weeks = 1;
             let next_timestamp = Timing::next_weekly_timestamp(
                 TIMESTAMP,
+                Tz::Africa__Lagos,
                 weeks,
                 None,
                 &timing::TimingOptions::default(),
@@ -1321,6 +1894,7 @@ mod tests {
             let weeks = 3;
             let next_timestamp = Timing::next_weekly_timestamp(
                 TIMESTAMP,
+                Tz::Africa__Lagos,
                 weeks,
                 None,
                 &timing::TimingOptions::default(),
@@ -1332,4 +1906,721 @@ mod tests {
             )
         }
     }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_weekly_weekdays_on_local_calendar() {
+        let tz: Tz = "Africa/Lagos".parse().unwrap();
+        let clock = FixedClock::new(Timestamp::Millis(0));
+        let expr = StWeeklyExpression::with_weekdays(1, vec![1, 3]);
+        let freq = StRegularFrequency::with_weekly_expr(StFrequencyType::Week, expr, None);
+        // Monday at 00:30 in Africa/Lagos, which is still Sunday in UTC
+        let schedule = StSchedule::try_with_frequency(
+            "id",
+            "2025-01-13T00:30:00.000",
+            "Africa/Lagos",
+            Some(StFrequency::Regular(freq)),
+            None,
+            &clock,
+        )
+        .unwrap();
+
+        // The weekdays are those of the calendar in the schedule's timezone, as they are for `BYDAY`
+        let occurrences = schedule
+            .occurrences_after(Timestamp::Millis(0))
+            .take(5)
+            .map(|occurrence| {
+                let deadline = occurrence.timing.deadline.to_datetime().with_timezone(&tz);
+                deadline.format("%a %Y-%m-%d %H:%M").to_string()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            occurrences,
+            vec![
+                "Mon 2025-01-13 00:30",
+                "Wed 2025-01-15 00:30",
+                "Mon 2025-01-20 00:30",
+                "Wed 2025-01-22 00:30",
+                "Mon 2025-01-27 00:30",
+            ]
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_cron_until_is_exclusive() {
+        let timing =
+            Timing::with_naive_anchor_tz("2025-01-15T09:00:00.000", "Africa/Lagos").unwrap();
+        let clock = FixedClock::new(timing.anchor + Timestamp::Millis(1));
+        // The next occurrence, 2025-01-16T09:00:00.000 in Africa/Lagos
+        let next = 1737014400000;
+        let frequency = |until| {
+            StFrequency::Custom(StCustomFrequency::new(
+                vec![String::from("0 9 * * *")],
+                Some(until),
+            ))
+        };
+
+        assert!(matches!(
+            timing.refresh_with(&frequency(next), &clock),
+            Err(TimingError::FrequencyExpired)
+        ));
+        assert_eq!(
+            timing
+                .refresh_with(&frequency(next + 1), &clock)
+                .unwrap()
+                .deadline,
+            Timestamp::Millis(next as i64)
+        );
+    }
+
+    /// Steps a timing anchored at the given wall time in the given timezone through the next `count` occurrences
+    /// of the given frequency, as the wall times they fall at
+    fn next_local_times(
+        frequency: StFrequency,
+        naive_anchor: &str,
+        timezone: &str,
+        count: usize,
+    ) -> Vec<String> {
+        let mut timing = Timing::with_naive_anchor_tz(naive_anchor, timezone).unwrap();
+
+        (0..count)
+            .map(|_| {
+                let clock = FixedClock::new(timing.deadline + Timestamp::Millis(1));
+                timing = timing.refresh_with(&frequency, &clock).unwrap();
+
+                let deadline = timing
+                    .deadline
+                    .to_datetime()
+                    .with_timezone(&timing.timezone);
+                deadline.format("%Y-%m-%d %H:%M").to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_monthly_and_yearly_timestamps_on_local_calendar() {
+        // 21:00 in New York is already the next day in UTC, so the days must be those of the local calendar
+        let monthly = StFrequency::Regular(StRegularFrequency::with_monthly_expr(
+            StFrequencyType::Month,
+            StMonthlyExpression::with_ordinal_weekday(1, StOrdinals::Last, StConstWeekday::Fri),
+            None,
+        ));
+        assert_eq!(
+            next_local_times(monthly, "2025-01-15T21:00:00.000", "America/New_York", 3),
+            vec!["2025-01-31 21:00", "2025-02-28 21:00", "2025-03-28 21:00"]
+        );
+
+        // Months without the anchor's day of the month are skipped
+        let yearly = StFrequency::Regular(StRegularFrequency::with_yearly_expr(
+            StFrequencyType::Year,
+            StYearlyExpression::with_months(1, vec![2, 1, 0]),
+            None,
+        ));
+        assert_eq!(
+            next_local_times(yearly, "2025-01-31T21:00:00.000", "America/New_York", 3),
+            vec!["2025-03-31 21:00", "2026-01-31 21:00", "2026-03-31 21:00"]
+        );
+    }
+
+    /// Formats the first `count` occurrences of a schedule anchored at 2025-01-15 09:00 in Africa/Lagos with the
+    /// given frequency, as the local times they fall at
+    fn first_occurrences(frequency: StFrequency, count: usize) -> Vec<String> {
+        let clock = FixedClock::new(Timestamp::Millis(0));
+        let schedule = StSchedule::try_with_frequency(
+            "id",
+            "2025-01-15T09:00:00.000",
+            "Africa/Lagos",
+            Some(frequency),
+            None,
+            &clock,
+        )
+        .unwrap();
+        let tz: Tz = "Africa/Lagos".parse().unwrap();
+
+        schedule
+            .occurrences_after(Timestamp::Millis(0))
+            .take(count)
+            .map(|occurrence| {
+                let deadline = occurrence.timing.deadline.to_datetime().with_timezone(&tz);
+                deadline.format("%Y-%m-%d %H:%M").to_string()
+            })
+            .collect()
+    }
+
+    fn monthly(expr: StMonthlyExpression) -> StFrequency {
+        StFrequency::Regular(StRegularFrequency::with_monthly_expr(
+            StFrequencyType::Month,
+            expr,
+            None,
+        ))
+    }
+
+    fn yearly(expr: StYearlyExpression) -> StFrequency {
+        StFrequency::Regular(StRegularFrequency::with_yearly_expr(
+            StFrequencyType::Year,
+            expr,
+            None,
+        ))
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_monthly_occurrences() {
+        assert_eq!(
+            first_occurrences(
+                monthly(StMonthlyExpression::with_days(1, vec![31, 1, 15])),
+                7
+            ),
+            vec![
+                "2025-01-15 09:00",
+                "2025-01-31 09:00",
+                "2025-02-01 09:00",
+                "2025-02-15 09:00",
+                "2025-03-01 09:00",
+                "2025-03-15 09:00",
+                "2025-03-31 09:00",
+            ]
+        );
+        assert_eq!(
+            first_occurrences(
+                monthly(StMonthlyExpression::with_ordinal_weekday(
+                    2,
+                    StOrdinals::Last,
+                    StConstWeekday::Fri,
+                )),
+                4
+            ),
+            vec![
+                "2025-01-15 09:00",
+                "2025-01-31 09:00",
+                "2025-03-28 09:00",
+                "2025-05-30 09:00",
+            ]
+        );
+        assert_eq!(
+            first_occurrences(
+                monthly(StMonthlyExpression::with_ordinal_weekday(
+                    1,
+                    StOrdinals::Fifth,
+                    StConstWeekday::Mon,
+                )),
+                3
+            ),
+            vec!["2025-01-15 09:00", "2025-03-31 09:00", "2025-06-30 09:00"]
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_yearly_occurrences() {
+        assert_eq!(
+            first_occurrences(yearly(StYearlyExpression::with_months(1, vec![6, 2])), 4),
+            vec![
+                "2025-01-15 09:00",
+                "2025-03-15 09:00",
+                "2025-07-15 09:00",
+                "2026-03-15 09:00",
+            ]
+        );
+        assert_eq!(
+            first_occurrences(
+                yearly(StYearlyExpression::with_months_ordinal_const_weekday(
+                    2,
+                    vec![2],
+                    StOrdinals::First,
+                    StConstWeekday::Mon,
+                )),
+                3
+            ),
+            vec!["2025-01-15 09:00", "2025-03-03 09:00", "2027-03-01 09:00"]
+        );
+        assert_eq!(
+            first_occurrences(
+                yearly(StYearlyExpression::with_months_ordinal_var_weekday(
+                    1,
+                    vec![11],
+                    StOrdinals::Last,
+                    StVarWeekday::Weekday,
+                )),
+                3
+            ),
+            vec!["2025-01-15 09:00", "2025-12-31 09:00", "2026-12-31 09:00"]
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_occurrences_between() {
+        let until = Timestamp::Millis(1737360000000); // 2025-01-20T08:00:00.000Z
+        let frequency = StFrequency::Regular(StRegularFrequency::with_daily_expr(
+            StFrequencyType::Day,
+            StDailyExpression::new(2),
+            Some(until.as_ms() as u64),
+        ));
+        let clock = FixedClock::new(Timestamp::Millis(0));
+        let schedule = StSchedule::try_with_frequency(
+            "id",
+            "2025-01-15T09:00:00.000",
+            "Africa/Lagos",
+            Some(frequency),
+            None,
+            &clock,
+        )
+        .unwrap();
+        let anchor = schedule.get_anchor_millis();
+        let day = DAY_MILLIS as i64;
+
+        // The end is exclusive, and so is the frequency's until
+        assert_eq!(
+            schedule.get_occurrences(anchor, anchor + 2 * day),
+            vec![anchor]
+        );
+        assert_eq!(
+            schedule.get_occurrences(anchor + 1, anchor + 10 * day),
+            vec![anchor + 2 * day, anchor + 4 * day]
+        );
+        assert_eq!(
+            schedule.get_occurrences_after(anchor, 5),
+            vec![anchor + 2 * day, anchor + 4 * day]
+        );
+        assert!(schedule
+            .get_occurrences(anchor + 5 * day, anchor + 10 * day)
+            .is_empty());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_cron_occurrences() {
+        let frequency = StFrequency::Custom(StCustomFrequency::new(
+            vec![String::from("0 9 * * 1-5")],
+            None,
+        ));
+
+        assert_eq!(
+            first_occurrences(frequency, 4),
+            vec![
+                "2025-01-15 09:00",
+                "2025-01-16 09:00",
+                "2025-01-17 09:00",
+                "2025-01-20 09:00",
+            ]
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_previous_occurrence() {
+        let frequency = monthly(StMonthlyExpression::with_ordinal_weekday(
+            1,
+            StOrdinals::Fifth,
+            StConstWeekday::Mon,
+        ));
+        let clock = FixedClock::new(Timestamp::Millis(0));
+        let schedule = StSchedule::try_with_frequency(
+            "id",
+            "2025-01-15T09:00:00.000",
+            "Africa/Lagos",
+            Some(frequency),
+            None,
+            &clock,
+        )
+        .unwrap();
+        let anchor = schedule.timing.anchor;
+        let mar_31 = Timestamp::Millis(1743408000000); // 2025-03-31T08:00:00.000Z
+        let jun_30 = Timestamp::Millis(1751270400000); // 2025-06-30T08:00:00.000Z
+
+        assert_eq!(schedule.previous_occurrence(anchor), None);
+        assert_eq!(
+            schedule
+                .previous_occurrence(mar_31)
+                .map(|occurrence| occurrence.timing.deadline),
+            Some(anchor)
+        );
+        assert_eq!(
+            schedule
+                .previous_occurrence(jun_30 - Timestamp::Millis(1))
+                .map(|occurrence| occurrence.timing.deadline),
+            Some(mar_31)
+        );
+        // 2100-01-01T00:00:00.000Z, the fifth Monday before it falling on 2099-11-30
+        assert_eq!(
+            schedule.get_previous_occurrence(4102444800000),
+            Some(4099708800000)
+        );
+
+        assert!(schedule.is_occurrence(anchor));
+        assert!(schedule.is_occurrence(mar_31));
+        assert!(!schedule.is_occurrence(mar_31 + Timestamp::Millis(1)));
+        assert!(!schedule.is_occurrence(anchor - Timestamp::Millis(DAY_MILLIS as i64)));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_previous_cron_occurrence() {
+        let until = Timestamp::Millis(1737360000000); // 2025-01-20T08:00:00.000Z
+        let frequency = StFrequency::Custom(StCustomFrequency::new(
+            vec![String::from("0 9 * * 1-5")],
+            Some(until.as_ms() as u64),
+        ));
+        let clock = FixedClock::new(Timestamp::Millis(0));
+        let schedule = StSchedule::try_with_frequency(
+            "id",
+            "2025-01-15T09:00:00.000",
+            "Africa/Lagos",
+            Some(frequency),
+            None,
+            &clock,
+        )
+        .unwrap();
+        let jan_17 = 1737100800000; // 2025-01-17T08:00:00.000Z
+
+        assert_eq!(
+            schedule.get_previous_occurrence(until.as_ms()),
+            Some(jan_17)
+        );
+        assert_eq!(
+            schedule.get_previous_occurrence(until.as_ms() + 30 * DAY_MILLIS as i64),
+            Some(jan_17)
+        );
+        assert!(schedule.is_occurrence_millis(jan_17));
+        assert!(!schedule.is_occurrence_millis(until.as_ms()));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_count_termination() {
+        let mut daily = StRegularFrequency::with_daily_expr(
+            StFrequencyType::Day,
+            StDailyExpression::new(1),
+            None,
+        );
+        daily.set_count(Some(3));
+        let mut cron = StCustomFrequency::new(vec![String::from("0 9 * * 1-5")], None);
+        cron.set_count(Some(3));
+
+        assert_eq!(
+            first_occurrences(StFrequency::Regular(daily.clone()), 5),
+            vec!["2025-01-15 09:00", "2025-01-16 09:00", "2025-01-17 09:00"]
+        );
+        assert_eq!(
+            first_occurrences(StFrequency::Custom(cron), 5),
+            vec!["2025-01-15 09:00", "2025-01-16 09:00", "2025-01-17 09:00"]
+        );
+
+        let anchor = Timestamp::Millis(1736928000000); // 2025-01-15T08:00:00.000Z
+        let day = Timestamp::Millis(DAY_MILLIS as i64);
+        let schedule = StSchedule::try_with_frequency(
+            "id",
+            "2025-01-15T09:00:00.000",
+            "Africa/Lagos",
+            Some(StFrequency::Regular(daily.clone())),
+            None,
+            &FixedClock::new(Timestamp::Millis(0)),
+        )
+        .unwrap();
+
+        // The occurrences skipped while refreshing are counted too
+        let last = schedule
+            .get_upcoming_schedule_with(&FixedClock::new(anchor + day + Timestamp::Millis(1)))
+            .unwrap();
+        assert_eq!(last.timing.deadline, anchor + day + day);
+        assert_eq!(last.get_occurrence_index().unwrap(), 2);
+        assert!(last.is_final_occurrence());
+        assert!(matches!(
+            last.get_next_occurrence(),
+            Err(TimingError::CountExhausted)
+        ));
+        assert_eq!(
+            schedule
+                .occurrences(anchor + day, anchor + Timestamp::from_days(10.0))
+                .map(|occurrence| occurrence.timing.deadline)
+                .collect::<Vec<_>>(),
+            vec![anchor + day, anchor + day + day]
+        );
+        assert_eq!(
+            schedule.previous_occurrence(anchor + Timestamp::from_days(10.0)),
+            Some(last)
+        );
+
+        // A schedule created after its count is used up is already expired
+        assert!(matches!(
+            StSchedule::try_with_frequency(
+                "id",
+                "2025-01-15T09:00:00.000",
+                "Africa/Lagos",
+                Some(StFrequency::Regular(daily)),
+                None,
+                &FixedClock::new(anchor + Timestamp::from_days(3.0)),
+            ),
+            Err(TimingError::CountExhausted)
+        ));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_occurrence_dates() {
+        let tz: Tz = "Africa/Lagos".parse().unwrap();
+        let clock = FixedClock::new(Timestamp::Millis(0));
+        let at = |local: &str| {
+            let local = NaiveDateTime::parse_from_str(local, "%Y-%m-%d %H:%M").unwrap();
+            tz.from_local_datetime(&local)
+                .unwrap()
+                .to_utc()
+                .to_timestamp()
+        };
+        let local_times = |occurrences: Occurrences, count: usize| {
+            occurrences
+                .take(count)
+                .map(|occurrence| {
+                    let deadline = occurrence.timing.deadline.to_datetime().with_timezone(&tz);
+                    deadline.format("%Y-%m-%d %H:%M").to_string()
+                })
+                .collect::<Vec<_>>()
+        };
+        let schedule = |frequency: Option<StRegularFrequency>| {
+            let frequency = frequency.map(StFrequency::Regular);
+            StSchedule::try_with_frequency(
+                "id",
+                "2025-01-15T09:00:00.000",
+                "Africa/Lagos",
+                frequency,
+                None,
+                &clock,
+            )
+            .unwrap()
+        };
+        let daily = StRegularFrequency::with_daily_expr(
+            StFrequencyType::Day,
+            StDailyExpression::new(1),
+            None,
+        );
+
+        let mut amended = schedule(Some(daily.clone()));
+        assert!(amended.add_exclusion_with(at("2025-01-16 09:00"), &clock));
+        assert!(!amended.add_exclusion_with(at("2025-01-16 09:00"), &clock));
+        assert!(amended.add_inclusion_with(at("2025-01-18 13:00"), &clock));
+        // Included times before the anchor are ignored, and excluded ones are skipped
+        amended.add_inclusion_with(at("2025-01-14 09:00"), &clock);
+        amended.add_inclusion_with(at("2025-01-19 13:00"), &clock);
+        amended.add_exclusion_with(at("2025-01-19 13:00"), &clock);
+
+        assert_eq!(
+            local_times(amended.occurrences_after(Timestamp::Millis(0)), 5),
+            vec![
+                "2025-01-15 09:00",
+                "2025-01-17 09:00",
+                "2025-01-18 09:00",
+                "2025-01-18 13:00",
+                "2025-01-19 09:00",
+            ]
+        );
+        assert!(!amended.is_occurrence(at("2025-01-16 09:00")));
+        assert!(amended.is_occurrence(at("2025-01-18 13:00")));
+        assert_eq!(
+            amended
+                .previous_occurrence(at("2025-01-18 09:00"))
+                .map(|occurrence| occurrence.timing.deadline),
+            Some(at("2025-01-17 09:00"))
+        );
+
+        // Skipping the next occurrence moves the schedule on, and restoring it moves the schedule back
+        assert_eq!(amended.timing.deadline, at("2025-01-15 09:00"));
+        amended.add_exclusion_with(at("2025-01-15 09:00"), &clock);
+        assert_eq!(amended.timing.deadline, at("2025-01-17 09:00"));
+        assert!(amended.remove_exclusion_with(at("2025-01-15 09:00"), &clock));
+        assert_eq!(amended.timing.deadline, at("2025-01-15 09:00"));
+        assert!(!amended.remove_inclusion_with(at("2025-01-20 13:00"), &clock));
+
+        // Included times are not counted among the occurrences of a frequency with a count
+        let mut counted = daily.clone();
+        counted.set_count(Some(3));
+        let mut amended = schedule(Some(counted));
+        amended.add_exclusion_with(at("2025-01-16 09:00"), &clock);
+        amended.add_inclusion_with(at("2025-01-16 12:00"), &clock);
+        amended.add_inclusion_with(at("2025-01-20 12:00"), &clock);
+
+        assert_eq!(
+            local_times(amended.occurrences_after(Timestamp::Millis(0)), 10),
+            vec![
+                "2025-01-15 09:00",
+                "2025-01-16 12:00",
+                "2025-01-17 09:00",
+                "2025-01-20 12:00",
+            ]
+        );
+
+        // A one-off schedule occurs at its included times too
+        let mut one_off = schedule(None);
+        one_off.add_inclusion_with(at("2025-01-20 09:00"), &clock);
+        let occurrences = one_off
+            .occurrences_after(Timestamp::Millis(0))
+            .collect::<Vec<_>>();
+
+        assert_eq!(occurrences.len(), 2);
+        assert!(!occurrences[0].is_final_occurrence());
+        assert!(occurrences[1].is_final_occurrence());
+        assert_eq!(occurrences[1].timing.deadline, at("2025-01-20 09:00"));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_business_days() {
+        let tz: Tz = "Africa/Lagos".parse().unwrap();
+        let clock = FixedClock::new(Timestamp::Millis(0));
+        let at = |local: &str| {
+            let local = NaiveDateTime::parse_from_str(local, "%Y-%m-%d %H:%M").unwrap();
+            tz.from_local_datetime(&local)
+                .unwrap()
+                .to_utc()
+                .to_timestamp()
+        };
+        let local_dates = |occurrences: Occurrences, count: usize| {
+            occurrences
+                .take(count)
+                .map(|occurrence| {
+                    let deadline = occurrence.timing.deadline.to_datetime().with_timezone(&tz);
+                    deadline.format("%m-%d %H:%M").to_string()
+                })
+                .collect::<Vec<_>>()
+        };
+        let schedule = |frequency: StRegularFrequency, roll: StRollConvention| {
+            let mut schedule = StSchedule::try_with_frequency(
+                "id",
+                "2026-01-25T09:00:00.000",
+                "Africa/Lagos",
+                Some(StFrequency::Regular(frequency)),
+                None,
+                &clock,
+            )
+            .unwrap();
+            let calendar = StHolidayCalendar::from_rules("last mon of may").unwrap();
+            schedule.set_holiday_calendar_with(Some(calendar), roll, &clock);
+            schedule
+        };
+        // Pay on the 25th, which is a Sunday in January, a Saturday in April and a holiday in May 2026
+        let monthly = StRegularFrequency::with_monthly_expr(
+            StFrequencyType::Month,
+            StMonthlyExpression::with_days(1, vec![25]),
+            None,
+        );
+        let cases = [
+            (
+                StRollConvention::Preceding,
+                [
+                    "01-23 09:00",
+                    "02-25 09:00",
+                    "03-25 09:00",
+                    "04-24 09:00",
+                    "05-22 09:00",
+                ],
+            ),
+            (
+                StRollConvention::Following,
+                [
+                    "01-26 09:00",
+                    "02-25 09:00",
+                    "03-25 09:00",
+                    "04-27 09:00",
+                    "05-26 09:00",
+                ],
+            ),
+            (
+                StRollConvention::Skip,
+                [
+                    "02-25 09:00",
+                    "03-25 09:00",
+                    "06-25 09:00",
+                    "08-25 09:00",
+                    "09-25 09:00",
+                ],
+            ),
+        ];
+
+        for (roll, expected) in cases {
+            let payday = schedule(monthly.clone(), roll);
+            assert_eq!(
+                local_dates(payday.occurrences_after(Timestamp::Millis(0)), 5),
+                expected,
+                "{:?}",
+                roll
+            );
+            assert_eq!(payday.get_roll_convention(), Some(roll));
+        }
+
+        // The first occurrence is rolled back from the anchor
+        let payday = schedule(monthly.clone(), StRollConvention::Preceding);
+        assert_eq!(payday.timing.deadline, at("2026-01-23 09:00"));
+        assert_eq!(payday.timing.rolled_from, Some(at("2026-01-25 09:00")));
+        assert!(payday.is_occurrence(at("2026-04-24 09:00")));
+        assert!(!payday.is_occurrence(at("2026-04-25 09:00")));
+        assert_eq!(
+            payday
+                .get_next_occurrence()
+                .map(|occurrence| occurrence.timing.deadline)
+                .unwrap(),
+            at("2026-02-25 09:00")
+        );
+
+        // An occurrence of the frequency before the given time can be rolled after it
+        let payday = schedule(monthly.clone(), StRollConvention::Following);
+        assert_eq!(
+            local_dates(
+                payday.occurrences(at("2026-04-26 00:00"), at("2026-06-01 00:00")),
+                5
+            ),
+            vec!["04-27 09:00", "05-26 09:00"]
+        );
+        let upcoming = payday
+            .at_deadline(at("2026-03-25 09:00"))
+            .get_upcoming_schedule_with(&FixedClock::new(at("2026-04-26 00:00")))
+            .unwrap();
+        assert_eq!(upcoming.timing.deadline, at("2026-04-27 09:00"));
+
+        // A rolled occurrence can be excluded, and the schedule occurs on any day without a calendar
+        let mut payday = schedule(monthly.clone(), StRollConvention::Preceding);
+        payday.add_exclusion_with(at("2026-01-23 09:00"), &clock);
+        assert_eq!(payday.timing.deadline, at("2026-02-25 09:00"));
+        payday.set_holiday_calendar_with(None, StRollConvention::Preceding, &clock);
+        assert_eq!(payday.timing.deadline, at("2026-01-25 09:00"));
+        assert_eq!(payday.get_holiday_calendar(), None);
+
+        // Occurrences rolled to the same day are one, and are counted as the occurrences they were rolled from
+        let mut daily = StRegularFrequency::with_daily_expr(
+            StFrequencyType::Day,
+            StDailyExpression::new(1),
+            None,
+        );
+        daily.set_count(Some(6));
+        let weekdays = schedule(daily, StRollConvention::Following);
+        assert_eq!(
+            local_dates(weekdays.occurrences_after(Timestamp::Millis(0)), 10),
+            vec![
+                "01-26 09:00",
+                "01-27 09:00",
+                "01-28 09:00",
+                "01-29 09:00",
+                "01-30 09:00"
+            ]
+        );
+        let occurrence = weekdays
+            .occurrences_after(Timestamp::Millis(0))
+            .nth(1)
+            .unwrap();
+        assert_eq!(occurrence.get_occurrence_index().unwrap(), 2);
+
+        // No occurrence ever falls on a business day, as the 25th of every month is a holiday
+        let rules = (1..=12)
+            .map(|month| format!("{:02}-25", month))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let calendar = StHolidayCalendar::from_rules(&rules).unwrap();
+        let mut never = schedule(monthly, StRollConvention::Skip);
+        never.set_holiday_calendar_with(Some(calendar), StRollConvention::Skip, &clock);
+        assert!(matches!(
+            never.get_next_occurrence(),
+            Err(TimingError::NonDeterministic)
+        ));
+    }
 }
@@ -1,42 +1,151 @@
-use async_std::sync::{Arc, Mutex};
-use async_std::{channel, task};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use async_std::channel;
+use async_std::future;
+use async_std::sync::Mutex;
 
 use wasm_bindgen::prelude::*;
-use wasm_bindgen_futures::spawn_local;
 
+use crate::core::clock::{Clock, FixedClock, ManualClock, SystemClock};
+use crate::core::command::{ScheduleEntry, StCommandOutcome};
+use crate::core::errors::{RunnerError, SnapshotError, TimingError};
+use crate::core::event::{
+    DueEvent, ExpiredEvent, MissedEvent, OverdueEvent, ReminderEvent, SchedulerEvent, UpcomingEvent,
+};
+use crate::core::policy::StCatchUpPolicy;
 use crate::core::schedule::StSchedule;
-use crate::core::time::timestamp;
+use crate::core::snapshot::{
+    OverdueSnapshot, ScheduleSnapshot, ScheduleStatus, SnoozeSnapshot, StSchedulerSnapshot,
+    SNAPSHOT_VERSION,
+};
+use crate::core::subscriber::{JsSubscriber, Subscriber};
 use crate::core::time::Timestamp;
+use crate::core::trigger::{Trigger, TriggerKind};
 use crate::queue::priority_queue::PQComparator;
 use crate::queue::priority_queue::PriorityQueue;
+use crate::runtime::spawn_local;
 use crate::traits::*;
 use crate::{console_error, console_log};
 
+/// The longest the runner idles in one go before re-polling the scheduler.
+///
+/// Timers in the browser overflow and fire immediately for delays longer than a signed 32-bit
+/// number of milliseconds (~24.8 days), so longer waits are broken into multiple idles.
+const MAX_IDLE: Timestamp = Timestamp::Millis(i32::MAX as i64);
+
+/// How late a schedule can be dispatched by default before its occurrence counts as missed
+const DEFAULT_LATE_TOLERANCE: Timestamp = Timestamp::Millis(60_000);
+
+/// The most occurrences of a schedule that are caught up on when it is fired, the latest ones being kept.
+///
+/// The occurrences before them are not fired whatever the catch-up policy, and are only reported as missed
+/// by their number, so a schedule that was asleep for long does not flood subscribers.
+const MAX_CAUGHT_UP_OCCURRENCES: usize = 100;
+
+/// How the occurrences that fell inside a pause are handled when resuming without an explicit policy.
+///
+/// A paused schedule is one whose occurrences are deliberately unwanted for a while, so they are only
+/// reported as missed rather than fired.
+const DEFAULT_RESUME_POLICY: StCatchUpPolicy = StCatchUpPolicy::SkipAll;
+
 enum Poll<P, R> {
     Empty,
     Pending(P),
     Ready(R),
 }
 
-#[derive(Debug, Clone)]
+/// The sending end of the channel the result of a command is sent back through
+type Reply<T> = channel::Sender<T>;
+
+#[derive(Debug)]
 enum XMessage {
-    Suspend,
-    Remove(String),
-    Update(StSchedule),
+    Add(StSchedule, Reply<StCommandOutcome>),
+    Upsert(StSchedule, Reply<StCommandOutcome>),
+    Remove(String, Reply<StCommandOutcome>),
+    Update(StSchedule, Reply<StCommandOutcome>),
+    DropAll(Reply<u32>),
+    Pause(String, Option<Timestamp>, Reply<StCommandOutcome>),
+    Resume(String, Option<StCatchUpPolicy>, Reply<StCommandOutcome>),
+    List(Reply<Vec<ScheduleEntry>>),
+    Upcoming(usize, Reply<Vec<UpcomingEvent>>),
+    NextFireTime(String, Reply<Option<Timestamp>>),
+    QueueSize(Reply<usize>),
+    Snapshot(Reply<StSchedulerSnapshot>),
+    SnoozeFor(String, Timestamp, Reply<StCommandOutcome>),
+    SnoozeUntil(String, Timestamp, Reply<StCommandOutcome>),
+    Acknowledge(String, Reply<StCommandOutcome>),
+}
+
+/// A schedule taken off the queue until it is resumed
+#[derive(Debug, Clone)]
+struct Paused {
+    schedule: StSchedule,
+    /// When the schedule is automatically resumed, if ever
+    until: Option<Timestamp>,
 }
 
 #[wasm_bindgen]
 pub struct StScheduler {
     pq: PriorityQueue<StSchedule>,
-    /// A JavaScript function subscribing to the scheduler for updates
-    subscriber: Option<js_sys::Function>,
-    suspended: bool,
+    /// The schedules that are paused, by ID
+    paused: HashMap<String, Paused>,
+    /// The one-off triggers queued alongside the schedules, e.g. snoozed occurrences
+    triggers: PriorityQueue<Trigger>,
+    /// The occurrence of each schedule that was fired last, by ID
+    last_fired: HashMap<String, StSchedule>,
+    /// The subscribers notified of the events dispatched by the scheduler
+    subscribers: Vec<Box<dyn Subscriber>>,
+    /// The clock the scheduler reads the current time from
+    clock: Box<dyn Clock>,
+    /// The policy for missed occurrences of schedules that do not have their own
+    catch_up: StCatchUpPolicy,
+    /// How late an occurrence can be dispatched before it counts as missed
+    late_tolerance: Timestamp,
+    /// How long after its planned time an occurrence becomes overdue, for schedules that do not have their own
+    grace_period: Option<Timestamp>,
+}
+
+/// The lifecycle of a [`StSchedulerRunner`].
+///
+/// A runner is idle until it runs a scheduler, and it can then be suspended and resumed any number of times
+/// until it is stopped for good.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StRunnerState {
+    /// The runner has not run a scheduler yet
+    Idle,
+    /// The runner fires the schedules of its scheduler as they are due
+    Running,
+    /// The runner still carries out commands but fires nothing until it is resumed
+    Suspended,
+    /// The runner no longer carries out commands nor fires anything
+    Stopped,
+}
+
+impl core::fmt::Display for StRunnerState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let state = match self {
+            StRunnerState::Idle => "idle",
+            StRunnerState::Running => "running",
+            StRunnerState::Suspended => "suspended",
+            StRunnerState::Stopped => "stopped",
+        };
+
+        write!(f, "{}", state)
+    }
 }
 
 #[wasm_bindgen]
 pub struct StSchedulerRunner {
     sender: channel::Sender<XMessage>,
     receiver: channel::Receiver<XMessage>,
+    /// Shared with the poll loop, which idles while suspended and ends once stopped
+    state: Rc<Cell<StRunnerState>>,
+    /// Wakes an idling poll loop early, e.g. when a message changes the head of the queue
+    wake_sender: channel::Sender<()>,
+    wake_receiver: channel::Receiver<()>,
 }
 
 // unsafe impl Send for StScheduler {}
@@ -44,34 +153,329 @@ pub struct StSchedulerRunner {
 #[wasm_bindgen]
 impl StScheduler {
     pub fn new() -> StScheduler {
+        Self::with_clock(Box::new(SystemClock))
+    }
+
+    /// Makes the scheduler read the current time from the given manual clock, which can then be
+    /// set or advanced from the outside to step through schedules deterministically.
+    pub fn use_manual_clock(&mut self, clock: &ManualClock) {
+        self.clock = Box::new(clock.clone());
+    }
+
+    /// Subscribes a JavaScript function to the scheduler, which is called with an `StSchedulerEvent` object
+    /// for every event of the schedules' lifecycle, telling them apart by their `kind`
+    pub fn subscribe(&mut self, receiver: js_sys::Function) {
+        self.add_subscriber(JsSubscriber::new(receiver));
+    }
+
+    /// Sets the policy for missed occurrences of the schedules that do not have their own policy
+    pub fn set_catch_up_policy(&mut self, policy: StCatchUpPolicy) {
+        self.catch_up = policy;
+    }
+
+    /// Sets how late, in milliseconds, an occurrence can be dispatched before it counts as missed
+    pub fn set_late_tolerance(&mut self, millis: u32) {
+        self.late_tolerance = Timestamp::Millis(millis as i64);
+    }
+
+    /// Sets how long, in milliseconds, after its planned time an occurrence becomes overdue unless it is
+    /// acknowledged, for the schedules that do not have their own grace period. Passing `undefined` stops
+    /// such occurrences from becoming overdue, which is the default.
+    pub fn set_grace_period(&mut self, millis: Option<u32>) {
+        self.grace_period = millis.map(|ms| Timestamp::Millis(ms as i64));
+    }
+
+    /// Adds a schedule to the scheduler.
+    ///
+    /// A schedule whose deadline has already passed is queued at its upcoming occurrence, since there is
+    /// no telling which of its past occurrences were already fired. Catch-up policies only apply to the
+    /// occurrences missed while the schedule is queued.
+    ///
+    /// Returns `false` when the schedule has no upcoming occurrence, or one with the same ID already exists.
+    pub fn add_schedule(&mut self, schedule: StSchedule) -> bool {
+        self.insert_schedule(schedule) == StCommandOutcome::Inserted
+    }
+
+    /// Takes a snapshot of the schedules held by the scheduler, along with which of their occurrences were
+    /// fired last, are paused, snoozed or waiting to be acknowledged
+    pub fn snapshot(&self) -> StSchedulerSnapshot {
+        let queued = self.pq.iter().map(|s| (s, ScheduleStatus::Queued));
+        let paused = self
+            .paused
+            .values()
+            .map(|p| (&p.schedule, ScheduleStatus::Paused(p.until)));
+        // A schedule with no occurrence left is only held for its fired occurrences
+        let expired = self
+            .last_fired
+            .iter()
+            .filter(|(id, _)| !self.contains(id))
+            .map(|(_, occurrence)| (occurrence, ScheduleStatus::Expired));
+
+        let mut schedules = queued
+            .chain(paused)
+            .chain(expired)
+            .map(|(schedule, status)| self.snapshot_schedule(schedule, status))
+            .collect::<Vec<_>>();
+
+        schedules.sort_by(|a, b| a.schedule.get_id_as_str().cmp(b.schedule.get_id_as_str()));
+        StSchedulerSnapshot::new(schedules)
+    }
+
+    /// Restores the state recorded by the given snapshot, replacing every schedule held by the scheduler.
+    ///
+    /// Each schedule is queued at the occurrence it was due next when the snapshot was taken, even when it is
+    /// already past, so the occurrences that fell due since are fired or reported as missed according to the
+    /// catch-up policies, while the ones fired before the snapshot are not fired again.
+    ///
+    /// Throws when the snapshot is of a version other than the current one, which only happens for snapshots
+    /// not read with `StSchedulerSnapshot.from_json`, as it migrates older ones.
+    pub fn restore(&mut self, snapshot: StSchedulerSnapshot) -> Result<(), SnapshotError> {
+        if snapshot.get_version() != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(snapshot.get_version()));
+        }
+
+        self.drop_all_schedules();
+
+        for entry in snapshot.into_schedules() {
+            let schedule = entry.schedule;
+            let id = schedule.get_id();
+
+            if let Some(planned_at) = entry.last_fired_at {
+                self.last_fired
+                    .insert(id.clone(), schedule.at_deadline(planned_at));
+            }
+
+            if let Some(snooze) = entry.snooze {
+                let occurrence = schedule.at_deadline(snooze.planned_at);
+                self.triggers
+                    .enqueue(Trigger::snooze(occurrence, snooze.fires_at));
+            }
+
+            for pending in entry.unacknowledged {
+                let occurrence = schedule.at_deadline(pending.planned_at);
+                let due_at = pending.overdue_at - pending.grace;
+                self.triggers
+                    .enqueue(Trigger::overdue(occurrence, due_at, pending.grace));
+            }
+
+            match entry.status {
+                ScheduleStatus::Queued => self.enqueue(schedule),
+                ScheduleStatus::Paused(until) => {
+                    self.paused.insert(id, Paused { schedule, until });
+                }
+                ScheduleStatus::Expired => {}
+            }
+        }
+
+        console_log!(
+            "Restored {} schedules",
+            self.queue_size() + self.paused.len()
+        );
+        Ok(())
+    }
+}
+
+impl StScheduler {
+    /// Initializes a scheduler that reads the current time from the given [`Clock`]
+    pub fn with_clock(clock: Box<dyn Clock>) -> StScheduler {
         let comparator = PQComparator::new(|a: &StSchedule, b: &StSchedule| a < b);
 
         StScheduler {
             pq: PriorityQueue::new(Box::new(comparator)),
-            subscriber: None,
-            suspended: true,
+            paused: HashMap::new(),
+            triggers: PriorityQueue::new(Box::new(PQComparator::new(|a, b| a < b))),
+            last_fired: HashMap::new(),
+            subscribers: vec![],
+            clock,
+            catch_up: StCatchUpPolicy::default(),
+            late_tolerance: DEFAULT_LATE_TOLERANCE,
+            grace_period: None,
         }
     }
 
-    pub fn subscribe(&mut self, receiver: js_sys::Function) {
+    /// Subscribes the given [`Subscriber`] to the events dispatched by the scheduler
+    pub fn add_subscriber<S>(&mut self, subscriber: S)
+    where
+        S: Subscriber + 'static,
+    {
         console_log!("Subscription received!");
-        self.subscriber = Some(receiver)
+        self.subscribers.push(Box::new(subscriber));
     }
 
-    pub fn add_schedule(&mut self, schedule: StSchedule) -> bool {
+    /// Subscribes to the events dispatched by the scheduler through a channel, returning its receiving end.
+    ///
+    /// The receiver is a `futures::Stream` of [`SchedulerEvent`]s, which ends when the scheduler is dropped.
+    pub fn events(&mut self) -> channel::Receiver<SchedulerEvent> {
+        let (tx, rx) = channel::unbounded::<SchedulerEvent>();
+        self.add_subscriber(tx);
+        rx
+    }
+
+    /// Adds a schedule that does not exist yet to the scheduler, see [`StScheduler::add_schedule`]
+    pub fn insert_schedule(&mut self, schedule: StSchedule) -> StCommandOutcome {
+        if self.contains(schedule.get_id_as_str()) {
+            console_error!(
+                "Schedule with ID '{}' already exists",
+                schedule.get_id_as_str()
+            );
+            return StCommandOutcome::Duplicate;
+        }
+
+        self.queue_schedule(schedule)
+    }
+
+    /// Replaces the schedule with the same ID as the given one when there is one, otherwise adds it
+    pub fn upsert_schedule(&mut self, schedule: StSchedule) -> StCommandOutcome {
+        if self.contains(schedule.get_id_as_str()) {
+            self.update_schedule(schedule)
+        } else {
+            self.queue_schedule(schedule)
+        }
+    }
+
+    /// Removes every schedule from the scheduler, along with their snoozes and reminders, returning how
+    /// many there were
+    pub fn drop_all_schedules(&mut self) -> u32 {
+        let count = self.pq.clear() + self.paused.len();
+
+        self.paused.clear();
+        self.triggers.clear();
+        self.last_fired.clear();
+        console_log!("Dropped {} schedules", count);
+
+        count as u32
+    }
+
+    /// Lists the schedules held by the scheduler, paused or not, in the order they are next due
+    pub fn list_schedules(&self) -> Vec<ScheduleEntry> {
+        let queued = self.pq.iter().map(|schedule| ScheduleEntry {
+            id: schedule.get_id(),
+            next_at: Timestamp::Millis(schedule.get_deadline_millis()),
+            paused: false,
+            resumes_at: None,
+        });
+        let paused = self.paused.iter().map(|(id, paused)| ScheduleEntry {
+            id: id.clone(),
+            next_at: Timestamp::Millis(paused.schedule.get_deadline_millis()),
+            paused: true,
+            resumes_at: paused.until,
+        });
+
+        let mut entries = queued.chain(paused).collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.next_at.cmp(&b.next_at).then_with(|| a.id.cmp(&b.id)));
+        entries
+    }
+
+    /// Returns the given number of occurrences next due across the queued schedules, earliest first.
+    ///
+    /// The occurrences of paused schedules and snoozed occurrences are left out.
+    pub fn upcoming(&self, count: usize) -> Vec<UpcomingEvent> {
+        let mut upcoming = vec![];
+
+        for schedule in self.pq.iter() {
+            let mut occurrence = schedule.clone();
+
+            for _ in 0..count {
+                upcoming.push(UpcomingEvent::new(&occurrence));
+                match occurrence.get_next_occurrence() {
+                    Ok(next) => occurrence = next,
+                    Err(_) => break,
+                }
+            }
+        }
+
+        upcoming.sort_by(|a, b| {
+            a.planned_at
+                .cmp(&b.planned_at)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        upcoming.truncate(count);
+        upcoming
+    }
+
+    /// Returns when the schedule with the given ID next fires, be it at its next occurrence or its snoozed
+    /// one, or `None` when there is no such schedule or it is paused
+    pub fn next_fire_time(&self, id: &str) -> Option<Timestamp> {
+        let queued = self
+            .pq
+            .find(id.to_string())
+            .map(|s| Timestamp::Millis(s.get_deadline_millis()));
+        let snoozed = self.triggers.find(Trigger::snooze_key(id)).map(|t| t.at);
+
+        queued.into_iter().chain(snoozed).min()
+    }
+
+    /// Returns the number of schedules queued, which leaves out the paused ones
+    pub fn queue_size(&self) -> usize {
+        self.pq.size()
+    }
+
+    /// Records the state of the given schedule, held by the scheduler with the given status
+    fn snapshot_schedule(&self, schedule: &StSchedule, status: ScheduleStatus) -> ScheduleSnapshot {
+        let id = schedule.get_id_as_str();
+        let planned_at =
+            |occurrence: &StSchedule| Timestamp::Millis(occurrence.get_deadline_millis());
+        let triggers = self.triggers.iter().filter(|t| t.get_schedule_id() == id);
+
+        let mut snooze = None;
+        let mut unacknowledged = vec![];
+
+        for trigger in triggers {
+            match &trigger.kind {
+                TriggerKind::Snooze(occurrence) => {
+                    snooze = Some(SnoozeSnapshot {
+                        planned_at: planned_at(occurrence),
+                        fires_at: trigger.at,
+                    })
+                }
+                TriggerKind::Overdue(occurrence, grace) => unacknowledged.push(OverdueSnapshot {
+                    planned_at: planned_at(occurrence),
+                    overdue_at: trigger.at,
+                    grace: *grace,
+                }),
+                // Reminders are queued again from the schedule when it is restored
+                TriggerKind::Reminder(..) => {}
+            }
+        }
+
+        unacknowledged.sort_by_key(|pending| pending.planned_at);
+
+        ScheduleSnapshot {
+            schedule: schedule.clone(),
+            status,
+            last_fired_at: self.last_fired.get(id).map(planned_at),
+            snooze,
+            unacknowledged,
+        }
+    }
+
+    /// Returns whether the scheduler holds a schedule with the given ID, paused or not
+    fn contains(&self, id: &str) -> bool {
+        self.paused.contains_key(id) || self.pq.find(id.to_string()).is_some()
+    }
+
+    /// Queues the given schedule at its upcoming occurrence, see [`StScheduler::add_schedule`]
+    fn queue_schedule(&mut self, schedule: StSchedule) -> StCommandOutcome {
         let queued_msg = format!("Schedule with ID '{}' queued!", schedule.get_id_as_str());
+        let deadline = Timestamp::Millis(schedule.get_deadline_millis());
 
-        if schedule.is_passed() {
+        if schedule.is_passed_with(&*self.clock) {
             console_log!("Added schedule already passed");
             console_log!("Evaluating upcoming schedules");
             let id = schedule.get_id_as_str();
-            let upcoming_schedule = schedule.get_upcoming_schedule();
+            let upcoming_schedule = schedule.get_upcoming_schedule_with(&*self.clock);
 
             match upcoming_schedule {
                 Ok(s) => {
-                    self.pq.enqueue(s);
+                    self.enqueue(s);
                     console_log!("{}", queued_msg);
-                    true
+                    StCommandOutcome::Inserted
+                }
+                Err(TimingError::FrequencyExpired | TimingError::CountExhausted) => {
+                    console_log!("Added schedule with ID '{}' already expired", id);
+                    let event = ExpiredEvent::new(&schedule, self.clock.now());
+                    self.dispatch(&SchedulerEvent::Expired(event));
+                    StCommandOutcome::Invalid
                 }
                 Err(err) => {
                     console_error!("Failed to compute upcoming schedules: {}", err);
@@ -79,66 +483,526 @@ impl StScheduler {
                         "Error occured while computing for schedule with ID '{}'",
                         id
                     );
-                    false
+                    StCommandOutcome::Invalid
                 }
             }
+        } else if schedule.get_dates().is_excluded(deadline) {
+            // A schedule is only left at an excluded deadline when it has no occurrence left after it
+            console_log!(
+                "Added schedule with ID '{}' has no occurrence left",
+                schedule.get_id_as_str()
+            );
+            let event = ExpiredEvent::new(&schedule, self.clock.now());
+            self.dispatch(&SchedulerEvent::Expired(event));
+            StCommandOutcome::Invalid
         } else {
-            self.pq.enqueue(schedule);
+            self.enqueue(schedule);
             console_log!("{}", queued_msg);
-            true
+            StCommandOutcome::Inserted
         }
     }
 
-    #[inline]
-    fn remove_schedule(&mut self, id: String) -> Option<StSchedule> {
-        self.pq.remove(id)
+    /// Notifies every subscriber of the given event
+    fn dispatch(&self, event: &SchedulerEvent) {
+        for subscriber in self.subscribers.iter() {
+            subscriber.notify(event);
+        }
     }
 
-    fn update_schedule(&mut self, schedule: StSchedule) -> bool {
-        self.remove_schedule(schedule.get_id())
-            .map_or(false, |_| self.add_schedule(schedule))
+    /// Pauses the schedule with the given ID, taking it off the queue until it is resumed, or automatically
+    /// until the given time when there is one.
+    ///
+    /// Pausing a schedule that is already paused only changes when it is resumed. Returns `false` when there
+    /// is no such schedule.
+    pub fn pause_schedule(&mut self, id: String, until: Option<Timestamp>) -> bool {
+        if let Some(paused) = self.paused.get_mut(&id) {
+            paused.until = until;
+            return true;
+        }
+
+        match self.pq.remove(id.clone()) {
+            Some(schedule) => {
+                console_log!("Schedule with ID '{}' paused", id);
+                // Reminders are queued again from the schedule's deadline when it is resumed
+                self.remove_triggers(&id, |t| matches!(t, TriggerKind::Reminder(..)));
+                self.paused.insert(id, Paused { schedule, until });
+                true
+            }
+            None => false,
+        }
     }
 
-    fn suspend(&mut self) {
-        if self.suspended == false {
-            console_log!("Scheduler suspended!");
-            self.suspended = true;
+    /// Resumes the paused schedule with the given ID, putting it back on the queue.
+    ///
+    /// The occurrences that fell inside the pause are handled with the given policy, and when there is none,
+    /// they are skipped and reported as missed. Returns `false` when there is no such paused schedule.
+    pub fn resume_schedule(&mut self, id: String, policy: Option<StCatchUpPolicy>) -> bool {
+        let schedule = match self.paused.remove(&id) {
+            Some(paused) => paused.schedule,
+            None => return false,
+        };
+
+        console_log!("Schedule with ID '{}' resumed", id);
+
+        if schedule.is_passed_with(&*self.clock) {
+            self.fire_with(schedule, policy.unwrap_or(DEFAULT_RESUME_POLICY));
+        } else {
+            self.enqueue(schedule);
         }
+
+        true
+    }
+
+    /// Checks if the schedule with the given ID is paused
+    pub fn is_paused(&self, id: &str) -> bool {
+        self.paused.contains_key(id)
     }
 
-    fn start(&mut self) {
-        if self.suspended == true {
-            console_log!("Scheduler running ✅");
-            self.suspended = false;
+    /// Snoozes the occurrence of the schedule with the given ID that was fired last, re-firing it at the given
+    /// time, without affecting the schedule's regular occurrences.
+    ///
+    /// Snoozing an occurrence that is already snoozed only changes when it is re-fired. Returns `false` when
+    /// no occurrence of the schedule has been fired yet.
+    pub fn snooze_schedule(&mut self, id: String, until: Timestamp) -> bool {
+        let occurrence = match self.last_fired.get(&id) {
+            Some(occurrence) => occurrence.clone(),
+            None => return false,
+        };
+
+        console_log!("Schedule with ID '{}' snoozed until {}", id, until);
+        // Snoozing an occurrence acknowledges it, and it can become overdue again once re-fired
+        self.acknowledge_schedule(&id);
+        let trigger = Trigger::snooze(occurrence, until);
+        self.triggers.remove(trigger.get_id());
+        self.triggers.enqueue(trigger);
+        true
+    }
+
+    /// Same as [`StScheduler::snooze_schedule`] but the occurrence is re-fired after the given duration
+    pub fn snooze_schedule_for(&mut self, id: String, duration: Timestamp) -> bool {
+        let until = self.clock.now() + duration;
+        self.snooze_schedule(id, until)
+    }
+
+    /// Acknowledges the fired occurrences of the schedule with the given ID, so they do not become overdue.
+    ///
+    /// Returns `false` when none of its occurrences were waiting to be acknowledged.
+    pub fn acknowledge_schedule(&mut self, id: &str) -> bool {
+        let pending = self
+            .triggers
+            .iter()
+            .any(|t| t.get_schedule_id() == id && matches!(t.kind, TriggerKind::Overdue(..)));
+
+        self.remove_triggers(id, |t| matches!(t, TriggerKind::Overdue(..)));
+        pending
+    }
+
+    /// Dispatches the due event of the given occurrence, arming it to become overdue once its grace period has
+    /// passed since its planned time, which makes it overdue right away when it was dispatched later than that.
+    ///
+    /// A snoozed occurrence being re-fired is due again when it is re-fired, so its grace period starts then.
+    fn dispatch_due(&mut self, occurrence: StSchedule, event: DueEvent) {
+        let grace = occurrence.get_grace_period_override().or(self.grace_period);
+        let due_at = match event.snoozed {
+            true => event.dispatched_at,
+            false => event.planned_at,
+        };
+
+        self.dispatch(&SchedulerEvent::Due(event));
+        self.last_fired
+            .insert(occurrence.get_id(), occurrence.clone());
+
+        if let Some(grace) = grace {
+            let trigger = Trigger::overdue(occurrence, due_at, grace);
+            self.triggers.remove(trigger.get_id());
+
+            match trigger.at <= self.clock.now() {
+                true => self.dispatch_overdue(trigger),
+                false => self.triggers.enqueue(trigger),
+            }
+        }
+    }
+
+    /// Dispatches the overdue event of the occurrence of the given overdue trigger
+    fn dispatch_overdue(&self, trigger: Trigger) {
+        if let TriggerKind::Overdue(occurrence, grace) = trigger.kind {
+            console_log!(
+                "Schedule with ID '{}' is overdue",
+                occurrence.get_id_as_str()
+            );
+            let event = OverdueEvent::new(&occurrence, grace, self.clock.now());
+            self.dispatch(&SchedulerEvent::Overdue(event));
         }
     }
 
-    fn is_suspended(&self) -> bool {
-        return self.suspended == true;
+    /// Fires the triggers that are due by now
+    fn fire_elapsed_triggers(&mut self) {
+        let now = self.clock.now();
+
+        while self.triggers.peek().is_some_and(|t| t.at <= now) {
+            let trigger = self.triggers.dequeue().unwrap();
+
+            match trigger.kind {
+                TriggerKind::Snooze(occurrence) => {
+                    let mut event = DueEvent::new(&occurrence, now);
+                    event.snoozed = true;
+                    self.dispatch_due(occurrence, event);
+                }
+                TriggerKind::Reminder(occurrence, offset) => {
+                    let event = ReminderEvent::new(&occurrence, offset, now);
+                    self.dispatch(&SchedulerEvent::Reminder(event));
+
+                    if let Ok(next) = occurrence.get_next_occurrence() {
+                        self.queue_reminder(next, offset);
+                    }
+                }
+                TriggerKind::Overdue(..) => self.dispatch_overdue(trigger),
+            }
+        }
+    }
+
+    /// Queues a schedule along with its reminders
+    fn enqueue(&mut self, schedule: StSchedule) {
+        for offset in schedule.get_reminder_offsets().to_vec() {
+            self.queue_reminder(schedule.clone(), offset);
+        }
+
+        self.dispatch(&SchedulerEvent::Upcoming(UpcomingEvent::new(&schedule)));
+        self.pq.enqueue(schedule);
+    }
+
+    /// Queues the reminder at the given offset before the first occurrence, starting from the given one,
+    /// whose reminder is still ahead, replacing any reminder already queued at that offset.
+    ///
+    /// Every offset is queued independently, so an offset longer than the time between two occurrences
+    /// still reminds of each of them.
+    fn queue_reminder(&mut self, occurrence: StSchedule, offset: Timestamp) {
+        let now = self.clock.now();
+        let mut occurrence = occurrence;
+
+        self.triggers
+            .remove(Trigger::reminder_key(occurrence.get_id_as_str(), offset));
+
+        while Timestamp::Millis(occurrence.get_deadline_millis()) - offset <= now {
+            match occurrence.get_next_occurrence() {
+                Ok(next) => occurrence = next,
+                Err(_) => return,
+            }
+        }
+
+        self.triggers.enqueue(Trigger::reminder(occurrence, offset));
+    }
+
+    /// Removes the triggers of the given kinds that belong to the schedule with the given ID
+    fn remove_triggers<F>(&mut self, id: &str, predicate: F)
+    where
+        F: Fn(&TriggerKind) -> bool,
+    {
+        let keys = self
+            .triggers
+            .iter()
+            .filter(|t| t.get_schedule_id() == id && predicate(&t.kind))
+            .map(|t| t.get_id())
+            .collect::<Vec<_>>();
+
+        for key in keys {
+            self.triggers.remove(key);
+        }
+    }
+
+    /// Resumes the paused schedules that are due to be resumed by now
+    fn resume_elapsed_pauses(&mut self) {
+        let now = self.clock.now();
+        let elapsed = self
+            .paused
+            .iter()
+            .filter(|(_, p)| p.until.is_some_and(|until| until <= now))
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+
+        for id in elapsed {
+            self.resume_schedule(id, None);
+        }
+    }
+
+    /// Returns the earliest time a paused schedule is due to be resumed
+    fn next_resume(&self) -> Option<Timestamp> {
+        self.paused.values().filter_map(|p| p.until).min()
+    }
+
+    fn remove_schedule(&mut self, id: String) -> Option<StSchedule> {
+        self.remove_triggers(&id, |_| true);
+        self.last_fired.remove(&id);
+
+        match self.paused.remove(&id) {
+            Some(paused) => Some(paused.schedule),
+            None => self.pq.remove(id),
+        }
+    }
+
+    /// Replaces the schedule with the same ID as the given one.
+    ///
+    /// A replacement without an upcoming occurrence leaves the scheduler without the schedule.
+    fn update_schedule(&mut self, schedule: StSchedule) -> StCommandOutcome {
+        // A paused schedule stays paused with its update
+        if let Some(paused) = self.paused.get_mut(schedule.get_id_as_str()) {
+            paused.schedule = schedule;
+            return StCommandOutcome::Replaced;
+        }
+
+        // The update replaces the schedule's reminders but not its snoozed occurrence
+        match self.pq.remove(schedule.get_id()) {
+            Some(_) => {
+                let id = schedule.get_id();
+                self.remove_triggers(&id, |t| matches!(t, TriggerKind::Reminder(..)));
+                match self.queue_schedule(schedule) {
+                    StCommandOutcome::Inserted => StCommandOutcome::Replaced,
+                    outcome => outcome,
+                }
+            }
+            None => StCommandOutcome::NotFound,
+        }
     }
 
     fn poll(&mut self) -> Poll<Timestamp, StSchedule> {
+        self.resume_elapsed_pauses();
+        self.fire_elapsed_triggers();
+
+        // The earliest of the next resumption and the next trigger
+        let next_resume = self
+            .next_resume()
+            .into_iter()
+            .chain(self.triggers.peek().map(|t| t.at))
+            .min()
+            .map(|at| at - self.clock.now());
+
         if self.pq.is_empty() {
-            console_log!("Nothing to poll");
-            return Poll::Empty;
+            return match next_resume {
+                Some(difference) => {
+                    console_log!("Next trigger in {:.3}s", difference.as_sec_f64());
+                    Poll::Pending(difference)
+                }
+                None => {
+                    console_log!("Nothing to poll");
+                    Poll::Empty
+                }
+            };
         }
 
         let peeked = self.pq.peek().unwrap();
         let peeked_id = peeked.get_id();
-        let anchor_ts = Timestamp::Millis(peeked.get_anchor_millis());
-        let current_ts = timestamp();
-        let difference = anchor_ts - current_ts;
+        let deadline_ts = Timestamp::Millis(peeked.get_deadline_millis());
+        let current_ts = self.clock.now();
+        let difference = deadline_ts - current_ts;
 
         if difference > Timestamp::Millis(0) {
             console_log!("Next schedule in {:.3}s", difference.as_sec_f64());
-            return Poll::Pending(difference);
+            return Poll::Pending(next_resume.map_or(difference, |r| r.min(difference)));
         }
 
         let item = self.pq.dequeue().unwrap();
         let item_id = item.get_id();
         assert_eq!(item_id, peeked_id);
 
-        return Poll::Ready(item);
+        Poll::Ready(item)
+    }
+
+    /// Fires a schedule that is due, along with its later occurrences that are also due by now, then pushes
+    /// it back onto the queue at its next occurrence.
+    ///
+    /// Occurrences dispatched later than the late tolerance allows are missed, and are handled with the
+    /// schedule's catch-up policy, falling back to the scheduler's, for up to [`MAX_CAUGHT_UP_OCCURRENCES`]
+    /// of the latest ones.
+    fn fire(&mut self, due: StSchedule) {
+        let policy = due.get_catch_up_policy().unwrap_or(self.catch_up);
+        self.fire_with(due, policy);
+    }
+
+    /// Same as [`StScheduler::fire`] but the missed occurrences are handled with the given policy
+    fn fire_with(&mut self, due: StSchedule, policy: StCatchUpPolicy) {
+        let now = self.clock.now();
+        let (occurrences, dropped) = Self::caught_up(due, now);
+
+        let missed_count = occurrences
+            .iter()
+            .take_while(|s| now - Timestamp::Millis(s.get_deadline_millis()) > self.late_tolerance)
+            .count();
+        // Missed occurrences are always the earliest ones
+        let (missed, on_time) = occurrences.split_at(missed_count);
+
+        let mut skipped: Vec<Timestamp> = vec![];
+        let mut fired: Vec<DueEvent> = vec![];
+
+        if let Some((latest, earlier)) = missed.split_last() {
+            let planned = |v: &[StSchedule]| {
+                v.iter()
+                    .map(|s| Timestamp::Millis(s.get_deadline_millis()))
+                    .collect::<Vec<_>>()
+            };
+
+            match policy {
+                StCatchUpPolicy::FireAll => {
+                    fired.extend(missed.iter().map(|s| DueEvent::new(s, now)))
+                }
+                StCatchUpPolicy::FireLatest => {
+                    skipped = planned(earlier);
+                    fired.push(DueEvent::new(latest, now));
+                }
+                StCatchUpPolicy::SkipAll => skipped = planned(missed),
+                StCatchUpPolicy::Coalesce => {
+                    let mut event = DueEvent::new(latest, now);
+                    event.coalesced = planned(earlier);
+                    fired.push(event);
+                }
+            }
+        }
+
+        fired.extend(on_time.iter().map(|s| DueEvent::new(s, now)));
+
+        if !skipped.is_empty() || dropped > 0 {
+            console_log!(
+                "Missed {} occurrence(s) of schedule with ID '{}'",
+                skipped.len() as u64 + dropped,
+                occurrences[0].get_id_as_str()
+            );
+            self.dispatch(&SchedulerEvent::Missed(MissedEvent {
+                id: occurrences[0].get_id(),
+                missed: skipped,
+                dropped,
+                detected_at: now,
+                policy,
+            }));
+        }
+
+        for event in fired {
+            let occurrence = occurrences
+                .iter()
+                .find(|s| Timestamp::Millis(s.get_deadline_millis()) == event.planned_at)
+                .cloned()
+                .unwrap();
+            self.dispatch_due(occurrence, event);
+        }
+
+        self.push_back(occurrences.last().cloned().unwrap());
+    }
+
+    /// Returns the occurrences of the given due schedule that are due by the given time, up to the latest
+    /// [`MAX_CAUGHT_UP_OCCURRENCES`] of them, along with the number of the ones before them, which are dropped.
+    ///
+    /// The occurrences are walked on from the due one, which advances its index if it is tracked. Once there are
+    /// more of them than are kept, the latest ones are looked up before the given time instead, in windows that
+    /// double in length, so the occurrences of a schedule asleep for long are not evaluated one by one. The ones
+    /// that are jumped over are then estimated from the spacing of the ones kept, and the index of the ones kept
+    /// is no longer tracked.
+    fn caught_up(due: StSchedule, now: Timestamp) -> (Vec<StSchedule>, u64) {
+        let deadline = |s: &StSchedule| Timestamp::Millis(s.get_deadline_millis());
+        let mut walked = vec![due];
+
+        // The first occurrence past the ones walked that is also due
+        let next = loop {
+            match walked.last().unwrap().get_next_occurrence() {
+                Ok(next) if deadline(&next) <= now => {
+                    if walked.len() == MAX_CAUGHT_UP_OCCURRENCES {
+                        break next;
+                    }
+                    walked.push(next);
+                }
+                _ => return (walked, 0),
+            }
+        };
+
+        let end = now + Timestamp::Millis(1);
+        let first = deadline(&next);
+        let mut window =
+            (deadline(walked.last().unwrap()) - deadline(&walked[0])).max(Timestamp::Millis(1));
+
+        loop {
+            let from = (end - window).max(first);
+            let mut latest = walked[0].occurrences(from, end).collect::<Vec<_>>();
+
+            // There are few enough occurrences left that they are walked on too, so they are counted exactly
+            if from == first {
+                walked.extend(core::iter::successors(Some(next), |occurrence| {
+                    occurrence
+                        .get_next_occurrence()
+                        .ok()
+                        .filter(|next| deadline(next) <= now)
+                }));
+                let dropped = walked.len() - MAX_CAUGHT_UP_OCCURRENCES;
+                return (walked.split_off(dropped), dropped as u64);
+            }
+
+            if latest.len() >= MAX_CAUGHT_UP_OCCURRENCES {
+                let kept = latest.split_off(latest.len() - MAX_CAUGHT_UP_OCCURRENCES);
+                let span = deadline(kept.last().unwrap()) - deadline(&kept[0]);
+                let spacing = span.as_ms_f64() / (MAX_CAUGHT_UP_OCCURRENCES - 1) as f64;
+                let jumped = ((deadline(&kept[0]) - first).as_ms_f64() / spacing).round() as u64;
+                return (kept, walked.len() as u64 + jumped);
+            }
+
+            window += window;
+        }
+    }
+
+    /// Pushes a schedule that was just fired back onto the queue at its next occurrence, or dispatches its
+    /// expiry when it has none.
+    ///
+    /// The next occurrence is evaluated from no earlier than just past the fired deadline, so a schedule
+    /// fired right on its deadline is not queued up at that same deadline again.
+    fn push_back(&mut self, fired: StSchedule) -> bool {
+        let past_deadline = Timestamp::Millis(fired.get_deadline_millis()) + Timestamp::Millis(1);
+        let clock = FixedClock::new(self.clock.now().max(past_deadline));
+
+        match fired.get_upcoming_schedule_with(&clock) {
+            Ok(upcoming) => {
+                self.enqueue(upcoming);
+                true
+            }
+            // The frequency is past its `until` or `count`, or the schedule does not repeat at all
+            Err(
+                TimingError::FrequencyExpired
+                | TimingError::CountExhausted
+                | TimingError::MissingExpression,
+            ) => {
+                console_log!("Schedule with ID '{}' expired", fired.get_id_as_str());
+                let event = ExpiredEvent::new(&fired, self.clock.now());
+                self.dispatch(&SchedulerEvent::Expired(event));
+                false
+            }
+            Err(err) => {
+                console_log!(
+                    "Schedule with ID '{}' not pushed back: {}",
+                    fired.get_id_as_str(),
+                    err
+                );
+                false
+            }
+        }
+    }
+
+    /// Returns the ID and deadline of the schedule at the head of the queue, that is the
+    /// schedule that is due next.
+    fn head(&self) -> Option<(String, Timestamp)> {
+        self.pq
+            .peek()
+            .map(|s| (s.get_id(), Timestamp::Millis(s.get_deadline_millis())))
+    }
+
+    /// Returns when the scheduler is next due to act, that is the earliest of the deadline at the head of the
+    /// queue, the next trigger and the next resumption of a paused schedule.
+    fn next_wake(&self) -> Option<Timestamp> {
+        self.head()
+            .map(|(_, deadline)| deadline)
+            .into_iter()
+            .chain(self.triggers.peek().map(|t| t.at))
+            .chain(self.next_resume())
+            .min()
+    }
+}
+
+impl Default for StScheduler {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -147,17 +1011,32 @@ impl StSchedulerRunner {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
         let (tx, rx) = channel::unbounded::<XMessage>();
+        // A single pending wake-up is enough to re-poll, so further ones are coalesced
+        let (wake_sender, wake_receiver) = channel::bounded::<()>(1);
 
         StSchedulerRunner {
             sender: tx,
             receiver: rx,
+            state: Rc::new(Cell::new(StRunnerState::Idle)),
+            wake_sender,
+            wake_receiver,
         }
     }
 
-    async fn idle(until: Option<Timestamp>) {
-        let sleep_ts = until.unwrap_or_else(|| Timestamp::Millis(1000));
-        console_log!("Idling for {}", sleep_ts);
-        task::sleep(sleep_ts.to_core_duration()).await
+    /// Idles until the given time elapses or until woken, whichever comes first.
+    /// When no time is given, it idles until woken.
+    async fn idle(until: Option<Timestamp>, wake_receiver: &channel::Receiver<()>) {
+        match until {
+            Some(until) => {
+                let sleep_ts = until.min(MAX_IDLE);
+                console_log!("Idling for {}", sleep_ts);
+                let _ = future::timeout(sleep_ts.to_core_duration(), wake_receiver.recv()).await;
+            }
+            None => {
+                console_log!("Idling until woken");
+                let _ = wake_receiver.recv().await;
+            }
+        }
     }
 
     #[inline]
@@ -170,90 +1049,341 @@ impl StSchedulerRunner {
         self.sender.clone()
     }
 
-    pub async fn update_scheduler_with(&self, schedule: StSchedule) -> bool {
+    /// Adds a schedule that does not exist yet to the scheduler, resolving with `Inserted` once it is queued
+    pub async fn add_schedule(&self, schedule: StSchedule) -> StCommandOutcome {
+        console_log!("Adding schedule with ID '{}':", schedule.get_id_as_str());
+        self.request(|reply| XMessage::Add(schedule, reply))
+            .await
+            .unwrap_or(StCommandOutcome::Unavailable)
+    }
+
+    /// Replaces the schedule with the same ID as the given one when there is one, otherwise adds it,
+    /// resolving with `Replaced` or `Inserted` respectively
+    pub async fn upsert_schedule(&self, schedule: StSchedule) -> StCommandOutcome {
+        console_log!("Upserting schedule with ID '{}':", schedule.get_id_as_str());
+        self.request(|reply| XMessage::Upsert(schedule, reply))
+            .await
+            .unwrap_or(StCommandOutcome::Unavailable)
+    }
+
+    /// Replaces the schedule with the same ID as the given one, resolving with `NotFound` when there is none
+    pub async fn update_scheduler_with(&self, schedule: StSchedule) -> StCommandOutcome {
         console_log!("Updating schedule with ID '{}':", schedule.get_id_as_str());
-        let tx = self.get_sender();
-        match tx.send(XMessage::Update(schedule)).await {
-            Ok(_) => true,
-            Err(error) => {
-                console_error!("Failed to update scheduler with new schedule: {}", error);
-                false
-            }
-        }
+        self.request(|reply| XMessage::Update(schedule, reply))
+            .await
+            .unwrap_or(StCommandOutcome::Unavailable)
     }
 
-    pub async fn remove_from_scheduler(&self, schedule_id: String) -> bool {
+    pub async fn remove_from_scheduler(&self, schedule_id: String) -> StCommandOutcome {
         console_log!("Removing schedule with ID '{schedule_id}' from scheduler:");
-        let tx = self.get_sender();
-        match tx.send(XMessage::Remove(schedule_id)).await {
-            Ok(_) => true,
-            Err(error) => {
-                console_error!("Failed to remove schedule from scheduler: {}", error);
-                false
-            }
-        }
+        self.request(|reply| XMessage::Remove(schedule_id, reply))
+            .await
+            .unwrap_or(StCommandOutcome::Unavailable)
+    }
+
+    /// Removes every schedule from the scheduler, resolving with how many there were
+    pub async fn drop_all_schedules(&self) -> u32 {
+        console_log!("Dropping all schedules from scheduler:");
+        self.request(XMessage::DropAll).await.unwrap_or(0)
     }
 
-    async fn stop(&self) -> bool {
-        console_log!("Suspending scheduler:");
+    /// Pauses the schedule with the given ID, optionally until the given timestamp in milliseconds
+    pub async fn pause_schedule(
+        &self,
+        schedule_id: String,
+        until: Option<u64>,
+    ) -> StCommandOutcome {
+        console_log!("Pausing schedule with ID '{schedule_id}':");
+        let until = until.map(|ms| Timestamp::Millis(ms as i64));
+        self.request(|reply| XMessage::Pause(schedule_id, until, reply))
+            .await
+            .unwrap_or(StCommandOutcome::Unavailable)
+    }
+
+    /// Resumes the paused schedule with the given ID, handling the occurrences that fell inside the pause
+    /// with the given policy, otherwise they are skipped and reported as missed
+    pub async fn resume_schedule(
+        &self,
+        schedule_id: String,
+        policy: Option<StCatchUpPolicy>,
+    ) -> StCommandOutcome {
+        console_log!("Resuming schedule with ID '{schedule_id}':");
+        self.request(|reply| XMessage::Resume(schedule_id, policy, reply))
+            .await
+            .unwrap_or(StCommandOutcome::Unavailable)
+    }
+
+    /// Lists the schedules held by the scheduler in the order they are next due, resolving with an array of
+    /// `StScheduleEntry` objects
+    pub async fn list_schedules(&self) -> js_sys::Array {
+        self.schedules().await.iter().map(JsValue::from).collect()
+    }
+
+    /// Snoozes the occurrence of the schedule with the given ID that was fired last, re-firing it after the
+    /// given number of milliseconds
+    pub async fn snooze_schedule_for(&self, schedule_id: String, millis: u32) -> StCommandOutcome {
+        console_log!("Snoozing schedule with ID '{schedule_id}':");
+        let duration = Timestamp::Millis(millis as i64);
+        self.request(|reply| XMessage::SnoozeFor(schedule_id, duration, reply))
+            .await
+            .unwrap_or(StCommandOutcome::Unavailable)
+    }
+
+    /// Snoozes the occurrence of the schedule with the given ID that was fired last, re-firing it at the
+    /// given timestamp in milliseconds
+    pub async fn snooze_schedule_until(&self, schedule_id: String, until: u64) -> StCommandOutcome {
+        console_log!("Snoozing schedule with ID '{schedule_id}':");
+        let until = Timestamp::Millis(until as i64);
+        self.request(|reply| XMessage::SnoozeUntil(schedule_id, until, reply))
+            .await
+            .unwrap_or(StCommandOutcome::Unavailable)
+    }
+
+    /// Acknowledges the fired occurrences of the schedule with the given ID, so they do not become overdue
+    pub async fn acknowledge_schedule(&self, schedule_id: String) -> StCommandOutcome {
+        console_log!("Acknowledging schedule with ID '{schedule_id}':");
+        self.request(|reply| XMessage::Acknowledge(schedule_id, reply))
+            .await
+            .unwrap_or(StCommandOutcome::Unavailable)
+    }
+
+    /// Gets the given number of occurrences next due across the schedules that are not paused, earliest first,
+    /// resolving with an array of `StUpcomingEvent` objects
+    pub async fn get_upcoming(&self, count: u32) -> js_sys::Array {
+        self.upcoming(count as usize)
+            .await
+            .iter()
+            .map(JsValue::from)
+            .collect()
+    }
+
+    /// Gets when the schedule with the given ID next fires, in milliseconds, resolving with `undefined` when
+    /// there is no such schedule or it is paused
+    pub async fn get_next_fire_time(&self, schedule_id: String) -> Option<f64> {
+        self.next_fire_time(schedule_id)
+            .await
+            .map(|ts| ts.as_ms_f64())
+    }
+
+    /// Gets the number of schedules queued, which leaves out the paused ones
+    pub async fn queue_size(&self) -> u32 {
+        self.request(XMessage::QueueSize).await.unwrap_or(0) as u32
+    }
+
+    /// Takes a snapshot of the scheduler, see [`StScheduler::snapshot`], resolving with `undefined` when the
+    /// runner stopped before it could be taken
+    pub async fn snapshot(&self) -> Option<StSchedulerSnapshot> {
+        self.request(XMessage::Snapshot).await
+    }
+
+    /// Sends the command made with the given reply channel to the scheduler, resolving with its result once
+    /// it is carried out, or with `None` when the runner stopped before then
+    async fn request<T, F>(&self, command: F) -> Option<T>
+    where
+        F: FnOnce(Reply<T>) -> XMessage,
+    {
+        let (reply, result) = channel::bounded::<T>(1);
         let tx = self.get_sender();
-        match tx.send(XMessage::Suspend).await {
-            Ok(_) => true,
-            Err(error) => {
-                console_error!("Failed to suspend scheduler: {}", error);
-                false
-            }
+
+        if let Err(error) = tx.send(command(reply)).await {
+            console_error!("Failed to send command to scheduler: {}", error);
+            return None;
         }
+
+        result.recv().await.ok()
     }
 
-    pub async fn quit(self) {
-        console_log!("Quitting the runner:");
-        let stopped = self.stop().await;
-        drop(self.sender);
-        if stopped == false {
-            panic!("Graceful suspension of scheduler failed")
-        }
-        console_log!("Runner quit successfully");
+    /// Gets the current state of the runner
+    pub fn get_state(&self) -> StRunnerState {
+        self.state.get()
     }
 
-    /// The
-    pub async fn run(&self, mut scheduler: StScheduler) {
-        if scheduler.is_suspended() == false {
-            panic!("Cannot run a scheduler more than once");
-        } else {
-            scheduler.start();
+    /// Suspends the running scheduler, which still carries out commands but fires nothing until resumed.
+    ///
+    /// Throws when the runner is not running.
+    pub fn suspend(&self) -> Result<(), RunnerError> {
+        self.transition(StRunnerState::Suspended, &[StRunnerState::Running])?;
+        console_log!("Scheduler suspended!");
+        Ok(())
+    }
+
+    /// Resumes the suspended scheduler, handling the occurrences that fell inside the suspension with the
+    /// catch-up policies like any other missed occurrences.
+    ///
+    /// Throws when the runner is not suspended.
+    pub fn resume(&self) -> Result<(), RunnerError> {
+        self.transition(StRunnerState::Running, &[StRunnerState::Suspended])?;
+        console_log!("Scheduler running ✅");
+        Ok(())
+    }
+
+    /// Stops the runner for good, after which commands resolve with `Unavailable`.
+    ///
+    /// The commands sent before stopping are still carried out. Stopping is terminal: a stopped runner cannot
+    /// be run, resumed or stopped again, which throws, so a new runner is needed to run a scheduler again.
+    pub fn stop(&self) -> Result<(), RunnerError> {
+        let from = [
+            StRunnerState::Idle,
+            StRunnerState::Running,
+            StRunnerState::Suspended,
+        ];
+        self.transition(StRunnerState::Stopped, &from)?;
+
+        // The command loop ends once it has received the messages already sent
+        self.sender.close();
+        console_log!("Runner stopped");
+        Ok(())
+    }
+
+    /// Moves the runner to the given state when it is in one of the given states it can be moved from, then
+    /// wakes the poll loop to act on it
+    fn transition(&self, to: StRunnerState, from: &[StRunnerState]) -> Result<(), RunnerError> {
+        let current = self.state.get();
+
+        if !from.contains(&current) {
+            return Err(RunnerError::InvalidTransition(current, to));
         }
 
-        let tx = self.get_sender();
+        self.state.set(to);
+        // A full channel means a wake-up is already pending
+        let _ = self.wake_sender.try_send(());
+        Ok(())
+    }
+
+    /// Runs the scheduler, spawning its command and poll loops onto the current thread.
+    ///
+    /// In the browser, the loops run on the event loop. With the `native` feature, they run on the
+    /// thread-local executor of `async-std`, so the calling thread has to keep driving it, e.g. by
+    /// blocking on a future with `async_std::task::block_on` for as long as the runner should live.
+    ///
+    /// Throws when the runner is not idle, since a runner only ever runs one scheduler.
+    pub async fn run(&self, scheduler: StScheduler) -> Result<(), RunnerError> {
+        self.transition(StRunnerState::Running, &[StRunnerState::Idle])?;
+        console_log!("Scheduler running ✅");
+
         let rx = self.get_receiver();
-        let scheduler = Arc::new(Mutex::new(scheduler));
+        let wake_sender = self.wake_sender.clone();
+        let wake_receiver = self.wake_receiver.clone();
+        let scheduler = Rc::new(Mutex::new(scheduler));
         let scheduler_clone = scheduler.clone();
 
         spawn_local(async move {
             while let Ok(msg) = rx.recv().await {
                 console_log!("Received {:#?} message", msg);
-                match msg {
-                    XMessage::Suspend => {
-                        let mut scheduler_lock = scheduler_clone.lock().await;
-                        console_log!("Lock acquired on scheduler");
-                        scheduler_lock.suspend();
+                let mut scheduler_lock = scheduler_clone.lock().await;
+                console_log!("Lock acquired on scheduler");
+                let wake_at = scheduler_lock.next_wake();
+
+                // A caller that stopped waiting for the result of its command is no concern of the scheduler's
+                let wake = match msg {
+                    // A schedule or its reminders can be due before the scheduler was next due to wake up
+                    XMessage::Add(schedule, reply) => {
+                        let _ = reply.try_send(scheduler_lock.insert_schedule(schedule));
+                        scheduler_lock.next_wake() != wake_at
+                    }
+
+                    XMessage::Upsert(schedule, reply) => {
+                        let _ = reply.try_send(scheduler_lock.upsert_schedule(schedule));
+                        scheduler_lock.next_wake() != wake_at
+                    }
+
+                    XMessage::Remove(id, reply) => {
+                        let outcome = match scheduler_lock.remove_schedule(id) {
+                            Some(_) => StCommandOutcome::Removed,
+                            None => StCommandOutcome::NotFound,
+                        };
+                        let _ = reply.try_send(outcome);
+                        scheduler_lock.next_wake() != wake_at
+                    }
+
+                    XMessage::Update(schedule, reply) => {
+                        let _ = reply.try_send(scheduler_lock.update_schedule(schedule));
+                        scheduler_lock.next_wake() != wake_at
+                    }
+
+                    // Dropping the snoozes and reminders too can make the next wake-up later
+                    XMessage::DropAll(reply) => {
+                        let _ = reply.try_send(scheduler_lock.drop_all_schedules());
+                        true
+                    }
+
+                    // Either can change when the scheduler is next due to resume a schedule
+                    XMessage::Pause(id, until, reply) => {
+                        let paused = scheduler_lock.pause_schedule(id, until);
+                        let _ = reply.try_send(match paused {
+                            true => StCommandOutcome::Paused,
+                            false => StCommandOutcome::NotFound,
+                        });
+                        paused
+                    }
+                    XMessage::Resume(id, policy, reply) => {
+                        let resumed = scheduler_lock.resume_schedule(id, policy);
+                        let _ = reply.try_send(match resumed {
+                            true => StCommandOutcome::Resumed,
+                            false => StCommandOutcome::NotFound,
+                        });
+                        resumed
                     }
 
-                    XMessage::Remove(id) => {
-                        let mut scheduler_lock = scheduler_clone.lock().await;
-                        console_log!("Lock acquired on scheduler");
-                        scheduler_lock.remove_schedule(id);
+                    // Queries leave the scheduler as it is
+                    XMessage::List(reply) => {
+                        let _ = reply.try_send(scheduler_lock.list_schedules());
+                        false
+                    }
+                    XMessage::Upcoming(count, reply) => {
+                        let _ = reply.try_send(scheduler_lock.upcoming(count));
+                        false
+                    }
+                    XMessage::NextFireTime(id, reply) => {
+                        let _ = reply.try_send(scheduler_lock.next_fire_time(&id));
+                        false
+                    }
+                    XMessage::QueueSize(reply) => {
+                        let _ = reply.try_send(scheduler_lock.queue_size());
+                        false
+                    }
+                    XMessage::Snapshot(reply) => {
+                        let _ = reply.try_send(scheduler_lock.snapshot());
+                        false
                     }
 
-                    XMessage::Update(schedule) => {
-                        let mut scheduler_lock = scheduler_clone.lock().await;
-                        console_log!("Lock acquired on scheduler");
-                        scheduler_lock.update_schedule(schedule);
+                    // A snooze is a trigger that could be due before the head of the queue
+                    XMessage::SnoozeFor(id, duration, reply) => {
+                        let snoozed = scheduler_lock.snooze_schedule_for(id, duration);
+                        let _ = reply.try_send(match snoozed {
+                            true => StCommandOutcome::Snoozed,
+                            false => StCommandOutcome::NotFound,
+                        });
+                        snoozed
                     }
+                    XMessage::SnoozeUntil(id, until, reply) => {
+                        let snoozed = scheduler_lock.snooze_schedule(id, until);
+                        let _ = reply.try_send(match snoozed {
+                            true => StCommandOutcome::Snoozed,
+                            false => StCommandOutcome::NotFound,
+                        });
+                        snoozed
+                    }
+
+                    // Acknowledging cancels triggers, which can only make the next wake-up later
+                    XMessage::Acknowledge(id, reply) => {
+                        let _ = reply.try_send(match scheduler_lock.acknowledge_schedule(&id) {
+                            true => StCommandOutcome::Acknowledged,
+                            false => StCommandOutcome::NotFound,
+                        });
+                        false
+                    }
+                };
+
+                if wake {
+                    // A full channel means a wake-up is already pending
+                    let _ = wake_sender.try_send(());
                 }
             }
         });
 
+        let state = self.state.clone();
+
         spawn_local(async move {
             console_log!("Poll started:");
             loop {
@@ -265,28 +1395,930 @@ impl StSchedulerRunner {
                 // define a short-lived scope for it by wrapping it in a "small" block, and using and dumping it
                 // as quickly as possible.
                 // ***********************************************************************************************
-                let result = {
+                let pending = {
                     let mut scheduler = scheduler.lock().await;
-                    if scheduler.is_suspended() {
-                        drop(tx);
-                        console_log!("Poll ended!");
-                        break;
-                    }
-                    scheduler.poll()
-                };
 
-                match result {
-                    Poll::Empty => Self::idle(None).await,
-                    Poll::Pending(_) => Self::idle(None).await,
-                    Poll::Ready(result) => {
-                        let mut scheduler = scheduler.lock().await;
-                        scheduler.subscriber.as_ref().map(|r| {
-                            r.call1(&JsValue::NULL, &JsValue::from_str(result.get_id_as_str()))
-                        });
-                        scheduler.add_schedule(result); // Push back
+                    match state.get() {
+                        StRunnerState::Stopped => {
+                            console_log!("Poll ended!");
+                            break;
+                        }
+                        // Nothing is fired until resumed, which wakes the poll loop
+                        StRunnerState::Suspended => None,
+                        _ => match scheduler.poll() {
+                            Poll::Empty => None,
+                            Poll::Pending(difference) => Some(difference),
+                            // Fired under the same lock it is polled with, so no command finds the schedule off
+                            // the queue in between
+                            Poll::Ready(due) => {
+                                scheduler.fire(due);
+                                continue;
+                            }
+                        },
                     }
                 };
+
+                Self::idle(pending, &wake_receiver).await;
             }
         });
+
+        Ok(())
+    }
+}
+
+impl StSchedulerRunner {
+    /// Lists the schedules held by the scheduler in the order they are next due
+    pub async fn schedules(&self) -> Vec<ScheduleEntry> {
+        self.request(XMessage::List).await.unwrap_or_default()
+    }
+
+    /// Gets the given number of occurrences next due across the schedules that are not paused, earliest first
+    pub async fn upcoming(&self, count: usize) -> Vec<UpcomingEvent> {
+        self.request(|reply| XMessage::Upcoming(count, reply))
+            .await
+            .unwrap_or_default()
+    }
+
+    /// Gets when the schedule with the given ID next fires, or `None` when there is no such schedule or it is
+    /// paused
+    pub async fn next_fire_time(&self, schedule_id: String) -> Option<Timestamp> {
+        self.request(|reply| XMessage::NextFireTime(schedule_id, reply))
+            .await
+            .flatten()
+    }
+}
+
+impl Default for StSchedulerRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    use crate::core::frequency::{
+        StCustomFrequency, StFrequencyType, StHourlyExpression, StRegularFrequency,
+    };
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    const ANCHOR_MILLIS: i64 = 1730145955025; // 2024-10-28T21:05:55.025 Africa/Lagos
+
+    fn hourly_schedule(clock: &ManualClock, policy: Option<StCatchUpPolicy>) -> StSchedule {
+        let expr = StHourlyExpression::new(1);
+        let freq = StRegularFrequency::new(StFrequencyType::Hour, expr, None);
+        let mut schedule = StSchedule::with_regular_and_clock(
+            "id",
+            "2024-10-28T21:05:55.025",
+            "Africa/Lagos",
+            freq,
+            None,
+            clock,
+        );
+        schedule.set_catch_up_policy(policy);
+        schedule
+    }
+
+    fn hours_after_anchor(hours: f64) -> Timestamp {
+        Timestamp::Millis(ANCHOR_MILLIS) + Timestamp::from_hours(hours)
+    }
+
+    /// Queues an hourly schedule, sleeps through three and a half hours of it, then fires what is due
+    fn sleep_through_schedule(
+        configure: impl FnOnce(&mut StScheduler),
+        policy: Option<StCatchUpPolicy>,
+    ) -> (StScheduler, Vec<SchedulerEvent>) {
+        let clock = ManualClock::new(ANCHOR_MILLIS);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+        configure(&mut scheduler);
+        let events = scheduler.events();
+
+        scheduler.add_schedule(hourly_schedule(&clock, policy));
+        clock.advance_by(Timestamp::from_hours(3.5));
+
+        match scheduler.poll() {
+            Poll::Ready(due) => scheduler.fire(due),
+            _ => panic!("Expected the schedule to be due"),
+        }
+
+        let events = core::iter::from_fn(|| events.try_recv().ok()).collect();
+        (scheduler, events)
+    }
+
+    fn fired(events: &[SchedulerEvent]) -> Vec<(Timestamp, Option<u64>, Vec<Timestamp>)> {
+        events
+            .iter()
+            .filter_map(|e| match e {
+                SchedulerEvent::Due(due) => {
+                    Some((due.planned_at, due.occurrence, due.coalesced.clone()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn missed(events: &[SchedulerEvent]) -> Vec<Timestamp> {
+        events
+            .iter()
+            .filter_map(|e| match e {
+                SchedulerEvent::Missed(missed) => Some(missed.missed.clone()),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_catch_up_fire_all() {
+        let (scheduler, events) =
+            sleep_through_schedule(|s| s.set_catch_up_policy(StCatchUpPolicy::FireAll), None);
+
+        let expected = (0..4)
+            .map(|i| (hours_after_anchor(i as f64), Some(i), vec![]))
+            .collect::<Vec<_>>();
+        assert_eq!(fired(&events), expected);
+        assert!(missed(&events).is_empty());
+        assert_eq!(scheduler.head().unwrap().1, hours_after_anchor(4.0));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_catch_up_fire_latest() {
+        let (scheduler, events) = sleep_through_schedule(|_| {}, None);
+
+        assert_eq!(
+            fired(&events),
+            vec![(hours_after_anchor(3.0), Some(3), vec![])]
+        );
+        assert_eq!(
+            missed(&events),
+            vec![
+                hours_after_anchor(0.0),
+                hours_after_anchor(1.0),
+                hours_after_anchor(2.0)
+            ]
+        );
+        assert_eq!(scheduler.head().unwrap().1, hours_after_anchor(4.0));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_catch_up_skip_all() {
+        let (scheduler, events) =
+            sleep_through_schedule(|s| s.set_catch_up_policy(StCatchUpPolicy::SkipAll), None);
+
+        assert!(fired(&events).is_empty());
+        assert_eq!(missed(&events).len(), 4);
+        assert_eq!(scheduler.head().unwrap().1, hours_after_anchor(4.0));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_catch_up_coalesce() {
+        let (_, events) =
+            sleep_through_schedule(|s| s.set_catch_up_policy(StCatchUpPolicy::Coalesce), None);
+
+        let coalesced = vec![
+            hours_after_anchor(0.0),
+            hours_after_anchor(1.0),
+            hours_after_anchor(2.0),
+        ];
+        assert!(missed(&events).is_empty());
+        assert_eq!(
+            fired(&events),
+            vec![(hours_after_anchor(3.0), Some(3), coalesced)]
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_catch_up_is_capped() {
+        let clock = ManualClock::new(ANCHOR_MILLIS);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+        scheduler.set_catch_up_policy(StCatchUpPolicy::FireAll);
+        let events = scheduler.events();
+
+        scheduler.add_schedule(hourly_schedule(&clock, None));
+        clock.advance_by(Timestamp::from_hours(149.5));
+
+        match scheduler.poll() {
+            Poll::Ready(due) => scheduler.fire(due),
+            _ => panic!("Expected the schedule to be due"),
+        }
+
+        // Only the latest occurrences are fired, and the ones before them are counted as missed
+        let events = core::iter::from_fn(|| events.try_recv().ok()).collect::<Vec<_>>();
+        let expected = (50..150)
+            .map(|i| (hours_after_anchor(i as f64), Some(i), vec![]))
+            .collect::<Vec<_>>();
+        assert_eq!(fired(&events), expected);
+        assert!(missed(&events).is_empty());
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, SchedulerEvent::Missed(missed) if missed.dropped == 50)));
+        assert_eq!(scheduler.head().unwrap().1, hours_after_anchor(150.0));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_catch_up_jumps_to_latest_occurrences() {
+        let anchor = 1730145960000; // 2024-10-28T21:06:00.000 Africa/Lagos
+        let clock = ManualClock::new(anchor);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+        scheduler.set_catch_up_policy(StCatchUpPolicy::FireAll);
+        let events = scheduler.events();
+
+        let freq = StCustomFrequency::new(vec![String::from("* * * * *")], None);
+        let schedule = StSchedule::with_custom_and_clock(
+            "id",
+            "2024-10-28T21:06:00.000",
+            "Africa/Lagos",
+            freq,
+            None,
+            &clock,
+        );
+        scheduler.add_schedule(schedule);
+        clock.advance_by(Timestamp::from_weeks(1.0) + Timestamp::Millis(30_000));
+
+        match scheduler.poll() {
+            Poll::Ready(due) => scheduler.fire(due),
+            _ => panic!("Expected the schedule to be due"),
+        }
+
+        // The week of occurrences in between is jumped over, and only estimated from the latest ones
+        let events = core::iter::from_fn(|| events.try_recv().ok()).collect::<Vec<_>>();
+        let minutes = 7 * 24 * 60;
+        let expected = (minutes - 99..=minutes)
+            .map(|i| (Timestamp::Millis(anchor + i * 60_000), None, vec![]))
+            .collect::<Vec<_>>();
+        assert_eq!(fired(&events), expected);
+        assert!(events.iter().any(
+            |e| matches!(e, SchedulerEvent::Missed(missed) if missed.dropped == minutes as u64 - 99)
+        ));
+        assert_eq!(
+            scheduler.head().unwrap().1,
+            Timestamp::Millis(anchor + (minutes + 1) * 60_000)
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_fire_long_running_schedule() {
+        let anchor = 1730145960000; // 2024-10-28T21:06:00.000 Africa/Lagos
+
+        // Fires a schedule that has been running every minute for the given minutes when it is added
+        let fire_after = |minutes: i64| {
+            let clock = ManualClock::new(anchor + minutes * 60_000);
+            let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+            let events = scheduler.events();
+
+            let freq = StCustomFrequency::new(vec![String::from("* * * * *")], None);
+            let schedule = StSchedule::with_custom_and_clock(
+                "id",
+                "2024-10-28T21:06:00.000",
+                "Africa/Lagos",
+                freq,
+                None,
+                &clock,
+            );
+            scheduler.add_schedule(schedule);
+
+            for _ in 0..200 {
+                let (_, deadline) = scheduler.head().unwrap();
+                clock.set(deadline.as_ms());
+                match scheduler.poll() {
+                    Poll::Ready(due) => scheduler.fire(due),
+                    _ => panic!("Expected the schedule to be due"),
+                }
+            }
+
+            let events = core::iter::from_fn(|| events.try_recv().ok()).collect::<Vec<_>>();
+            fired(&events)
+        };
+
+        // Each occurrence is counted on from the one before it, rather than from the anchor
+        let expected = (31..231)
+            .map(|i| {
+                (
+                    Timestamp::Millis(anchor + i * 60_000),
+                    Some(i as u64),
+                    vec![],
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(fire_after(30), expected);
+
+        // The index of a schedule added a day after its anchor is not tracked, nor evaluated from the anchor
+        let expected = (1441..1641)
+            .map(|i| (Timestamp::Millis(anchor + i * 60_000), None, vec![]))
+            .collect::<Vec<_>>();
+        assert_eq!(fire_after(1440), expected);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_catch_up_schedule_policy_and_tolerance() {
+        let (_, events) = sleep_through_schedule(
+            |s| {
+                s.set_catch_up_policy(StCatchUpPolicy::FireAll);
+                s.set_late_tolerance(3_600_000);
+            },
+            Some(StCatchUpPolicy::SkipAll),
+        );
+
+        // The last occurrence is within the tolerance, so it is not missed
+        assert_eq!(
+            fired(&events),
+            vec![(hours_after_anchor(3.0), Some(3), vec![])]
+        );
+        assert_eq!(
+            missed(&events),
+            vec![
+                hours_after_anchor(0.0),
+                hours_after_anchor(1.0),
+                hours_after_anchor(2.0)
+            ]
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_pause_and_resume_schedule() {
+        let clock = ManualClock::new(ANCHOR_MILLIS);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+        let events = scheduler.events();
+
+        scheduler.add_schedule(hourly_schedule(&clock, None));
+        assert!(scheduler.pause_schedule("id".to_string(), None));
+        assert!(scheduler.is_paused("id"));
+        assert!(matches!(scheduler.poll(), Poll::Empty));
+
+        clock.advance_by(Timestamp::from_hours(3.5));
+        assert!(scheduler.resume_schedule("id".to_string(), None));
+        assert!(!scheduler.resume_schedule("id".to_string(), None));
+
+        // The occurrences inside the pause are skipped by default
+        let events = core::iter::from_fn(|| events.try_recv().ok()).collect::<Vec<_>>();
+        assert!(fired(&events).is_empty());
+        assert_eq!(missed(&events).len(), 4);
+        assert_eq!(scheduler.head().unwrap().1, hours_after_anchor(4.0));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_resume_schedule_with_policy() {
+        let clock = ManualClock::new(ANCHOR_MILLIS);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+        let events = scheduler.events();
+
+        scheduler.add_schedule(hourly_schedule(&clock, None));
+        scheduler.pause_schedule("id".to_string(), None);
+        clock.advance_by(Timestamp::from_hours(1.5));
+        scheduler.resume_schedule("id".to_string(), Some(StCatchUpPolicy::FireLatest));
+
+        let events = core::iter::from_fn(|| events.try_recv().ok()).collect::<Vec<_>>();
+        assert_eq!(
+            fired(&events),
+            vec![(hours_after_anchor(1.0), Some(1), vec![])]
+        );
+        assert_eq!(missed(&events), vec![hours_after_anchor(0.0)]);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_pause_schedule_until() {
+        let clock = ManualClock::new(ANCHOR_MILLIS);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+
+        scheduler.add_schedule(hourly_schedule(&clock, None));
+        scheduler.pause_schedule("id".to_string(), Some(hours_after_anchor(2.5)));
+
+        match scheduler.poll() {
+            Poll::Pending(difference) => assert_eq!(difference, Timestamp::from_hours(2.5)),
+            _ => panic!("Expected the scheduler to wait for the resumption"),
+        }
+
+        clock.set(hours_after_anchor(2.5).as_ms());
+
+        match scheduler.poll() {
+            Poll::Pending(difference) => assert_eq!(difference, Timestamp::from_hours(0.5)),
+            _ => panic!("Expected the schedule to be resumed at its next occurrence"),
+        }
+        assert!(!scheduler.is_paused("id"));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_snooze_schedule() {
+        let clock = ManualClock::new(ANCHOR_MILLIS);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+        let events = scheduler.events();
+
+        scheduler.add_schedule(hourly_schedule(&clock, None));
+        // Nothing was fired yet, so there is nothing to snooze
+        assert!(!scheduler.snooze_schedule_for("id".to_string(), Timestamp::Millis(600_000)));
+
+        match scheduler.poll() {
+            Poll::Ready(due) => scheduler.fire(due),
+            _ => panic!("Expected the schedule to be due"),
+        }
+        assert!(scheduler.snooze_schedule_for("id".to_string(), Timestamp::Millis(600_000)));
+
+        match scheduler.poll() {
+            Poll::Pending(difference) => assert_eq!(difference, Timestamp::Millis(600_000)),
+            _ => panic!("Expected the scheduler to wait for the snoozed occurrence"),
+        }
+
+        clock.advance_by(Timestamp::Millis(600_000));
+
+        // The snoozed occurrence is re-fired while the next occurrence is still on time
+        match scheduler.poll() {
+            Poll::Pending(difference) => assert_eq!(difference, Timestamp::Millis(3_000_000)),
+            _ => panic!("Expected the scheduler to wait for the next occurrence"),
+        }
+
+        let events = core::iter::from_fn(|| events.try_recv().ok()).collect::<Vec<_>>();
+        let snoozed = events
+            .iter()
+            .filter_map(|e| match e {
+                SchedulerEvent::Due(due) => Some((due.planned_at, due.snoozed)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            snoozed,
+            vec![
+                (hours_after_anchor(0.0), false),
+                (hours_after_anchor(0.0), true)
+            ]
+        );
+        assert_eq!(scheduler.head().unwrap().1, hours_after_anchor(1.0));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_schedule_reminders() {
+        let clock = ManualClock::new(hours_after_anchor(-2.0).as_ms());
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+        let events = scheduler.events();
+
+        // An offset longer than the hourly period reminds of every occurrence all the same
+        let mut schedule = hourly_schedule(&clock, None);
+        schedule.set_reminders(vec![600_000, 5_400_000, 600_000]);
+        assert_eq!(schedule.get_reminders(), vec![5_400_000, 600_000]);
+        scheduler.add_schedule(schedule);
+
+        let mut dispatched = vec![];
+        for hours in [-1.5, -0.5, -10.0 / 60.0, 0.0] {
+            clock.set(hours_after_anchor(hours).as_ms());
+            if let Poll::Ready(due) = scheduler.poll() {
+                scheduler.fire(due);
+            }
+
+            dispatched.extend(core::iter::from_fn(|| events.try_recv().ok()).filter_map(
+                |e| match e {
+                    SchedulerEvent::Reminder(r) => Some((r.planned_at, Some(r.offset))),
+                    SchedulerEvent::Due(d) => Some((d.planned_at, None)),
+                    SchedulerEvent::Missed(_) => panic!("Nothing should be missed"),
+                    _ => None,
+                },
+            ));
+        }
+
+        assert_eq!(
+            dispatched,
+            vec![
+                (hours_after_anchor(0.0), Some(Timestamp::Millis(5_400_000))),
+                (hours_after_anchor(1.0), Some(Timestamp::Millis(5_400_000))),
+                (hours_after_anchor(0.0), Some(Timestamp::Millis(600_000))),
+                (hours_after_anchor(0.0), None),
+            ]
+        );
+    }
+
+    /// Returns the kind of each event along with the planned time of the occurrence it is about
+    fn lifecycle(events: &[SchedulerEvent]) -> Vec<(&'static str, Timestamp)> {
+        events
+            .iter()
+            .map(|e| match e {
+                SchedulerEvent::Upcoming(e) => ("upcoming", e.planned_at),
+                SchedulerEvent::Reminder(e) => ("reminder", e.planned_at),
+                SchedulerEvent::Due(e) => ("due", e.planned_at),
+                SchedulerEvent::Missed(e) => ("missed", e.missed[0]),
+                SchedulerEvent::Overdue(e) => ("overdue", e.planned_at),
+                SchedulerEvent::Expired(e) => ("expired", e.last_planned_at),
+            })
+            .collect()
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_overdue_schedule() {
+        let clock = ManualClock::new(ANCHOR_MILLIS);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+        let events = scheduler.events();
+        scheduler.set_grace_period(Some(900_000));
+
+        scheduler.add_schedule(hourly_schedule(&clock, None));
+
+        for minutes in [0, 15, 60, 65] {
+            clock.set((hours_after_anchor(0.0) + Timestamp::Millis(minutes * 60_000)).as_ms());
+            if let Poll::Ready(due) = scheduler.poll() {
+                scheduler.fire(due);
+            }
+
+            // The second occurrence is acknowledged before it becomes overdue
+            if minutes == 65 {
+                assert!(scheduler.acknowledge_schedule("id"));
+                assert!(!scheduler.acknowledge_schedule("id"));
+            }
+        }
+
+        clock.set(hours_after_anchor(1.5).as_ms());
+        scheduler.poll();
+
+        let events = core::iter::from_fn(|| events.try_recv().ok()).collect::<Vec<_>>();
+        assert_eq!(
+            lifecycle(&events),
+            vec![
+                ("upcoming", hours_after_anchor(0.0)),
+                ("due", hours_after_anchor(0.0)),
+                ("upcoming", hours_after_anchor(1.0)),
+                ("overdue", hours_after_anchor(0.0)),
+                ("due", hours_after_anchor(1.0)),
+                ("upcoming", hours_after_anchor(2.0)),
+            ]
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_overdue_from_planned_time() {
+        let clock = ManualClock::new(ANCHOR_MILLIS);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+        let events = scheduler.events();
+        scheduler.set_grace_period(Some(30_000));
+
+        scheduler.add_schedule(hourly_schedule(&clock, None));
+
+        // Fired within the late tolerance but past the grace period, so it is overdue as soon as it is due
+        clock.set((hours_after_anchor(0.0) + Timestamp::Millis(45_000)).as_ms());
+        if let Poll::Ready(due) = scheduler.poll() {
+            scheduler.fire(due);
+        }
+        assert!(!scheduler.acknowledge_schedule("id"));
+
+        // Fired on time, so it is only overdue once its grace period has passed since its planned time
+        clock.set(hours_after_anchor(1.0).as_ms());
+        if let Poll::Ready(due) = scheduler.poll() {
+            scheduler.fire(due);
+        }
+        clock.set((hours_after_anchor(1.0) + Timestamp::Millis(30_000)).as_ms());
+        scheduler.poll();
+
+        let events = core::iter::from_fn(|| events.try_recv().ok()).collect::<Vec<_>>();
+        assert_eq!(
+            lifecycle(&events),
+            vec![
+                ("upcoming", hours_after_anchor(0.0)),
+                ("due", hours_after_anchor(0.0)),
+                ("overdue", hours_after_anchor(0.0)),
+                ("upcoming", hours_after_anchor(1.0)),
+                ("due", hours_after_anchor(1.0)),
+                ("upcoming", hours_after_anchor(2.0)),
+                ("overdue", hours_after_anchor(1.0)),
+            ]
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_expired_schedule() {
+        let clock = ManualClock::new(ANCHOR_MILLIS);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+        let events = scheduler.events();
+
+        let until = hours_after_anchor(1.5).as_ms() as u64;
+        let expr = StHourlyExpression::new(1);
+        let freq = StRegularFrequency::new(StFrequencyType::Hour, expr, Some(until));
+        let schedule = StSchedule::with_regular_and_clock(
+            "id",
+            "2024-10-28T21:05:55.025",
+            "Africa/Lagos",
+            freq,
+            None,
+            &clock,
+        );
+        scheduler.add_schedule(schedule.clone());
+
+        for hours in [0.0, 1.0] {
+            clock.set(hours_after_anchor(hours).as_ms());
+            if let Poll::Ready(due) = scheduler.poll() {
+                scheduler.fire(due);
+            }
+        }
+
+        assert!(matches!(scheduler.poll(), Poll::Empty));
+
+        // A schedule added past its `until` expires right away
+        clock.set(hours_after_anchor(2.0).as_ms());
+        assert!(!scheduler.add_schedule(schedule));
+
+        let events = core::iter::from_fn(|| events.try_recv().ok()).collect::<Vec<_>>();
+        assert_eq!(
+            lifecycle(&events),
+            vec![
+                ("upcoming", hours_after_anchor(0.0)),
+                ("due", hours_after_anchor(0.0)),
+                ("upcoming", hours_after_anchor(1.0)),
+                ("due", hours_after_anchor(1.0)),
+                ("expired", hours_after_anchor(1.0)),
+                ("expired", hours_after_anchor(0.0)),
+            ]
+        );
+        match events.last() {
+            Some(SchedulerEvent::Expired(expired)) => {
+                assert_eq!(expired.until, Some(Timestamp::Millis(until as i64)))
+            }
+            _ => panic!("Expected the schedule to expire"),
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_count_exhausted_schedule() {
+        let clock = ManualClock::new(ANCHOR_MILLIS);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+        let events = scheduler.events();
+
+        let expr = StHourlyExpression::new(1);
+        let mut freq = StRegularFrequency::new(StFrequencyType::Hour, expr, None);
+        freq.set_count(Some(2));
+        let schedule = StSchedule::with_regular_and_clock(
+            "id",
+            "2024-10-28T21:05:55.025",
+            "Africa/Lagos",
+            freq,
+            None,
+            &clock,
+        );
+        scheduler.add_schedule(schedule);
+
+        for hours in [0.0, 1.0] {
+            clock.set(hours_after_anchor(hours).as_ms());
+            if let Poll::Ready(due) = scheduler.poll() {
+                scheduler.fire(due);
+            }
+        }
+
+        assert!(matches!(scheduler.poll(), Poll::Empty));
+
+        let events = core::iter::from_fn(|| events.try_recv().ok()).collect::<Vec<_>>();
+        assert_eq!(
+            lifecycle(&events),
+            vec![
+                ("upcoming", hours_after_anchor(0.0)),
+                ("due", hours_after_anchor(0.0)),
+                ("upcoming", hours_after_anchor(1.0)),
+                ("due", hours_after_anchor(1.0)),
+                ("expired", hours_after_anchor(1.0)),
+            ]
+        );
+        match events.last() {
+            Some(SchedulerEvent::Expired(expired)) => assert_eq!(expired.count, Some(2)),
+            _ => panic!("Expected the schedule to expire"),
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_excluded_and_included_occurrences() {
+        let clock = ManualClock::new(ANCHOR_MILLIS);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+        let events = scheduler.events();
+
+        let mut schedule = hourly_schedule(&clock, None);
+        schedule.add_exclusion_with(hours_after_anchor(1.0), &clock);
+        schedule.add_inclusion_with(hours_after_anchor(1.5), &clock);
+        scheduler.add_schedule(schedule.clone());
+
+        for hours in [0.0, 1.0, 1.5, 2.0] {
+            clock.set(hours_after_anchor(hours).as_ms());
+            if let Poll::Ready(due) = scheduler.poll() {
+                scheduler.fire(due);
+            }
+        }
+
+        let planned = fired(&core::iter::from_fn(|| events.try_recv().ok()).collect::<Vec<_>>())
+            .into_iter()
+            .map(|(planned_at, _, _)| planned_at)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            planned,
+            vec![
+                hours_after_anchor(0.0),
+                hours_after_anchor(1.5),
+                hours_after_anchor(2.0)
+            ]
+        );
+
+        // Skipping the next occurrence of a queued schedule
+        clock.set(hours_after_anchor(2.5).as_ms());
+        schedule.add_exclusion_with(hours_after_anchor(3.0), &clock);
+        assert_eq!(
+            scheduler.update_schedule(schedule),
+            StCommandOutcome::Replaced
+        );
+        assert_eq!(
+            scheduler.next_fire_time("id"),
+            Some(hours_after_anchor(4.0))
+        );
+
+        // A schedule without any occurrence left is not queued
+        let mut one_off = StSchedule::try_with_frequency(
+            "one-off",
+            "2024-10-29T09:00:00.000",
+            "Africa/Lagos",
+            None,
+            None,
+            &clock,
+        )
+        .unwrap();
+        one_off.add_exclusion_with(Timestamp::Millis(one_off.get_anchor_millis()), &clock);
+        assert_eq!(
+            scheduler.insert_schedule(one_off),
+            StCommandOutcome::Invalid
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_upsert_and_drop_all_schedules() {
+        let clock = ManualClock::new(ANCHOR_MILLIS);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+
+        let mut schedule = hourly_schedule(&clock, None);
+        schedule.set_reminders(vec![60_000]);
+        assert_eq!(
+            scheduler.upsert_schedule(schedule.clone()),
+            StCommandOutcome::Inserted
+        );
+        assert_eq!(
+            scheduler.insert_schedule(schedule.clone()),
+            StCommandOutcome::Duplicate
+        );
+        assert!(!scheduler.add_schedule(schedule));
+
+        // A replacement that already expired leaves the scheduler without the schedule
+        let until = Some((ANCHOR_MILLIS - 1) as u64);
+        let expr = StHourlyExpression::new(1);
+        let freq = StRegularFrequency::new(StFrequencyType::Hour, expr, until);
+        let created_at = ManualClock::new(ANCHOR_MILLIS - 3_600_000);
+        let expired = StSchedule::with_regular_and_clock(
+            "id",
+            "2024-10-28T20:05:55.025",
+            "Africa/Lagos",
+            freq,
+            None,
+            &created_at,
+        );
+        assert_eq!(
+            scheduler.upsert_schedule(expired.clone()),
+            StCommandOutcome::Invalid
+        );
+        assert!(scheduler.list_schedules().is_empty());
+        assert_eq!(
+            scheduler.update_schedule(expired),
+            StCommandOutcome::NotFound
+        );
+
+        scheduler.add_schedule(hourly_schedule(&clock, None));
+        scheduler.pause_schedule("id".to_string(), None);
+        assert_eq!(scheduler.drop_all_schedules(), 1);
+        assert!(scheduler.list_schedules().is_empty());
+        assert!(scheduler.triggers.is_empty());
+        assert!(matches!(scheduler.poll(), Poll::Empty));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_query_upcoming_schedules() {
+        let clock = ManualClock::new(ANCHOR_MILLIS);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+
+        let expr = StHourlyExpression::new(2);
+        let freq = StRegularFrequency::new(StFrequencyType::Hour, expr, None);
+        let other = StSchedule::with_regular_and_clock(
+            "other",
+            "2024-10-28T21:35:55.025",
+            "Africa/Lagos",
+            freq,
+            None,
+            &clock,
+        );
+        scheduler.add_schedule(hourly_schedule(&clock, None));
+        scheduler.add_schedule(other);
+
+        let upcoming = scheduler
+            .upcoming(4)
+            .into_iter()
+            .map(|e| (e.id, e.planned_at))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            upcoming,
+            vec![
+                ("id".to_string(), hours_after_anchor(0.0)),
+                ("other".to_string(), hours_after_anchor(0.5)),
+                ("id".to_string(), hours_after_anchor(1.0)),
+                ("id".to_string(), hours_after_anchor(2.0)),
+            ]
+        );
+        assert_eq!(scheduler.queue_size(), 2);
+
+        // A snoozed occurrence fires before the next one
+        if let Poll::Ready(due) = scheduler.poll() {
+            scheduler.fire(due);
+        }
+        scheduler.snooze_schedule_for("id".to_string(), Timestamp::Millis(600_000));
+        assert_eq!(
+            scheduler.next_fire_time("id"),
+            Some(hours_after_anchor(0.0) + Timestamp::Millis(600_000))
+        );
+
+        scheduler.pause_schedule("other".to_string(), None);
+        assert_eq!(scheduler.next_fire_time("other"), None);
+        assert_eq!(scheduler.next_fire_time("missing"), None);
+        assert_eq!(scheduler.queue_size(), 1);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_snapshot_and_restore_scheduler() {
+        let clock = ManualClock::new(ANCHOR_MILLIS);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+        scheduler.set_grace_period(Some(900_000));
+        scheduler.add_schedule(hourly_schedule(&clock, None));
+
+        if let Poll::Ready(due) = scheduler.poll() {
+            scheduler.fire(due);
+        }
+
+        let snapshot = scheduler.snapshot();
+        let entry = &snapshot.get_schedules()[0];
+        assert_eq!(snapshot.get_version(), SNAPSHOT_VERSION);
+        assert_eq!(entry.status, ScheduleStatus::Queued);
+        assert_eq!(entry.last_fired_at, Some(hours_after_anchor(0.0)));
+        assert_eq!(
+            entry.unacknowledged,
+            vec![OverdueSnapshot {
+                planned_at: hours_after_anchor(0.0),
+                overdue_at: hours_after_anchor(0.25),
+                grace: Timestamp::Millis(900_000),
+            }]
+        );
+
+        // The scheduler is reloaded after the next occurrence fell due
+        clock.set(hours_after_anchor(1.5).as_ms());
+        let mut restored = StScheduler::with_clock(Box::new(clock.clone()));
+        let events = restored.events();
+        restored.set_catch_up_policy(StCatchUpPolicy::FireAll);
+        restored.restore(snapshot).unwrap();
+
+        if let Poll::Ready(due) = restored.poll() {
+            restored.fire(due);
+        }
+
+        let events = core::iter::from_fn(|| events.try_recv().ok()).collect::<Vec<_>>();
+        assert_eq!(
+            lifecycle(&events),
+            vec![
+                ("upcoming", hours_after_anchor(1.0)),
+                ("overdue", hours_after_anchor(0.0)),
+                ("due", hours_after_anchor(1.0)),
+                ("upcoming", hours_after_anchor(2.0)),
+            ]
+        );
+
+        // A paused schedule stays paused, with when it is resumed
+        let resumes_at = hours_after_anchor(3.0);
+        restored.pause_schedule(String::from("id"), Some(resumes_at));
+        let snapshot = restored.snapshot();
+        assert_eq!(
+            snapshot.get_schedules()[0].status,
+            ScheduleStatus::Paused(Some(resumes_at))
+        );
+
+        scheduler.restore(snapshot).unwrap();
+        assert!(scheduler.is_paused("id"));
+        assert_eq!(scheduler.list_schedules()[0].resumes_at, Some(resumes_at));
+        assert_eq!(
+            scheduler.snapshot().get_schedules()[0].last_fired_at,
+            Some(hours_after_anchor(1.0))
+        );
     }
 }
@@ -0,0 +1,319 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::core::errors::SnapshotError;
+use crate::core::schedule::StSchedule;
+use crate::core::time::Timestamp;
+
+/// The version of the snapshots taken by this version of the scheduler.
+///
+/// It is bumped whenever the shape of a snapshot changes, along with a migration from the previous version
+/// added to `MIGRATIONS`, so snapshots persisted by older versions can still be restored.
+pub const SNAPSHOT_VERSION: u32 = 4;
+
+/// Upgrades the JSON of a snapshot by one version, the first upgrading version 1 to version 2 and so on
+#[cfg(feature = "serde")]
+type Migration = fn(serde_json::Value) -> Result<serde_json::Value, SnapshotError>;
+
+/// The migrations of snapshots from each older version to the next, in order, so there is always one fewer
+/// than [`SNAPSHOT_VERSION`]
+#[cfg(feature = "serde")]
+const MIGRATIONS: &[Migration] = &[
+    add_occurrence_dates,
+    add_business_days,
+    untrack_uncounted_index,
+];
+
+/// Version 2 amends the occurrences of schedules with excluded and included times, which the schedules of older
+/// versions have none of, so their deadlines are never an included time either
+#[cfg(feature = "serde")]
+fn add_occurrence_dates(value: serde_json::Value) -> Result<serde_json::Value, SnapshotError> {
+    migrate_schedules(value, |schedule| {
+        schedule
+            .entry("dates")
+            .or_insert_with(|| serde_json::json!({ "excluded": [], "included": [] }));
+        if let Some(timing) = schedule
+            .get_mut("timing")
+            .and_then(serde_json::Value::as_object_mut)
+        {
+            timing
+                .entry("extra")
+                .or_insert(serde_json::Value::Bool(false));
+        }
+    })
+}
+
+/// Version 3 rolls the occurrences of schedules to business days, which the schedules of older versions occur on
+/// any day, so their deadlines are never rolled either
+#[cfg(feature = "serde")]
+fn add_business_days(value: serde_json::Value) -> Result<serde_json::Value, SnapshotError> {
+    migrate_schedules(value, |schedule| {
+        schedule
+            .entry("business_days")
+            .or_insert(serde_json::Value::Null);
+        if let Some(timing) = schedule
+            .get_mut("timing")
+            .and_then(serde_json::Value::as_object_mut)
+        {
+            timing
+                .entry("rolled_from")
+                .or_insert(serde_json::Value::Null);
+        }
+    })
+}
+
+/// Version 4 tracks the index of the deadlines of every schedule, which older versions only tracked for
+/// frequencies with a count, so the others are left to be located
+#[cfg(feature = "serde")]
+fn untrack_uncounted_index(value: serde_json::Value) -> Result<serde_json::Value, SnapshotError> {
+    migrate_schedules(value, |schedule| {
+        let counted = schedule
+            .get("frequency")
+            .and_then(|frequency| frequency.get("count"))
+            .is_some_and(|count| !count.is_null());
+
+        if let Some(timing) = schedule
+            .get_mut("timing")
+            .and_then(serde_json::Value::as_object_mut)
+        {
+            if !counted {
+                timing.insert(String::from("index"), serde_json::Value::Null);
+            }
+        }
+    })
+}
+
+/// Applies the given change to the JSON of each schedule recorded by the JSON of a snapshot
+#[cfg(feature = "serde")]
+fn migrate_schedules<F>(
+    mut value: serde_json::Value,
+    change: F,
+) -> Result<serde_json::Value, SnapshotError>
+where
+    F: Fn(&mut serde_json::Map<String, serde_json::Value>),
+{
+    let malformed = |field: &str| SnapshotError::Malformed(format!("missing field `{}`", field));
+    let schedules = value
+        .get_mut("schedules")
+        .and_then(serde_json::Value::as_array_mut)
+        .ok_or_else(|| malformed("schedules"))?;
+
+    for entry in schedules {
+        let schedule = entry
+            .get_mut("schedule")
+            .and_then(serde_json::Value::as_object_mut)
+            .ok_or_else(|| malformed("schedule"))?;
+        change(schedule);
+    }
+
+    Ok(value)
+}
+
+/// An image of the state of a scheduler, taken with [`crate::core::scheduler::StScheduler::snapshot`] and
+/// restored with [`crate::core::scheduler::StScheduler::restore`].
+///
+/// Besides the schedules, it records which of their occurrences were already fired, so a restored scheduler
+/// neither fires them again nor loses the ones that fell due while it was not running.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StSchedulerSnapshot {
+    /// The version of the shape of the snapshot
+    version: u32,
+    /// The state of each schedule held by the scheduler, ordered by ID
+    schedules: Vec<ScheduleSnapshot>,
+}
+
+/// The state of a schedule held by a scheduler, as recorded by a [`StSchedulerSnapshot`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScheduleSnapshot {
+    /// The schedule at its next occurrence, which has not been fired yet, or at the occurrence fired last when
+    /// it has none left
+    pub schedule: StSchedule,
+    pub status: ScheduleStatus,
+    /// The planned time of the occurrence that was fired last, if any
+    pub last_fired_at: Option<Timestamp>,
+    /// The fired occurrence that is snoozed, if any
+    pub snooze: Option<SnoozeSnapshot>,
+    /// The fired occurrences waiting to be acknowledged before they become overdue
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unacknowledged: Vec<OverdueSnapshot>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ScheduleStatus {
+    /// The schedule is queued at its next occurrence
+    Queued,
+    /// The schedule is paused, and automatically resumed at the given time if ever
+    Paused(Option<Timestamp>),
+    /// The schedule has no occurrence left, and is only kept for its snoozed or unacknowledged occurrences
+    Expired,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SnoozeSnapshot {
+    /// The planned time of the snoozed occurrence
+    pub planned_at: Timestamp,
+    /// When the snoozed occurrence is fired again
+    pub fires_at: Timestamp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OverdueSnapshot {
+    /// The planned time of the fired occurrence
+    pub planned_at: Timestamp,
+    /// When the occurrence becomes overdue unless it is acknowledged
+    pub overdue_at: Timestamp,
+    /// The grace period the occurrence was fired with
+    pub grace: Timestamp,
+}
+
+#[wasm_bindgen]
+impl StSchedulerSnapshot {
+    /// Gets the version of the shape of the snapshot
+    pub fn get_version(&self) -> u32 {
+        self.version
+    }
+
+    /// Gets the number of schedules recorded by the snapshot
+    pub fn size(&self) -> usize {
+        self.schedules.len()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen]
+impl StSchedulerSnapshot {
+    /// Writes the snapshot as JSON, e.g. to persist it
+    pub fn to_json(&self) -> Result<String, SnapshotError> {
+        serde_json::to_string(self).map_err(|err| SnapshotError::Malformed(err.to_string()))
+    }
+
+    /// Reads a snapshot from JSON written by [`StSchedulerSnapshot::to_json`], migrating it to the current
+    /// version when it was written by an older version of the scheduler.
+    ///
+    /// Throws when the JSON is not a snapshot, or is one of a version newer than the current one.
+    pub fn from_json(json: &str) -> Result<StSchedulerSnapshot, SnapshotError> {
+        let malformed = |err: serde_json::Error| SnapshotError::Malformed(err.to_string());
+        let mut value = serde_json::from_str::<serde_json::Value>(json).map_err(malformed)?;
+
+        let version = value
+            .get("version")
+            .and_then(serde_json::Value::as_u64)
+            .ok_or_else(|| SnapshotError::Malformed(String::from("missing field `version`")))?;
+
+        if version == 0 || version > SNAPSHOT_VERSION as u64 {
+            return Err(SnapshotError::UnsupportedVersion(version as u32));
+        }
+
+        for (from, migrate) in MIGRATIONS.iter().enumerate().skip(version as usize - 1) {
+            value = migrate(value)?;
+            value["version"] = serde_json::Value::from(from + 2);
+        }
+
+        serde_json::from_value(value).map_err(malformed)
+    }
+}
+
+impl StSchedulerSnapshot {
+    /// Creates a snapshot of the current version from the state of the given schedules
+    pub fn new(schedules: Vec<ScheduleSnapshot>) -> Self {
+        StSchedulerSnapshot {
+            version: SNAPSHOT_VERSION,
+            schedules,
+        }
+    }
+
+    pub fn get_schedules(&self) -> &[ScheduleSnapshot] {
+        &self.schedules
+    }
+
+    pub(crate) fn into_schedules(self) -> Vec<ScheduleSnapshot> {
+        self.schedules
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::core::schedule::OccurrenceDates;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_migrations_reach_current_version() {
+        assert_eq!(MIGRATIONS.len() as u32, SNAPSHOT_VERSION - 1);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_read_snapshot_versions() {
+        let empty = StSchedulerSnapshot::new(vec![]);
+        let json = empty.to_json().unwrap();
+
+        assert_eq!(StSchedulerSnapshot::from_json(&json), Ok(empty));
+        let newer = format!(r#"{{"version":{},"schedules":[]}}"#, SNAPSHOT_VERSION + 1);
+        assert_eq!(
+            StSchedulerSnapshot::from_json(&newer),
+            Err(SnapshotError::UnsupportedVersion(SNAPSHOT_VERSION + 1))
+        );
+        assert!(matches!(
+            StSchedulerSnapshot::from_json(r#"{"schedules":[]}"#),
+            Err(SnapshotError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_restore_version_1_snapshot() {
+        use crate::core::clock::ManualClock;
+        use crate::core::scheduler::StScheduler;
+
+        // An hourly schedule whose first occurrence was fired and snoozed for half an hour
+        let json = concat!(
+            r#"{"version":1,"schedules":[{"schedule":{"id":"id","timing":{"timezone":"Africa/Lagos","#,
+            r#""anchor":1730145955025,"deadline":1730149555025,"naive_anchor":"2024-10-28T21:05:55.025"},"#,
+            r#""priority":null,"frequency":{"kind":"regular","ftype":"Hour","until":null,"#,
+            r#""expr":{"kind":"hourly","every":1}},"catch_up":null,"reminders":[],"grace_period":null},"#,
+            r#""status":"queued","last_fired_at":1730145955025,"#,
+            r#""snooze":{"planned_at":1730145955025,"fires_at":1730147755025},"unacknowledged":[]}]}"#
+        );
+
+        let snapshot = StSchedulerSnapshot::from_json(json).unwrap();
+        assert_eq!(snapshot.get_version(), SNAPSHOT_VERSION);
+        let schedule = &snapshot.get_schedules()[0].schedule;
+        assert_eq!(schedule.get_count(), None);
+        assert_eq!(schedule.get_dates(), &OccurrenceDates::default());
+        assert_eq!(schedule.get_business_days(), None);
+        assert_eq!(schedule.get_occurrence_index().unwrap(), 1);
+        assert_eq!(schedule.get_deadline_millis(), 1730149555025);
+
+        let clock = ManualClock::new(1730145955025 + 60_000);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock));
+        scheduler.restore(snapshot).unwrap();
+        assert_eq!(
+            scheduler.next_fire_time("id"),
+            Some(Timestamp::Millis(1730147755025))
+        );
+        assert_eq!(
+            scheduler.snapshot().get_schedules()[0].last_fired_at,
+            Some(Timestamp::Millis(1730145955025))
+        );
+
+        // The index of a deadline was not tracked without a count before version 4
+        let untracked = json
+            .replace(r#""version":1"#, r#""version":3"#)
+            .replace(r#""timezone""#, r#""index":0,"timezone""#);
+        let snapshot = StSchedulerSnapshot::from_json(&untracked).unwrap();
+        let schedule = &snapshot.get_schedules()[0].schedule;
+        assert_eq!(schedule.get_occurrence_index().unwrap(), 1);
+    }
+}
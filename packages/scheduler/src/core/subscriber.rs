@@ -0,0 +1,106 @@
+use core::fmt::Debug;
+
+use async_std::channel;
+use wasm_bindgen::prelude::*;
+
+use crate::console_error;
+pub use crate::core::event::SchedulerEvent; // Re-Export
+
+/// A sink that observes the events dispatched by a scheduler.
+///
+/// Closures taking a [`SchedulerEvent`] reference, channel senders, and JavaScript functions (through
+/// [`JsSubscriber`]) are all subscribers. Since the receiving end of a channel is a
+/// `futures::Stream`, subscribing a channel sender is also the way to observe events as a stream.
+pub trait Subscriber {
+    /// Notifies the subscriber of an event
+    fn notify(&self, event: &SchedulerEvent);
+}
+
+impl<F> Subscriber for F
+where
+    F: Fn(&SchedulerEvent),
+{
+    fn notify(&self, event: &SchedulerEvent) {
+        self(event)
+    }
+}
+
+impl Subscriber for channel::Sender<SchedulerEvent> {
+    fn notify(&self, event: &SchedulerEvent) {
+        // The scheduler cannot wait on a slow subscriber, so events that do not fit are dropped
+        if let Err(error) = self.try_send(event.clone()) {
+            console_error!("Failed to send scheduler event: {}", error);
+        }
+    }
+}
+
+/// A [`Subscriber`] adapter for a JavaScript function.
+///
+/// The function is called with a plain object of the shape of `StSchedulerEvent` for every event.
+#[derive(Debug, Clone)]
+pub struct JsSubscriber(js_sys::Function);
+
+impl JsSubscriber {
+    pub fn new(function: js_sys::Function) -> Self {
+        JsSubscriber(function)
+    }
+}
+
+impl Subscriber for JsSubscriber {
+    fn notify(&self, event: &SchedulerEvent) {
+        if let Err(error) = self.0.call1(&JsValue::NULL, &JsValue::from(event)) {
+            console_error!("Subscriber threw an error: {:?}", error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use wasm_bindgen_test::*;
+
+    use crate::core::event::DueEvent;
+    use crate::core::schedule::StSchedule;
+    use crate::core::time::Timestamp;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn due_event(id: &str) -> SchedulerEvent {
+        let schedule = StSchedule::new(id, "2024-10-28T21:05:55.025", "Africa/Lagos", None);
+        SchedulerEvent::Due(DueEvent::new(&schedule, Timestamp::Millis(1730145955025)))
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_closure_subscriber() {
+        let received = Rc::new(RefCell::new(vec![]));
+        let received_clone = received.clone();
+        let subscriber = move |event: &SchedulerEvent| {
+            if let SchedulerEvent::Due(due) = event {
+                received_clone.borrow_mut().push(due.id.clone())
+            }
+        };
+
+        subscriber.notify(&due_event("first"));
+        subscriber.notify(&due_event("second"));
+
+        assert_eq!(*received.borrow(), vec!["first", "second"]);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    pub fn test_channel_subscriber() {
+        let (tx, rx) = channel::bounded::<SchedulerEvent>(1);
+
+        tx.notify(&due_event("first"));
+        tx.notify(&due_event("dropped")); // The channel is full
+
+        match rx.try_recv() {
+            Ok(SchedulerEvent::Due(due)) => assert_eq!(due.id, "first"),
+            other => panic!("Expected a due event, got {:?}", other),
+        }
+        assert!(rx.try_recv().is_err());
+    }
+}
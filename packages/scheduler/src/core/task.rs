@@ -0,0 +1,554 @@
+use core::ops::RangeInclusive;
+
+use js_sys::Date;
+use serde::de::{DeserializeOwned, Error};
+use serde::{Deserialize, Deserializer};
+use tsify::Tsify;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::core::clock::SystemClock;
+use crate::core::errors::TaskScheduleError;
+use crate::core::frequency::{
+    StConstWeekday, StCustomFrequency, StDailyExpression, StFrequency, StFrequencyType,
+    StHourlyExpression, StMonthlyExpression, StOrdinals, StRegularFrequency, StVarWeekday,
+    StWeeklyExpression, StYearlyExpression,
+};
+use crate::core::schedule::StSchedule;
+use crate::core::time::Timestamp;
+
+// The types below are the single source of both how a task schedule is read and of its TypeScript declarations,
+// which `Tsify` derives from their fields and `serde` attributes.
+//
+// Weekdays are 0-6 from Sunday, months are 0-11 from January, and days of the month are 1-31.
+
+/// A `Date` of a task schedule, read as is off the JavaScript value
+pub struct StTaskDate(pub Timestamp);
+
+impl<'de> Deserialize<'de> for StTaskDate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value: JsValue = serde_wasm_bindgen::preserve::deserialize(deserializer)?;
+        let millis = value
+            .dyn_ref::<Date>()
+            .ok_or_else(|| D::Error::custom("invalid type: expected a Date"))?
+            .get_time();
+
+        match millis.is_nan() {
+            true => Err(D::Error::custom("invalid value: expected a valid Date")),
+            false => Ok(StTaskDate(Timestamp::Millis(millis as i64))),
+        }
+    }
+}
+
+/// The ordinal of a weekday in a month, as in "the first Monday"
+#[derive(Tsify, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StTaskOrdinal {
+    First,
+    Second,
+    Third,
+    Fourth,
+    Fifth,
+    Last,
+}
+
+impl From<StTaskOrdinal> for StOrdinals {
+    fn from(value: StTaskOrdinal) -> Self {
+        match value {
+            StTaskOrdinal::First => StOrdinals::First,
+            StTaskOrdinal::Second => StOrdinals::Second,
+            StTaskOrdinal::Third => StOrdinals::Third,
+            StTaskOrdinal::Fourth => StOrdinals::Fourth,
+            StTaskOrdinal::Fifth => StOrdinals::Fifth,
+            StTaskOrdinal::Last => StOrdinals::Last,
+        }
+    }
+}
+
+/// The timing of a task schedule, the same as `Timing` from `@stitches/common`
+#[derive(Tsify, Deserialize)]
+pub struct StTaskTiming {
+    #[tsify(optional, type = "Date")]
+    pub anchor: Option<StTaskDate>,
+    /// The anchor time without a timezone, e.g. "2024-10-28T21:05:55.025"
+    pub naive: String,
+    /// The IANA timezone of the anchor time
+    pub tzone: String,
+}
+
+#[derive(Tsify, Deserialize)]
+pub struct StTaskInterval {
+    pub every: u32,
+}
+
+/// An hourly or a daily frequency
+#[derive(Tsify, Deserialize)]
+pub struct StTaskIntervalFrequency {
+    #[tsify(optional, type = "Date | null")]
+    pub until: Option<StTaskDate>,
+    /// The number of occurrences, counting the anchor, after which the frequency stops repeating
+    #[tsify(optional, type = "number | null")]
+    pub count: Option<u32>,
+    pub exprs: StTaskInterval,
+}
+
+#[derive(Tsify, Deserialize)]
+pub struct StTaskWeekdays {
+    pub weekdays: Vec<u32>,
+}
+
+#[derive(Tsify, Deserialize)]
+pub struct StTaskWeeklyExprs {
+    pub every: u32,
+    pub subexpr: StTaskWeekdays,
+}
+
+#[derive(Tsify, Deserialize)]
+pub struct StTaskWeeklyFrequency {
+    #[tsify(optional, type = "Date | null")]
+    pub until: Option<StTaskDate>,
+    #[tsify(optional, type = "number | null")]
+    pub count: Option<u32>,
+    pub exprs: StTaskWeeklyExprs,
+}
+
+#[derive(Tsify, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum StTaskMonthlySubexpr {
+    Ondays {
+        days: Vec<u32>,
+    },
+    Onthe {
+        ordinal: StTaskOrdinal,
+        weekday: u32,
+    },
+}
+
+#[derive(Tsify, Deserialize)]
+pub struct StTaskMonthlyExprs {
+    pub every: u32,
+    pub subexpr: StTaskMonthlySubexpr,
+}
+
+#[derive(Tsify, Deserialize)]
+pub struct StTaskMonthlyFrequency {
+    #[tsify(optional, type = "Date | null")]
+    pub until: Option<StTaskDate>,
+    #[tsify(optional, type = "number | null")]
+    pub count: Option<u32>,
+    pub exprs: StTaskMonthlyExprs,
+}
+
+#[derive(Tsify, Deserialize)]
+pub struct StTaskMonths {
+    pub months: Vec<u32>,
+}
+
+#[derive(Tsify, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StTaskVariableWeekday {
+    Day,
+    Weekday,
+    WeekendDay,
+}
+
+#[derive(Tsify, Deserialize)]
+pub struct StTaskYearlyOn {
+    pub ordinal: StTaskOrdinal,
+    #[tsify(optional)]
+    pub weekday: Option<u32>,
+    #[tsify(optional)]
+    pub variable: Option<StTaskVariableWeekday>,
+}
+
+#[derive(Tsify, Deserialize)]
+pub struct StTaskYearlySubexpr {
+    #[serde(rename = "in")]
+    pub within: StTaskMonths,
+    #[tsify(optional)]
+    pub on: Option<StTaskYearlyOn>,
+}
+
+#[derive(Tsify, Deserialize)]
+pub struct StTaskYearlyExprs {
+    pub every: u32,
+    pub subexpr: StTaskYearlySubexpr,
+}
+
+#[derive(Tsify, Deserialize)]
+pub struct StTaskYearlyFrequency {
+    #[tsify(optional, type = "Date | null")]
+    pub until: Option<StTaskDate>,
+    #[tsify(optional, type = "number | null")]
+    pub count: Option<u32>,
+    pub exprs: StTaskYearlyExprs,
+}
+
+#[derive(Tsify, Deserialize)]
+pub struct StTaskCron {
+    pub expression: String,
+    pub frequency: String,
+}
+
+#[derive(Tsify, Deserialize)]
+pub struct StTaskCustomFrequency {
+    #[tsify(optional, type = "Date | null")]
+    pub until: Option<StTaskDate>,
+    #[tsify(optional, type = "number | null")]
+    pub count: Option<u32>,
+    pub crons: Vec<StTaskCron>,
+}
+
+/// The frequency of a task schedule, the same as `Frequency` from `@stitches/common`.
+///
+/// It is not deserialized as a whole, as the buffering `serde` does for tagged enums loses its dates; the type it
+/// names is read first and the frequency of that type is then read off the same value.
+#[derive(Tsify)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum StTaskFrequency {
+    Never,
+    Hour(StTaskIntervalFrequency),
+    Day(StTaskIntervalFrequency),
+    Week(StTaskWeeklyFrequency),
+    Month(StTaskMonthlyFrequency),
+    Year(StTaskYearlyFrequency),
+    Custom(StTaskCustomFrequency),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum FrequencyType {
+    Never,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+    Custom,
+}
+
+#[derive(Deserialize)]
+struct FrequencyTag {
+    #[serde(rename = "type")]
+    kind: FrequencyType,
+}
+
+/// A task schedule, the same as `TaskSchedule` from `@stitches/common`
+#[derive(Tsify, Deserialize)]
+pub struct StTaskSchedule {
+    pub id: String,
+    pub timing: StTaskTiming,
+    #[tsify(type = "StTaskFrequency")]
+    #[serde(with = "serde_wasm_bindgen::preserve")]
+    pub frequency: JsValue,
+    /// How long before each occurrence to remind of it, in milliseconds
+    #[tsify(optional)]
+    pub reminders: Option<Vec<u32>>,
+    /// The times the schedule does not occur at, e.g. the occurrences skipped
+    #[tsify(optional, type = "Date[]")]
+    pub exclusions: Option<Vec<StTaskDate>>,
+    /// The times the schedule occurs at besides the occurrences of its frequency
+    #[tsify(optional, type = "Date[]")]
+    pub inclusions: Option<Vec<StTaskDate>>,
+}
+
+#[wasm_bindgen]
+extern "C" {
+    /// A JavaScript object of the shape of `StTaskSchedule`
+    #[wasm_bindgen(typescript_type = "StTaskSchedule")]
+    pub type JsTaskSchedule;
+}
+
+#[wasm_bindgen]
+impl StSchedule {
+    /// Creates a schedule from a plain object of the shape of `TaskSchedule` from `@stitches/common`.
+    ///
+    /// Throws a `TypeError` naming the first invalid field, or when the timing of the schedule could not be
+    /// evaluated from the fields, e.g. because the frequency is already expired.
+    pub fn from_js(value: JsTaskSchedule) -> Result<StSchedule, TaskScheduleError> {
+        let task: StTaskSchedule = read(String::from("schedule"), value.into())?;
+        let frequency = StTaskFrequency::read(task.frequency)?.into_frequency()?;
+
+        let mut schedule = StSchedule::try_with_frequency(
+            &task.id,
+            &task.timing.naive,
+            &task.timing.tzone,
+            frequency,
+            None,
+            &SystemClock,
+        )?;
+
+        if let Some(reminders) = task.reminders {
+            schedule.set_reminders(reminders);
+        }
+
+        for StTaskDate(exclusion) in task.exclusions.unwrap_or_default() {
+            schedule.add_exclusion_with(exclusion, &SystemClock);
+        }
+
+        for StTaskDate(inclusion) in task.inclusions.unwrap_or_default() {
+            schedule.add_inclusion_with(inclusion, &SystemClock);
+        }
+
+        Ok(schedule)
+    }
+}
+
+/// Deserializes the JavaScript value at the given path, naming the exact field that fails in errors
+fn read<T: DeserializeOwned>(path: String, value: JsValue) -> Result<T, TaskScheduleError> {
+    serde_path_to_error::deserialize(serde_wasm_bindgen::Deserializer::from(value)).map_err(|err| {
+        let path = match err.path().to_string().as_str() {
+            "." => path,
+            inner => format!("{}.{}", path, inner),
+        };
+        // The errors are JavaScript `Error`s, whose message is all that describes what failed
+        let reason: String = JsValue::from(err.into_inner())
+            .unchecked_into::<js_sys::Error>()
+            .message()
+            .into();
+
+        // `serde` reports a missing field on the object holding it, in the same words for every format
+        match reason
+            .strip_prefix("missing field `")
+            .and_then(|field| field.strip_suffix('`'))
+        {
+            Some(field) => TaskScheduleError::Missing(format!("{}.{}", path, field)),
+            None => TaskScheduleError::Malformed(path, reason),
+        }
+    })
+}
+
+impl StTaskFrequency {
+    /// Reads the frequency of the type named by the `type` field of the given value
+    fn read(value: JsValue) -> Result<StTaskFrequency, TaskScheduleError> {
+        let path = String::from("schedule.frequency");
+
+        if value.is_null() || value.is_undefined() {
+            return Err(TaskScheduleError::Missing(path));
+        }
+
+        let frequency = match read::<FrequencyTag>(path.clone(), value.clone())?.kind {
+            FrequencyType::Never => StTaskFrequency::Never,
+            FrequencyType::Hour => StTaskFrequency::Hour(read(path, value)?),
+            FrequencyType::Day => StTaskFrequency::Day(read(path, value)?),
+            FrequencyType::Week => StTaskFrequency::Week(read(path, value)?),
+            FrequencyType::Month => StTaskFrequency::Month(read(path, value)?),
+            FrequencyType::Year => StTaskFrequency::Year(read(path, value)?),
+            FrequencyType::Custom => StTaskFrequency::Custom(read(path, value)?),
+        };
+
+        Ok(frequency)
+    }
+
+    /// Converts the frequency, which is `None` for one-off schedules
+    fn into_frequency(self) -> Result<Option<StFrequency>, TaskScheduleError> {
+        let frequency = match self {
+            StTaskFrequency::Never => return Ok(None),
+            StTaskFrequency::Custom(freq) => {
+                let expressions = non_empty(freq.crons, "schedule.frequency.crons")?
+                    .into_iter()
+                    .map(|cron| cron.expression)
+                    .collect();
+
+                let mut custom = StCustomFrequency::new(expressions, None);
+                custom.until = freq.until.map(|StTaskDate(until)| until);
+                custom.count = read_count(freq.count)?;
+                StFrequency::Custom(custom)
+            }
+            StTaskFrequency::Hour(freq) => {
+                let expr = StHourlyExpression::new(read_every(freq.exprs.every)?);
+                read_until(
+                    StRegularFrequency::new(StFrequencyType::Hour, expr, None),
+                    freq.until,
+                    freq.count,
+                )?
+            }
+            StTaskFrequency::Day(freq) => {
+                let expr = StDailyExpression::new(read_every(freq.exprs.every)?);
+                let regular = StRegularFrequency::with_daily_expr(StFrequencyType::Day, expr, None);
+                read_until(regular, freq.until, freq.count)?
+            }
+            StTaskFrequency::Week(freq) => {
+                let path = "schedule.frequency.exprs.subexpr.weekdays";
+                let weekdays = non_empty(freq.exprs.subexpr.weekdays, path)?
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, weekday)| read_weekday(weekday, format!("{}[{}]", path, i)))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let every = read_every(freq.exprs.every)?;
+                let expr = StWeeklyExpression::with_weekdays(every, weekdays);
+                let regular =
+                    StRegularFrequency::with_weekly_expr(StFrequencyType::Week, expr, None);
+                read_until(regular, freq.until, freq.count)?
+            }
+            StTaskFrequency::Month(freq) => {
+                let every = read_every(freq.exprs.every)?;
+                let expr = read_monthly_expr(every, freq.exprs.subexpr)?;
+                let regular =
+                    StRegularFrequency::with_monthly_expr(StFrequencyType::Month, expr, None);
+                read_until(regular, freq.until, freq.count)?
+            }
+            StTaskFrequency::Year(freq) => {
+                let every = read_every(freq.exprs.every)?;
+                let expr = read_yearly_expr(every, freq.exprs.subexpr)?;
+                let regular =
+                    StRegularFrequency::with_yearly_expr(StFrequencyType::Year, expr, None);
+                read_until(regular, freq.until, freq.count)?
+            }
+        };
+
+        Ok(Some(frequency))
+    }
+}
+
+/// Wraps the given regular frequency, valid until the given time or for the given number of occurrences if any
+fn read_until(
+    mut freq: StRegularFrequency,
+    until: Option<StTaskDate>,
+    count: Option<u32>,
+) -> Result<StFrequency, TaskScheduleError> {
+    freq.until = until.map(|StTaskDate(until)| until);
+    freq.count = read_count(count)?;
+    Ok(StFrequency::Regular(freq))
+}
+
+fn read_count(count: Option<u32>) -> Result<Option<u32>, TaskScheduleError> {
+    count
+        .map(|count| {
+            within(
+                count,
+                1..=u32::MAX,
+                String::from("schedule.frequency.count"),
+                "a positive integer",
+            )
+        })
+        .transpose()
+}
+
+fn read_every(every: u32) -> Result<u32, TaskScheduleError> {
+    within(
+        every,
+        1..=u32::MAX,
+        String::from("schedule.frequency.exprs.every"),
+        "a positive integer",
+    )
+}
+
+fn read_weekday(weekday: u32, path: String) -> Result<u32, TaskScheduleError> {
+    within(
+        weekday,
+        0..=6,
+        path,
+        "a weekday between 0 (Sunday) and 6 (Saturday)",
+    )
+}
+
+fn read_monthly_expr(
+    every: u32,
+    subexpr: StTaskMonthlySubexpr,
+) -> Result<StMonthlyExpression, TaskScheduleError> {
+    let path = "schedule.frequency.exprs.subexpr";
+
+    match subexpr {
+        StTaskMonthlySubexpr::Ondays { days } => {
+            let path = format!("{}.days", path);
+            let days = non_empty(days, &path)?
+                .into_iter()
+                .enumerate()
+                .map(|(i, day)| {
+                    within(
+                        day,
+                        1..=31,
+                        format!("{}[{}]", path, i),
+                        "a day of the month between 1 and 31",
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(StMonthlyExpression::with_days(every, days))
+        }
+        StTaskMonthlySubexpr::Onthe { ordinal, weekday } => {
+            let weekday = read_weekday(weekday, format!("{}.weekday", path))?;
+
+            Ok(StMonthlyExpression::with_ordinal_weekday(
+                every,
+                ordinal.into(),
+                StConstWeekday::from(weekday),
+            ))
+        }
+    }
+}
+
+fn read_yearly_expr(
+    every: u32,
+    subexpr: StTaskYearlySubexpr,
+) -> Result<StYearlyExpression, TaskScheduleError> {
+    let path = "schedule.frequency.exprs.subexpr";
+    let months_path = format!("{}.in.months", path);
+    let months = non_empty(subexpr.within.months, &months_path)?
+        .into_iter()
+        .enumerate()
+        .map(|(i, month)| {
+            within(
+                month,
+                0..=11,
+                format!("{}[{}]", months_path, i),
+                "a month between 0 (January) and 11 (December)",
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let on = match subexpr.on {
+        Some(on) => on,
+        None => return Ok(StYearlyExpression::with_months(every, months)),
+    };
+
+    if let Some(weekday) = on.weekday {
+        let weekday = read_weekday(weekday, format!("{}.on.weekday", path))?;
+        return Ok(StYearlyExpression::with_months_ordinal_const_weekday(
+            every,
+            months,
+            on.ordinal.into(),
+            StConstWeekday::from(weekday),
+        ));
+    }
+
+    let weekday = match on.variable {
+        Some(StTaskVariableWeekday::Day) => StVarWeekday::Day,
+        Some(StTaskVariableWeekday::Weekday) => StVarWeekday::Weekday,
+        Some(StTaskVariableWeekday::WeekendDay) => StVarWeekday::Weekend,
+        None => return Err(TaskScheduleError::Missing(format!("{}.on.variable", path))),
+    };
+
+    Ok(StYearlyExpression::with_months_ordinal_var_weekday(
+        every,
+        months,
+        on.ordinal.into(),
+        weekday,
+    ))
+}
+
+/// Checks the integer at the given path is within the given range, described by `allowed` in error messages
+fn within(
+    n: u32,
+    range: RangeInclusive<u32>,
+    path: String,
+    allowed: &'static str,
+) -> Result<u32, TaskScheduleError> {
+    match range.contains(&n) {
+        true => Ok(n),
+        false => Err(TaskScheduleError::InvalidValue(path, allowed)),
+    }
+}
+
+/// Checks the array at the given path has at least one item
+fn non_empty<T>(items: Vec<T>, path: &str) -> Result<Vec<T>, TaskScheduleError> {
+    match items.is_empty() {
+        true => Err(TaskScheduleError::InvalidValue(
+            path.to_string(),
+            "a non-empty array",
+        )),
+        false => Ok(items),
+    }
+}
@@ -62,7 +62,7 @@ impl Timestamp {
 
     /// Converts `Timestamp` to a `core::time::Duration` in milliseconds
     pub fn to_core_duration(&self) -> core::time::Duration {
-        core::time::Duration::from_millis(self.as_ms().abs() as u64)
+        core::time::Duration::from_millis(self.as_ms().unsigned_abs())
     }
 
     /// Converts `Timestamp` to a `chrono::DateTime<UTC>` with millisecond precision
@@ -198,7 +198,7 @@ impl Eq for Timestamp {} // Necessary to implement `Ord`.
 
 impl PartialOrd for Timestamp {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.as_ms().partial_cmp(&other.as_ms())
+        Some(self.cmp(other))
     }
 }
 
@@ -208,6 +208,27 @@ impl Ord for Timestamp {
     }
 }
 
+/// Timestamps are serialized as a number of milliseconds, whatever their unit
+#[cfg(feature = "serde")]
+impl serde::Serialize for Timestamp {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_i64(self.as_ms())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Timestamp {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        i64::deserialize(deserializer).map(Timestamp::Millis)
+    }
+}
+
 #[inline(always)]
 pub fn timestamp() -> Timestamp {
     Utc::now().to_timestamp()
@@ -269,7 +290,7 @@ mod test {
         let ndt = NaiveDate::from_ymd_opt(2025, 1, 31)
             .unwrap()
             .and_time(anchor_dt.time());
-        let dt = DateTime::<Utc>::from_naive_utc_and_offset(ndt, anchor_dt.offset().clone());
+        let dt = DateTime::<Utc>::from_naive_utc_and_offset(ndt, *anchor_dt.offset());
 
         println!("{}", anchor_dt);
         println!("{}", ndt);
@@ -280,9 +301,9 @@ mod test {
         let v2: Option<Vec<u32>> = Some(vec![1, 2, 3]);
         // let v3: Option<Vec<u32>> = None;
 
-        let r: Option<&Vec<u32>> = v2.as_ref().map(|w| (!w.is_empty()).then_some(w)).flatten();
+        let r: Option<&Vec<u32>> = v2.as_ref().and_then(|w| (!w.is_empty()).then_some(w));
 
-        println!("{:?}", r.unwrap().into_iter().max());
+        println!("{:?}", r.unwrap().iter().max());
 
         let tz: Tz = "America/New_York".parse().unwrap();
         let dt = Local::now().with_timezone(&tz);
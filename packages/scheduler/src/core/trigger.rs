@@ -0,0 +1,106 @@
+use core::cmp::Ordering;
+
+use crate::core::schedule::StSchedule;
+use crate::core::time::Timestamp;
+use crate::traits::ID;
+
+/// A one-off trigger queued by the scheduler alongside a schedule's regular occurrences
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Trigger {
+    /// Identifies the trigger in the queue, a schedule has at most one trigger of each key
+    key: String,
+    /// The ID of the schedule the trigger belongs to
+    schedule_id: String,
+    /// When the trigger fires
+    pub(crate) at: Timestamp,
+    pub(crate) kind: TriggerKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TriggerKind {
+    /// Re-fires an occurrence that was snoozed, the schedule's deadline being the occurrence's planned time
+    Snooze(StSchedule),
+    /// Reminds of an upcoming occurrence the given offset before it, the schedule's deadline being the
+    /// occurrence's planned time
+    Reminder(StSchedule, Timestamp),
+    /// Marks a fired occurrence as overdue once the given grace period has passed without it being
+    /// acknowledged, the schedule's deadline being the occurrence's planned time
+    Overdue(StSchedule, Timestamp),
+}
+
+impl Trigger {
+    /// Creates a trigger re-firing the given occurrence at the given time
+    pub(crate) fn snooze(occurrence: StSchedule, at: Timestamp) -> Self {
+        let schedule_id = occurrence.get_id();
+
+        Trigger {
+            key: Self::snooze_key(&schedule_id),
+            schedule_id,
+            at,
+            kind: TriggerKind::Snooze(occurrence),
+        }
+    }
+
+    /// Creates a trigger reminding of the given occurrence the given offset before it
+    pub(crate) fn reminder(occurrence: StSchedule, offset: Timestamp) -> Self {
+        let schedule_id = occurrence.get_id();
+
+        Trigger {
+            key: Self::reminder_key(&schedule_id, offset),
+            at: Timestamp::Millis(occurrence.get_deadline_millis()) - offset,
+            schedule_id,
+            kind: TriggerKind::Reminder(occurrence, offset),
+        }
+    }
+
+    /// Creates a trigger marking the given occurrence, due at the given time, as overdue after the given
+    /// grace period
+    pub(crate) fn overdue(occurrence: StSchedule, due_at: Timestamp, grace: Timestamp) -> Self {
+        let schedule_id = occurrence.get_id();
+
+        Trigger {
+            key: format!(
+                "{}:overdue:{}",
+                schedule_id,
+                occurrence.get_deadline_millis()
+            ),
+            schedule_id,
+            at: due_at + grace,
+            kind: TriggerKind::Overdue(occurrence, grace),
+        }
+    }
+
+    /// Returns the key of the snooze trigger of the given schedule
+    pub(crate) fn snooze_key(schedule_id: &str) -> String {
+        format!("{}:snooze", schedule_id)
+    }
+
+    /// Returns the key of the reminder trigger of the given schedule at the given offset
+    pub(crate) fn reminder_key(schedule_id: &str, offset: Timestamp) -> String {
+        format!("{}:reminder:{}", schedule_id, offset.as_ms())
+    }
+
+    pub(crate) fn get_schedule_id(&self) -> &str {
+        &self.schedule_id
+    }
+}
+
+impl ID for Trigger {
+    fn get_id(&self) -> String {
+        self.key.clone()
+    }
+}
+
+impl Ord for Trigger {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.at
+            .cmp(&other.at)
+            .then_with(|| self.key.cmp(&other.key))
+    }
+}
+
+impl PartialOrd for Trigger {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
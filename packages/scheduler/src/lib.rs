@@ -1,3 +1,4 @@
+mod runtime;
 mod utils;
 
 pub mod core;
@@ -10,8 +11,8 @@ use core::{
     frequency::{StConstWeekday, StOrdinals},
     scheduler::{StScheduler, StSchedulerRunner},
 };
-use std::convert::TryInto;
 
+#[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_namespace = console)]
@@ -21,10 +22,20 @@ extern "C" {
     fn error(s: &str);
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+fn log(s: &str) {
+    log::info!("{}", s);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn error(s: &str) {
+    log::error!("{}", s);
+}
+
 #[macro_export]
 macro_rules! console_log {
     ($($t:tt)*) => {
-        let time = crate::core::time::timestamp().to_datetime().format("%Y-%m-%dT%H:%M:%S%.3f%z");
+        let time = $crate::core::time::timestamp().to_datetime().format("%Y-%m-%dT%H:%M:%S%.3f%z");
         $crate::log(&format!("[Scheduler] - {}  INFO  {}", time, &format!($($t)*)));
     };
 }
@@ -32,21 +43,19 @@ macro_rules! console_log {
 #[macro_export]
 macro_rules! console_error {
     ($($t:tt)*) => {
-        let time = crate::core::time::timestamp().to_datetime().format("%Y-%m-%dT%H:%M:%S%.3f%z");
+        let time = $crate::core::time::timestamp().to_datetime().format("%Y-%m-%dT%H:%M:%S%.3f%z");
         $crate::error(&format!("[Scheduler] - {}  ERROR  {}", time, &format!($($t)*)));
     };
 }
 
 #[wasm_bindgen]
 pub fn get_scheduler() -> StScheduler {
-    let scheduler = StScheduler::new();
-    scheduler
+    StScheduler::new()
 }
 
 #[wasm_bindgen]
 pub fn get_scheduler_runner() -> StSchedulerRunner {
-    let scheduler = StSchedulerRunner::new();
-    scheduler
+    StSchedulerRunner::new()
 }
 
 /// Gets the enum variant from a value between 0-6
@@ -56,7 +65,7 @@ pub fn get_scheduler_runner() -> StSchedulerRunner {
 /// When the supplied value is out of bounds, that is, greater than 6
 #[wasm_bindgen]
 pub fn st_const_weekday_from_value(value: u32) -> StConstWeekday {
-    value.try_into().unwrap()
+    value.into()
 }
 
 /// Gets the enum variant from a value between 0-4 and 255
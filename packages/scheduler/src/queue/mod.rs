@@ -1 +1 @@
-pub mod priority_queue;
\ No newline at end of file
+pub mod priority_queue;
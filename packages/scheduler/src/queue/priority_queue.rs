@@ -78,23 +78,34 @@ where
         }
     }
 
-    pub fn remove(&mut self, id: String) -> Option<T> {
-        let index = self.tracker.remove(&id);
+    /// Returns an iterator over the items in the queue in no particular order
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.heap.iter()
+    }
+
+    /// Removes every item from the queue, returning how many there were
+    pub fn clear(&mut self) -> usize {
+        let size = self.size();
+        self.heap.clear();
+        self.tracker.clear();
+        size
+    }
 
-        match index {
-            Some(index) => {
-                let result = Some(self.heap.remove(index));
+    pub fn remove(&mut self, id: String) -> Option<T> {
+        let index = self.tracker.remove(&id)?;
+        // swap the last element for the removed one rather than shifting every element after it
+        let result = self.heap.swap_remove(index);
 
-                if index > 0 {
-                    let idx = index;
-                    self.siftup(idx);
-                    self.siftdown(idx, None);
-                }
+        if index < self.size() {
+            // update the tracker with the new postion of the formerly last element
+            self.tracker.insert(self.heap[index].get_id(), index);
 
-                return result;
-            }
-            None => None,
+            // the formerly last element could belong either above or below its new position
+            self.siftup(index);
+            self.siftdown(index, None);
         }
+
+        Some(result)
     }
 
     pub fn enqueue(&mut self, value: T) {
@@ -194,7 +205,7 @@ mod test {
     // make sure to add unique numbers to the queue since the number is used as ID
     impl ID for i32 {
         fn get_id(&self) -> String {
-            return self.to_string();
+            self.to_string()
         }
     }
 
@@ -228,7 +239,7 @@ mod test {
 
         populate_pq(&mut pq);
 
-        assert_eq!(pq.is_empty(), false)
+        assert!(!pq.is_empty())
     }
 
     #[test]
@@ -313,6 +324,53 @@ mod test {
         assert_eq!(pq.dequeue(), Some(32));
         assert_eq!(pq.dequeue(), Some(47));
 
-        assert_eq!(pq.tracker.is_empty(), true);
+        assert!(pq.tracker.is_empty());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    // should keep the queue ordered and tracked when removing the top and the last items
+    pub fn test_remove_top_and_last() {
+        let mut pq = make_pq();
+
+        populate_pq(&mut pq);
+
+        assert_eq!(pq.remove("12".to_owned()), Some(12));
+        assert_eq!(pq.peek(), Some(&15));
+
+        let last = *pq.heap.last().unwrap();
+        assert_eq!(pq.remove(last.to_string()), Some(last));
+
+        // every remaining item should still be found where it is tracked
+        for value in pq.iter() {
+            assert_eq!(pq.find(value.to_string()), Some(value));
+        }
+
+        let mut remaining = vec![15, 27, 32, 47];
+        remaining.retain(|v| *v != last);
+
+        for value in remaining {
+            assert_eq!(pq.dequeue(), Some(value));
+        }
+
+        assert!(pq.is_empty());
+        assert_eq!(pq.remove("15".to_owned()), None);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    // should remove every item from the queue and stop tracking them
+    pub fn test_clear() {
+        let mut pq = make_pq();
+
+        populate_pq(&mut pq);
+
+        assert_eq!(pq.clear(), 5);
+        assert!(pq.is_empty());
+        assert!(pq.tracker.is_empty());
+
+        // the queue should still be usable afterwards
+        pq.enqueue(12);
+        assert_eq!(pq.find("12".to_owned()), Some(&12));
     }
 }
@@ -0,0 +1,27 @@
+//! The async runtime backend the scheduler runner spawns its tasks onto.
+//!
+//! By default, tasks are spawned onto the browser's event loop through `wasm-bindgen-futures`.
+//! With the `native` feature, they are spawned onto the thread-local executor of `async-std`
+//! instead, which runs them for as long as the spawning thread is blocked on a future,
+//! e.g. with `async_std::task::block_on`.
+
+use core::future::Future;
+
+/// Spawns a future onto the current thread
+#[cfg(not(feature = "native"))]
+pub fn spawn_local<F>(future: F)
+where
+    F: Future<Output = ()> + 'static,
+{
+    wasm_bindgen_futures::spawn_local(future)
+}
+
+/// Spawns a future onto the current thread
+#[cfg(feature = "native")]
+pub fn spawn_local<F>(future: F)
+where
+    F: Future<Output = ()> + 'static,
+{
+    // Dropping the handle detaches the task rather than cancelling it
+    async_std::task::spawn_local(future);
+}
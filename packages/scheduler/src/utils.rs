@@ -12,30 +12,31 @@ pub fn set_panic_hook() {
     console_error_panic_hook::set_once();
 }
 
-pub fn filter_unique<T, P>(collection: &Vec<T>, predicate: P) -> Vec<T>
+/// Returns the values of the collection that match the predicate without duplicates, in their original order
+pub fn filter_unique<T, P>(collection: &[T], mut predicate: P) -> Vec<T>
 where
     T: Hash + Eq + Clone,
     P: FnMut(&T) -> bool,
 {
+    let mut seen = HashSet::new();
     collection
-        .clone()
-        .into_iter()
-        .filter(predicate)
-        .collect::<HashSet<T>>()
-        .into_iter()
+        .iter()
+        .filter(|v| predicate(v) && seen.insert(*v))
+        .cloned()
         .collect()
 }
 
-pub fn map_unique<T, B, F>(collection: &Vec<T>, f: F) -> Vec<B>
+/// Returns the mapped values of the collection without duplicates, in the original order
+pub fn map_unique<T, B, F>(collection: &[T], f: F) -> Vec<B>
 where
-    B: Hash + Eq,
+    B: Hash + Eq + Clone,
     F: FnMut(&T) -> B,
 {
+    let mut seen = HashSet::new();
     collection
-        .into_iter()
+        .iter()
         .map(f)
-        .collect::<HashSet<B>>()
-        .into_iter()
+        .filter(|v| seen.insert(v.clone()))
         .collect()
 }
 
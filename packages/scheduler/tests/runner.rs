@@ -0,0 +1,471 @@
+//! Test suite for the scheduler runner on the native runtime backend.
+#![cfg(feature = "native")]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use async_std::channel::Receiver;
+use async_std::future;
+use async_std::stream::StreamExt;
+use async_std::task;
+
+extern crate scheduler;
+use scheduler::core::{
+    clock::ManualClock,
+    command::StCommandOutcome,
+    errors::RunnerError,
+    event::DueEvent,
+    frequency::{StFrequencyType, StHourlyExpression, StRegularFrequency},
+    schedule::StSchedule,
+    scheduler::{StRunnerState, StScheduler, StSchedulerRunner},
+    snapshot::StSchedulerSnapshot,
+    subscriber::SchedulerEvent,
+};
+
+static ISO_DATE_STRING: &str = "2024-10-28T21:05:55.025";
+static TIMEZONE: &str = "Africa/Lagos";
+static TIMESTAMP_MILLIS: i64 = 1730145955025;
+
+fn hourly_schedule(id: &str, clock: &ManualClock) -> StSchedule {
+    let freq = StRegularFrequency::new(StFrequencyType::Hour, StHourlyExpression::new(1), None);
+    StSchedule::with_regular_and_clock(id, ISO_DATE_STRING, TIMEZONE, freq, None, clock)
+}
+
+/// Waits for the next due event, skipping the other events
+async fn next_due(events: &mut Receiver<SchedulerEvent>) -> DueEvent {
+    events
+        .find_map(|event| match event {
+            SchedulerEvent::Due(due) => Some(due),
+            _ => None,
+        })
+        .await
+        .unwrap()
+}
+
+/// Should run, accept commands and stop gracefully without a browser event loop
+#[test]
+pub fn pass_run_and_stop_runner_natively() {
+    task::block_on(async {
+        let clock = ManualClock::new(TIMESTAMP_MILLIS);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+        scheduler.add_schedule(hourly_schedule("first", &clock));
+
+        let runner = StSchedulerRunner::new();
+        runner.run(scheduler).await.unwrap();
+
+        // Hand control over to the spawned loops so the due schedule is fired and pushed back
+        task::sleep(Duration::from_millis(10)).await;
+
+        assert_eq!(
+            runner
+                .update_scheduler_with(hourly_schedule("second", &clock))
+                .await,
+            StCommandOutcome::NotFound
+        );
+        assert_eq!(
+            runner.remove_from_scheduler("first".to_string()).await,
+            StCommandOutcome::Removed
+        );
+
+        task::sleep(Duration::from_millis(10)).await;
+        runner.stop().unwrap();
+    });
+}
+
+/// Should notify Rust subscribers, both closures and streams, of due schedules
+#[test]
+pub fn pass_notify_rust_subscribers_of_due_schedules() {
+    task::block_on(async {
+        let clock = ManualClock::new(TIMESTAMP_MILLIS);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+        scheduler.add_schedule(hourly_schedule("first", &clock));
+
+        let fired = Rc::new(RefCell::new(vec![]));
+        let fired_clone = fired.clone();
+        scheduler.add_subscriber(move |event: &SchedulerEvent| {
+            if let SchedulerEvent::Due(due) = event {
+                fired_clone.borrow_mut().push(due.id.clone())
+            }
+        });
+        let mut events = scheduler.events();
+
+        let runner = StSchedulerRunner::new();
+        runner.run(scheduler).await.unwrap();
+
+        match events.next().await {
+            Some(SchedulerEvent::Due(due)) => {
+                assert_eq!(due.id, "first");
+                assert_eq!(due.planned_at.as_ms(), TIMESTAMP_MILLIS);
+                assert_eq!(due.occurrence, Some(0));
+            }
+            other => panic!("Expected a due event, got {:?}", other),
+        }
+        assert_eq!(*fired.borrow(), vec!["first"]);
+
+        runner.stop().unwrap();
+    });
+}
+
+/// Should sleep until the head of the queue is due, firing it then rather than on a fixed polling interval
+#[test]
+pub fn pass_sleep_until_head_deadline() {
+    task::block_on(async {
+        let clock = ManualClock::new(TIMESTAMP_MILLIS - 50);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+        scheduler.add_schedule(hourly_schedule("first", &clock));
+        let mut events = scheduler.events();
+
+        let started = Instant::now();
+        let runner = StSchedulerRunner::new();
+        runner.run(scheduler).await.unwrap();
+
+        // The clock is moved on to the deadline while the runner sleeps, which it wakes up at on its own
+        task::sleep(Duration::from_millis(20)).await;
+        assert!(events.try_recv().is_err());
+        clock.set(TIMESTAMP_MILLIS);
+
+        let due = future::timeout(Duration::from_millis(800), next_due(&mut events))
+            .await
+            .expect("The runner should wake up at the deadline");
+        assert_eq!(due.id, "first");
+        assert!(started.elapsed() >= Duration::from_millis(50));
+
+        runner.stop().unwrap();
+    });
+}
+
+/// Should wake up early when a schedule due before the head of the queue is added
+#[test]
+pub fn pass_wake_early_for_earlier_schedule() {
+    task::block_on(async {
+        let clock = ManualClock::new(TIMESTAMP_MILLIS - 3_600_000);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+        scheduler.add_schedule(hourly_schedule("later", &clock));
+        let mut events = scheduler.events();
+
+        let runner = StSchedulerRunner::new();
+        runner.run(scheduler).await.unwrap();
+
+        // The runner is now asleep until the head of the queue is due in an hour
+        task::sleep(Duration::from_millis(20)).await;
+        assert!(events.try_recv().is_err());
+
+        let freq = StRegularFrequency::new(StFrequencyType::Hour, StHourlyExpression::new(1), None);
+        let sooner = StSchedule::with_regular_and_clock(
+            "sooner",
+            "2024-10-28T20:05:55.025",
+            TIMEZONE,
+            freq,
+            None,
+            &clock,
+        );
+        assert_eq!(
+            runner.add_schedule(sooner).await,
+            StCommandOutcome::Inserted
+        );
+
+        let due = future::timeout(Duration::from_millis(500), next_due(&mut events))
+            .await
+            .expect("The runner should be woken up by the earlier schedule");
+        assert_eq!(due.id, "sooner");
+        assert_eq!(due.planned_at.as_ms(), TIMESTAMP_MILLIS - 3_600_000);
+
+        runner.stop().unwrap();
+    });
+}
+
+/// Should wake up early when a schedule is added whose reminder is due before the head of the queue
+#[test]
+pub fn pass_wake_early_for_earlier_reminder() {
+    task::block_on(async {
+        let clock = ManualClock::new(TIMESTAMP_MILLIS - 6 * 3_600_000);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+        scheduler.add_schedule(hourly_schedule("head", &clock));
+        let mut events = scheduler.events();
+
+        let runner = StSchedulerRunner::new();
+        runner.run(scheduler).await.unwrap();
+
+        // The runner is now asleep until the head of the queue is due in six hours
+        task::sleep(Duration::from_millis(20)).await;
+        assert!(events.try_recv().is_err());
+
+        // A schedule due in two days, with a reminder due right before its second day
+        let freq = StRegularFrequency::new(StFrequencyType::Hour, StHourlyExpression::new(1), None);
+        let mut later = StSchedule::with_regular_and_clock(
+            "later",
+            "2024-10-30T15:05:55.025",
+            TIMEZONE,
+            freq,
+            None,
+            &clock,
+        );
+        later.set_reminders(vec![2 * 86_400_000 - 20]);
+        assert_eq!(runner.add_schedule(later).await, StCommandOutcome::Inserted);
+
+        // The clock is moved on to the reminder while the runner sleeps until it
+        task::sleep(Duration::from_millis(10)).await;
+        clock.set(TIMESTAMP_MILLIS - 6 * 3_600_000 + 20);
+
+        let reminder = future::timeout(
+            Duration::from_millis(500),
+            events.find_map(|event| match event {
+                SchedulerEvent::Reminder(reminder) => Some(reminder),
+                _ => None,
+            }),
+        )
+        .await
+        .expect("The runner should be woken up by the earlier reminder")
+        .unwrap();
+        assert_eq!(reminder.id, "later");
+        assert_eq!(
+            reminder.planned_at.as_ms(),
+            TIMESTAMP_MILLIS - 6 * 3_600_000 + 2 * 86_400_000
+        );
+
+        runner.stop().unwrap();
+    });
+}
+
+/// Should carry out every command and query sent to a running scheduler and resolve with its outcome
+#[test]
+pub fn pass_resolve_runner_commands_with_outcomes() {
+    task::block_on(async {
+        let clock = ManualClock::new(TIMESTAMP_MILLIS - 60_000);
+        let scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+
+        let runner = StSchedulerRunner::new();
+        runner.run(scheduler).await.unwrap();
+
+        let first = hourly_schedule("first", &clock);
+        assert_eq!(
+            runner.add_schedule(first.clone()).await,
+            StCommandOutcome::Inserted
+        );
+        assert_eq!(
+            runner.add_schedule(first.clone()).await,
+            StCommandOutcome::Duplicate
+        );
+        assert_eq!(
+            runner.upsert_schedule(first).await,
+            StCommandOutcome::Replaced
+        );
+        assert_eq!(
+            runner
+                .upsert_schedule(hourly_schedule("second", &clock))
+                .await,
+            StCommandOutcome::Inserted
+        );
+
+        let until = Some(TIMESTAMP_MILLIS as u64 + 3_600_000);
+        assert_eq!(
+            runner.pause_schedule("second".to_string(), until).await,
+            StCommandOutcome::Paused
+        );
+        assert_eq!(
+            runner.resume_schedule("first".to_string(), None).await,
+            StCommandOutcome::NotFound
+        );
+
+        let schedules = runner.schedules().await;
+        let listed = schedules
+            .iter()
+            .map(|s| (s.id.as_str(), s.next_at.as_ms(), s.paused))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            listed,
+            vec![
+                ("first", TIMESTAMP_MILLIS, false),
+                ("second", TIMESTAMP_MILLIS, true)
+            ]
+        );
+
+        assert_eq!(runner.queue_size().await, 1);
+        assert_eq!(runner.next_fire_time("second".to_string()).await, None);
+
+        assert_eq!(
+            runner.resume_schedule("second".to_string(), None).await,
+            StCommandOutcome::Resumed
+        );
+        assert_eq!(runner.queue_size().await, 2);
+        assert_eq!(
+            runner
+                .next_fire_time("second".to_string())
+                .await
+                .map(|ts| ts.as_ms()),
+            Some(TIMESTAMP_MILLIS)
+        );
+
+        let upcoming = runner
+            .upcoming(3)
+            .await
+            .into_iter()
+            .map(|e| (e.id, e.planned_at.as_ms()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            upcoming,
+            vec![
+                ("first".to_string(), TIMESTAMP_MILLIS),
+                ("second".to_string(), TIMESTAMP_MILLIS),
+                ("first".to_string(), TIMESTAMP_MILLIS + 3_600_000),
+            ]
+        );
+        assert_eq!(runner.drop_all_schedules().await, 2);
+        assert!(runner.schedules().await.is_empty());
+
+        runner.stop().unwrap();
+    });
+}
+
+/// Should resolve snoozing and acknowledging with whether there was a fired occurrence to apply them to
+#[test]
+pub fn pass_resolve_snooze_and_acknowledge_with_outcomes() {
+    task::block_on(async {
+        let clock = ManualClock::new(TIMESTAMP_MILLIS);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+        scheduler.set_grace_period(Some(60_000));
+        let mut events = scheduler.events();
+
+        let runner = StSchedulerRunner::new();
+        runner.run(scheduler).await.unwrap();
+
+        // Nothing was fired yet
+        assert_eq!(
+            runner
+                .snooze_schedule_for("first".to_string(), 60_000)
+                .await,
+            StCommandOutcome::NotFound
+        );
+
+        runner.add_schedule(hourly_schedule("first", &clock)).await;
+        next_due(&mut events).await;
+
+        assert_eq!(
+            runner.acknowledge_schedule("first".to_string()).await,
+            StCommandOutcome::Acknowledged
+        );
+        assert_eq!(
+            runner.acknowledge_schedule("first".to_string()).await,
+            StCommandOutcome::NotFound
+        );
+        assert_eq!(
+            runner
+                .snooze_schedule_for("first".to_string(), 60_000)
+                .await,
+            StCommandOutcome::Snoozed
+        );
+        let until = TIMESTAMP_MILLIS as u64 + 120_000;
+        assert_eq!(
+            runner
+                .snooze_schedule_until("first".to_string(), until)
+                .await,
+            StCommandOutcome::Snoozed
+        );
+        assert_eq!(
+            runner
+                .snooze_schedule_until("second".to_string(), until)
+                .await,
+            StCommandOutcome::NotFound
+        );
+
+        runner.stop().unwrap();
+        assert_eq!(
+            runner.acknowledge_schedule("first".to_string()).await,
+            StCommandOutcome::Unavailable
+        );
+    });
+}
+
+/// Should fire nothing while suspended, catch up once resumed, and reject invalid transitions
+#[test]
+pub fn pass_suspend_resume_and_stop_runner() {
+    task::block_on(async {
+        let clock = ManualClock::new(TIMESTAMP_MILLIS - 60_000);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+        scheduler.add_schedule(hourly_schedule("first", &clock));
+        let mut events = scheduler.events();
+
+        let runner = StSchedulerRunner::new();
+        assert_eq!(runner.get_state(), StRunnerState::Idle);
+        assert_eq!(
+            runner.resume(),
+            Err(RunnerError::InvalidTransition(
+                StRunnerState::Idle,
+                StRunnerState::Running
+            ))
+        );
+
+        runner.run(scheduler).await.unwrap();
+        assert!(runner.run(StScheduler::new()).await.is_err());
+
+        runner.suspend().unwrap();
+        assert!(runner.suspend().is_err());
+        assert_eq!(runner.get_state(), StRunnerState::Suspended);
+
+        // The schedule falls due while suspended, and commands are still carried out
+        clock.set(TIMESTAMP_MILLIS);
+        assert_eq!(runner.queue_size().await, 1);
+        task::sleep(Duration::from_millis(10)).await;
+        assert!(events.try_recv().is_err());
+
+        runner.resume().unwrap();
+        match events.next().await {
+            Some(SchedulerEvent::Due(due)) => assert_eq!(due.planned_at.as_ms(), TIMESTAMP_MILLIS),
+            other => panic!("Expected a due event, got {:?}", other),
+        }
+
+        runner.stop().unwrap();
+        assert_eq!(runner.get_state(), StRunnerState::Stopped);
+        assert!(runner.stop().is_err());
+        assert_eq!(
+            runner.remove_from_scheduler("first".to_string()).await,
+            StCommandOutcome::Unavailable
+        );
+    });
+}
+
+/// Should restore a scheduler from the JSON snapshot of a running one without firing its occurrences again
+#[test]
+pub fn pass_snapshot_and_restore_running_scheduler() {
+    task::block_on(async {
+        let clock = ManualClock::new(TIMESTAMP_MILLIS);
+        let mut scheduler = StScheduler::with_clock(Box::new(clock.clone()));
+        scheduler.add_schedule(hourly_schedule("first", &clock));
+        let mut events = scheduler.events();
+
+        let runner = StSchedulerRunner::new();
+        runner.run(scheduler).await.unwrap();
+
+        match events.next().await {
+            Some(SchedulerEvent::Due(due)) => assert_eq!(due.planned_at.as_ms(), TIMESTAMP_MILLIS),
+            other => panic!("Expected a due event, got {:?}", other),
+        }
+
+        let json = runner.snapshot().await.unwrap().to_json().unwrap();
+        runner.stop().unwrap();
+        assert_eq!(runner.snapshot().await, None);
+
+        let mut restored = StScheduler::with_clock(Box::new(clock.clone()));
+        restored
+            .restore(StSchedulerSnapshot::from_json(&json).unwrap())
+            .unwrap();
+        let mut events = restored.events();
+
+        let runner = StSchedulerRunner::new();
+        runner.run(restored).await.unwrap();
+        clock.set(TIMESTAMP_MILLIS + 3_600_000);
+        assert_eq!(runner.queue_size().await, 1);
+
+        let due = events
+            .find_map(|event| match event {
+                SchedulerEvent::Due(due) => Some(due),
+                _ => None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(due.planned_at.as_ms(), TIMESTAMP_MILLIS + 3_600_000);
+
+        runner.stop().unwrap();
+    });
+}
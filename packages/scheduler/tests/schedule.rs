@@ -1,13 +1,14 @@
 //! Test suite for the Web and headless browsers.
 
-use std::{cmp::max, vec};
+use std::vec;
 
-use chrono::{DateTime, Timelike, Utc};
+use chrono::{DateTime, Timelike};
 // extern crate wasm_bindgen_test;
 use wasm_bindgen_test::*;
 
 extern crate scheduler;
 use scheduler::core::{
+    clock::ManualClock,
     frequency::{StCustomFrequency, StFrequencyType, StHourlyExpression, StRegularFrequency},
     priority::StPriority,
     schedule::*,
@@ -18,7 +19,7 @@ wasm_bindgen_test_configure!(run_in_browser);
 
 static ISO_DATE_STRING: &str = "2024-10-28T21:05:55.025";
 static TIMEZONE: &str = "Africa/Lagos";
-static TIMESTAMP_MILLIS: i64 = 1730149555025;
+static TIMESTAMP_MILLIS: i64 = 1730145955025;
 
 /// Should sucessfilly create a bare minimum schedule with the given data
 #[wasm_bindgen_test]
@@ -81,32 +82,111 @@ pub fn pass_get_upcoming_cron_schedule() {
         .map(|v| v.to_string())
         .collect();
     let cstm_freq = StCustomFrequency::new(expr, None);
+    let clock = ManualClock::new(TIMESTAMP_MILLIS);
 
-    let schedule = StSchedule::with_custom(
+    let schedule = StSchedule::with_custom_and_clock(
         "id",
         ISO_DATE_STRING,
         TIMEZONE,
         cstm_freq.clone(),
         Some(StPriority::High),
+        &clock,
     );
 
-    println!("Is Passed -> {:#}", schedule);
+    // The anchor is not yet passed, so it is the first deadline
+    assert_eq!(schedule.get_deadline_millis(), TIMESTAMP_MILLIS);
+    assert!(schedule.get_upcoming_schedule_with(&clock).is_err());
 
-    let anchor_dt = DateTime::from_timestamp_millis(schedule.get_anchor_millis()).unwrap();
-    let upcoming_schedule = schedule.get_upcoming_schedule().unwrap();
+    // Step through the upcoming schedules: 20:06, 20:08, 20:10, 20:12 UTC
+    let expected = [(20, 6), (20, 8), (20, 10), (20, 12)];
+    let mut schedule = schedule;
 
-    assert_ne!(schedule, upcoming_schedule);
+    for (hour, minute) in expected.iter() {
+        clock.set(schedule.get_deadline_millis() + 1);
 
-    // Ensure the greatest of `time_t` and `now` is used
-    let after_time_t = max(Utc::now(), anchor_dt);
-    let upcoming_time_t =
-        DateTime::from_timestamp_millis(upcoming_schedule.get_anchor_millis()).unwrap();
+        let upcoming_schedule = schedule.get_upcoming_schedule_with(&clock).unwrap();
+        let upcoming_time_t =
+            DateTime::from_timestamp_millis(upcoming_schedule.get_deadline_millis()).unwrap();
 
-    if after_time_t.minute() <= 50 {
-        assert_eq!(after_time_t.hour(), upcoming_time_t.hour());
-        assert_eq!(upcoming_time_t.minute() % 10, 0);
-    } else {
-        assert_eq!(upcoming_time_t.hour(), (after_time_t.hour() + 1) % 24);
-        assert_eq!(upcoming_time_t.minute(), 0);
+        assert_ne!(schedule, upcoming_schedule);
+        assert_eq!(upcoming_schedule.get_anchor_millis(), TIMESTAMP_MILLIS);
+        assert_eq!(upcoming_time_t.hour(), *hour);
+        assert_eq!(upcoming_time_t.minute(), *minute);
+        assert_eq!(upcoming_time_t.second(), 0);
+
+        schedule = upcoming_schedule;
+    }
+}
+
+/// Should serialize a schedule with its timezone name and naive anchor, and deserialize it back as it was
+#[cfg(feature = "serde")]
+#[test]
+pub fn pass_serialize_and_deserialize_schedule() {
+    use scheduler::core::frequency::StWeeklyExpression;
+    use serde_json::json;
+
+    let clock = ManualClock::new(TIMESTAMP_MILLIS);
+    let expr = StWeeklyExpression::with_weekdays(2, vec![1, 3]);
+    let reg_freq = StRegularFrequency::with_weekly_expr(StFrequencyType::Week, expr, None);
+    let mut schedule = StSchedule::with_regular_and_clock(
+        "id",
+        ISO_DATE_STRING,
+        TIMEZONE,
+        reg_freq,
+        Some(StPriority::High),
+        &clock,
+    );
+    schedule.set_reminders(vec![600_000]);
+
+    let value = serde_json::to_value(&schedule).unwrap();
+    let expected = json!({
+        "id": "id",
+        "timing": {
+            "timezone": TIMEZONE,
+            "anchor": TIMESTAMP_MILLIS,
+            "deadline": TIMESTAMP_MILLIS,
+            "naive_anchor": ISO_DATE_STRING,
+            "index": 0,
+            "extra": false,
+            "rolled_from": null,
+        },
+        "priority": "High",
+        "frequency": {
+            "kind": "regular",
+            "ftype": "Week",
+            "until": null,
+            "count": null,
+            "expr": { "kind": "weekly", "every": 2, "subexpr": { "weekdays": ["Mon", "Wed"] } },
+        },
+        "catch_up": null,
+        "reminders": [600_000],
+        "grace_period": null,
+        "dates": { "excluded": [], "included": [] },
+        "business_days": null,
+    });
+    assert_eq!(value, expected);
+
+    let deserialized: StSchedule = serde_json::from_value(value).unwrap();
+    assert_eq!(deserialized, schedule);
+
+    // The fields added to schedules over time can be left out
+    let mut minimal = expected.clone();
+    for key in [
+        "catch_up",
+        "reminders",
+        "grace_period",
+        "dates",
+        "business_days",
+    ] {
+        minimal.as_object_mut().unwrap().remove(key);
+    }
+    for key in ["index", "extra", "rolled_from"] {
+        minimal["timing"].as_object_mut().unwrap().remove(key);
     }
+    minimal["frequency"]
+        .as_object_mut()
+        .unwrap()
+        .remove("count");
+    let deserialized: StSchedule = serde_json::from_value(minimal).unwrap();
+    assert_eq!(deserialized.get_reminders(), Vec::<u32>::new());
 }
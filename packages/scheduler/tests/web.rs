@@ -1,8 +1,14 @@
 //! Test suite for the Web and headless browsers.
 
-#![cfg(target_arch = "wasm32")]
+#![cfg(all(target_arch = "wasm32", feature = "serde"))]
 
 extern crate wasm_bindgen_test;
+use js_sys::Reflect;
+use scheduler::core::errors::TaskScheduleError;
+use scheduler::core::schedule::StSchedule;
+use scheduler::core::task::JsTaskSchedule;
+use scheduler::core::time::Timestamp;
+use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_test::*;
 
 wasm_bindgen_test_configure!(run_in_browser);
@@ -12,6 +18,108 @@ fn pass() {
     assert_eq!(1 + 1, 2);
 }
 
+fn task_schedule(json: &str) -> JsTaskSchedule {
+    js_sys::JSON::parse(json).unwrap().unchecked_into()
+}
+
+#[wasm_bindgen_test]
+fn pass_create_schedule_from_task_schedule() {
+    let value = task_schedule(
+        r#"{
+            "id": "a1",
+            "taskId": "t1",
+            "timing": { "naive": "2099-03-02T09:00:00", "tzone": "Africa/Lagos" },
+            "frequency": {
+                "type": "month",
+                "exprs": { "every": 1, "subexpr": { "type": "onthe", "ordinal": "last", "weekday": 1 } }
+            },
+            "reminders": [600000, 60000]
+        }"#,
+    );
+    let schedule = StSchedule::from_js(value).unwrap();
+
+    assert!(schedule.get_regular_frequency().is_some());
+    assert_eq!(schedule.get_reminders(), vec![600_000, 60_000]);
+}
+
+#[wasm_bindgen_test]
+fn pass_apply_until_date_of_task_schedule() {
+    let value = task_schedule(
+        r#"{
+            "id": "a1",
+            "timing": { "naive": "2099-03-02T09:00:00", "tzone": "UTC" },
+            "frequency": { "type": "day", "exprs": { "every": 2 } }
+        }"#,
+    );
+    let until = js_sys::Date::new(&JsValue::from_f64(4_102_444_800_000.0));
+    let frequency = Reflect::get(&value, &"frequency".into()).unwrap();
+    Reflect::set(&frequency, &"until".into(), &until).unwrap();
+
+    let schedule = StSchedule::from_js(value).unwrap();
+
+    assert_eq!(
+        schedule.get_until(),
+        Some(Timestamp::Millis(4_102_444_800_000))
+    );
+}
+
+#[wasm_bindgen_test]
+fn pass_apply_count_of_task_schedule() {
+    let value = task_schedule(
+        r#"{
+            "id": "a1",
+            "timing": { "naive": "2099-03-02T09:00:00", "tzone": "UTC" },
+            "frequency": { "type": "custom", "crons": [{ "expression": "0 9 * * *", "frequency": "day" }], "count": 10 }
+        }"#,
+    );
+    let schedule = StSchedule::from_js(value).unwrap();
+
+    assert_eq!(schedule.get_count(), Some(10));
+}
+
+#[wasm_bindgen_test]
+fn pass_skip_excluded_dates_of_task_schedule() {
+    let value = task_schedule(
+        r#"{
+            "id": "a1",
+            "timing": { "naive": "2099-03-02T09:00:00", "tzone": "UTC" },
+            "frequency": { "type": "day", "exprs": { "every": 1 } }
+        }"#,
+    );
+    let first = js_sys::Date::new(&JsValue::from_f64(4_076_125_200_000.0));
+    let exclusions = js_sys::Array::of1(&first);
+    Reflect::set(&value, &"exclusions".into(), &exclusions).unwrap();
+
+    let schedule = StSchedule::from_js(value).unwrap();
+
+    assert_eq!(schedule.get_exclusions(), vec![4_076_125_200_000]);
+    assert_eq!(schedule.get_deadline_millis(), 4_076_211_600_000);
+}
+
+#[wasm_bindgen_test]
+fn fail_name_invalid_field_of_task_schedule() {
+    let value = task_schedule(
+        r#"{
+            "id": "a1",
+            "timing": { "naive": "2099-03-02T09:00:00", "tzone": "UTC" },
+            "frequency": { "type": "week", "exprs": { "every": 1, "subexpr": { "weekdays": [1, 7] } } }
+        }"#,
+    );
+    let error = StSchedule::from_js(value).unwrap_err();
+
+    assert!(matches!(
+        error,
+        TaskScheduleError::InvalidValue(path, _) if path == "schedule.frequency.exprs.subexpr.weekdays[1]"
+    ));
+}
+
+#[wasm_bindgen_test]
+fn fail_require_timing_of_task_schedule() {
+    let value = task_schedule(r#"{ "id": "a1", "frequency": { "type": "never" } }"#);
+    let error = StSchedule::from_js(value).unwrap_err();
+
+    assert_eq!(error.to_string(), "'schedule.timing' is required");
+}
 
 // &.collapsed {
 //     & .s-subtask-listitem {
@@ -55,4 +163,4 @@ fn pass() {
 //         /* filter: saturate(calc(10% * var(--s-index) + 30%)); */
 //       }
 //     }
-//   }
\ No newline at end of file
+//   }
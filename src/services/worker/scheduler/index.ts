@@ -9,9 +9,14 @@ export interface SchedulerController {
   add(schedule: TaskSchedule[]): void
   update(schedule: TaskSchedule): void
   remove(id: string): void
+  /** Asks for the scheduler's state, which is posted to the observer as a `snapshot` trigger */
+  snapshot(): void
   observer: Observable<MasterMessageEventData>
 }
 
+/** Where the scheduler's state is persisted between sessions */
+const SNAPSHOT_STORAGE_KEY = 'stitches:scheduler:snapshot'
+
 function msgFactory(msg: WorkerMessageEventData) {
   return msg
 }
@@ -31,13 +36,20 @@ export function SchedulerWorker(): SchedulerController {
     },
     remove(id) {
       worker.postMessage(msgFactory({ command: 'drop', data: id }))
-    }
+    },
+    snapshot() {
+      worker.postMessage(msgFactory({ command: 'snapshot' }))
+    },
   }
 
   const ob = new Observable<MasterMessageEventData>((subscriber) => {
+    const snapshot = localStorage.getItem(SNAPSHOT_STORAGE_KEY)
+
     worker.postMessage(msgFactory({ command: 'subscribe' }))
+    if (snapshot) worker.postMessage(msgFactory({ command: 'restore', data: snapshot }))
     worker.postMessage(msgFactory({ command: 'run' }))
     worker.addEventListener('message', (ev: MessageEvent<MasterMessageEventData>) => {
+      if (ev.data.trigger === 'snapshot') localStorage.setItem(SNAPSHOT_STORAGE_KEY, ev.data.data)
       subscriber.next(ev.data)
     })
 
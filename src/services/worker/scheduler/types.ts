@@ -1,11 +1,25 @@
 import type { TaskSchedule } from '@stitches/common'
+import type {
+  StDueEvent,
+  StExpiredEvent,
+  StMissedEvent,
+  StOverdueEvent,
+  StReminderEvent,
+  StUpcomingEvent,
+} from '@stitches/scheduler'
 
-export type SchedulerWorkerActions = 'abort' | 'run' | 'subscribe'
+export type SchedulerWorkerActions = 'abort' | 'run' | 'subscribe' | 'snapshot'
 export type SchedulerWorkerCommamds =
   | 'add'
   | 'drop'
   | 'drop_all'
   | 'update'
+  | 'upsert'
+  | 'pause'
+  | 'resume'
+  | 'snooze'
+  | 'acknowledge'
+  | 'restore'
   | SchedulerWorkerActions
 
 export interface SchedulerWorkerMsgEvtAction<C extends SchedulerWorkerCommamds> {
@@ -20,8 +34,14 @@ export interface SchedulerWorkerMsgEvtData<C extends SchedulerWorkerCommamds, D>
 export type TaskSchedulerWorkerMsgEvtData =
   | SchedulerWorkerMsgEvtData<'add', TaskSchedule | TaskSchedule[]>
   | SchedulerWorkerMsgEvtData<'update', TaskSchedule>
+  | SchedulerWorkerMsgEvtData<'upsert', TaskSchedule>
   | SchedulerWorkerMsgEvtData<'drop', string | string[]>
   | SchedulerWorkerMsgEvtData<'drop_all', undefined>
+  | SchedulerWorkerMsgEvtData<'pause', { id: string; until?: Date }>
+  | SchedulerWorkerMsgEvtData<'resume', { id: string }>
+  | SchedulerWorkerMsgEvtData<'snooze', { id: string; for: number } | { id: string; until: Date }>
+  | SchedulerWorkerMsgEvtData<'acknowledge', { id: string }>
+  | SchedulerWorkerMsgEvtData<'restore', string>
 
 export type TaskSchedulerWorkerMsgEvtActions = SchedulerWorkerMsgEvtAction<SchedulerWorkerActions>
 
@@ -32,12 +52,33 @@ export type WorkerMessageEventData =
 /////////////////////////////////////////////////////////////////////////////////////////
 // TRIGGERS
 /////////////////////////////////////////////////////////////////////////////////////////
-export type SchedulerMasterTriggers = 'due'
+export type SchedulerMasterTriggers =
+  | 'upcoming'
+  | 'due'
+  | 'missed'
+  | 'reminder'
+  | 'overdue'
+  | 'expired'
+  | 'snapshot'
 
 export interface SchedulerWorkerMsgEvtTrigger<T extends SchedulerMasterTriggers, D> {
   trigger: T
   data: D
 }
-export type TaskSchedulerDueTrigger = SchedulerWorkerMsgEvtTrigger<'due', { id: string }>
+export type TaskSchedulerDueTrigger = SchedulerWorkerMsgEvtTrigger<'due', StDueEvent>
+export type TaskSchedulerMissedTrigger = SchedulerWorkerMsgEvtTrigger<'missed', StMissedEvent>
+export type TaskSchedulerReminderTrigger = SchedulerWorkerMsgEvtTrigger<'reminder', StReminderEvent>
+export type TaskSchedulerUpcomingTrigger = SchedulerWorkerMsgEvtTrigger<'upcoming', StUpcomingEvent>
+export type TaskSchedulerOverdueTrigger = SchedulerWorkerMsgEvtTrigger<'overdue', StOverdueEvent>
+export type TaskSchedulerExpiredTrigger = SchedulerWorkerMsgEvtTrigger<'expired', StExpiredEvent>
+/** The scheduler's state written as JSON, posted whenever it changes so it can be persisted and restored */
+export type TaskSchedulerSnapshotTrigger = SchedulerWorkerMsgEvtTrigger<'snapshot', string>
 
-export type MasterMessageEventData = TaskSchedulerDueTrigger
+export type MasterMessageEventData =
+  | TaskSchedulerDueTrigger
+  | TaskSchedulerMissedTrigger
+  | TaskSchedulerReminderTrigger
+  | TaskSchedulerUpcomingTrigger
+  | TaskSchedulerOverdueTrigger
+  | TaskSchedulerExpiredTrigger
+  | TaskSchedulerSnapshotTrigger
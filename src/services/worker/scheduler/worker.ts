@@ -1,151 +1,107 @@
 /// <reference lib="webworker" />
 import * as sch from '@stitches/scheduler'
-import type { WeekdayVariable } from '@stitches/common'
-import type { TaskSchedule } from '@stitches/common'
-import { never, ordinalToU32 } from '@stitches/common'
-
-import type { MasterMessageEventData, WorkerMessageEventData } from './types'
-
-const st_var_weekday_map = {
-  day: sch.StVarWeekday.Day,
-  weekday: sch.StVarWeekday.Weekday,
-  'weekend-day': sch.StVarWeekday.Weekend,
-  get(key: WeekdayVariable) {
-    return this[key]
-  },
-} as const satisfies Record<WeekdayVariable, sch.StVarWeekday> & {
-  get(key: WeekdayVariable): sch.StVarWeekday
-}
-
-/**
- * Create an {@link sch.StSchedule} from the given data
- *
- * @param data The data to use to create a scheduler
- * @throws {Error}
- * @returns The created schedule
- */
-function create_st_schedule(data: TaskSchedule) {
-  if (!data.timing) throw new Error('`timing` is required to create schedule')
-
-  const until =
-    data.frequency.type !== 'never' && data.frequency.until
-      ? BigInt(data.frequency.until.getTime())
-      : undefined
-
-  switch (data.frequency.type) {
-    case 'never': {
-      return new sch.StSchedule(data.id, data.timing.naive, data.timing.tzone)
-    }
-
-    case 'custom': {
-      const exprs = data.frequency.crons.map((c) => c.expression)
-      const freq = new sch.StCustomFrequency(exprs, until)
-
-      return sch.StSchedule.with_custom(data.id, data.timing.naive, data.timing.tzone, freq)
-    }
-
-    case 'hour': {
-      const expr = new sch.StHourlyExpression(data.frequency.exprs.every)
-      const freq = new sch.StRegularFrequency(sch.StFrequencyType.Hour, expr, until)
-
-      return sch.StSchedule.with_regular(data.id, data.timing.naive, data.timing.tzone, freq)
-    }
-
-    case 'day': {
-      const expr = new sch.StDailyExpression(data.frequency.exprs.every)
-      const freq = sch.StRegularFrequency.with_daily_expr(sch.StFrequencyType.Day, expr, until)
-
-      return sch.StSchedule.with_regular(data.id, data.timing.naive, data.timing.tzone, freq)
-    }
-
-    case 'week': {
-      const weekdays = new Uint32Array(data.frequency.exprs.subexpr.weekdays)
-      const expr = sch.StWeeklyExpression.with_weekdays(data.frequency.exprs.every, weekdays)
-      const freq = sch.StRegularFrequency.with_weekly_expr(sch.StFrequencyType.Week, expr, until)
-
-      return sch.StSchedule.with_regular(data.id, data.timing.naive, data.timing.tzone, freq)
-    }
-
-    case 'month': {
-      let expr: sch.StMonthlyExpression
-
-      if (data.frequency.exprs.subexpr.type === 'ondays') {
-        const days = new Uint32Array(data.frequency.exprs.subexpr.days)
-        expr = sch.StMonthlyExpression.with_days(data.frequency.exprs.every, days)
-      } else {
-        expr = sch.StMonthlyExpression.with_ordinal_weekday(
-          data.frequency.exprs.every,
-          sch.st_ordinals_from_value(ordinalToU32(data.frequency.exprs.subexpr.ordinal)),
-          sch.st_const_weekday_from_value(data.frequency.exprs.subexpr.weekday),
-        )
-      }
-
-      const freq = sch.StRegularFrequency.with_monthly_expr(sch.StFrequencyType.Month, expr, until)
-      return sch.StSchedule.with_regular(data.id, data.timing.naive, data.timing.tzone, freq)
-    }
-
-    case 'year': {
-      let expr: sch.StYearlyExpression
-      const months = new Uint32Array(data.frequency.exprs.subexpr.in.months)
-
-      if (data.frequency.exprs.subexpr.on && data.frequency.exprs.subexpr.on.weekday) {
-        expr = sch.StYearlyExpression.with_months_ordinal_const_weekday(
-          data.frequency.exprs.every,
-          months,
-          sch.st_ordinals_from_value(ordinalToU32(data.frequency.exprs.subexpr.on.ordinal)),
-          sch.st_const_weekday_from_value(data.frequency.exprs.subexpr.on.weekday),
-        )
-      } else if (data.frequency.exprs.subexpr.on && data.frequency.exprs.subexpr.on.variable) {
-        expr = sch.StYearlyExpression.with_months_ordinal_var_weekday(
-          data.frequency.exprs.every,
-          months,
-          sch.st_ordinals_from_value(ordinalToU32(data.frequency.exprs.subexpr.on.ordinal)),
-          st_var_weekday_map.get(data.frequency.exprs.subexpr.on.variable),
-        )
-      } else {
-        expr = sch.StYearlyExpression.with_months(data.frequency.exprs.every, months)
-      }
-
-      const freq = sch.StRegularFrequency.with_yearly_expr(sch.StFrequencyType.Year, expr, until)
-
-      return sch.StSchedule.with_regular(data.id, data.timing.naive, data.timing.tzone, freq)
-    }
-
+import { never } from '@stitches/common'
+
+import type {
+  MasterMessageEventData,
+  SchedulerWorkerCommamds,
+  WorkerMessageEventData,
+} from './types'
+
+/** The commands that change the scheduler's state, after which it is snapshotted */
+const STATEFUL_COMMANDS = new Set<SchedulerWorkerCommamds>([
+  'add',
+  'drop',
+  'drop_all',
+  'update',
+  'upsert',
+  'pause',
+  'resume',
+  'snooze',
+  'acknowledge',
+])
+
+/** The events that change the scheduler's persisted state, after which it is snapshotted */
+const STATEFUL_EVENTS = new Set<sch.StSchedulerEvent['kind']>(['due', 'missed', 'expired'])
+
+function subDataFactory(event: sch.StSchedulerEvent): MasterMessageEventData {
+  switch (event.kind) {
+    case 'upcoming':
+      return { trigger: 'upcoming', data: event }
+    case 'due':
+      return { trigger: 'due', data: event }
+    case 'missed':
+      return { trigger: 'missed', data: event }
+    case 'reminder':
+      return { trigger: 'reminder', data: event }
+    case 'overdue':
+      return { trigger: 'overdue', data: event }
+    case 'expired':
+      return { trigger: 'expired', data: event }
     default:
-      never(data.frequency)
+      never(event)
   }
 }
 
-function subDataFactory(id: string): MasterMessageEventData {
-  return { trigger: 'due', data: { id } }
-}
-
 async function main() {
   const runner = sch.get_scheduler_runner()
   const scheduler = sch.get_scheduler()
 
+  /** Posts the scheduler's state so the master can persist it, unless the runner is not running it */
+  async function snapshot() {
+    const snapshot = await runner.snapshot()
+    if (!snapshot) return
+
+    const data: MasterMessageEventData = { trigger: 'snapshot', data: snapshot.to_json() }
+    self.postMessage(data)
+  }
+
   self.addEventListener('message', async (msg: MessageEvent<WorkerMessageEventData>) => {
     switch (msg.data.command) {
       case 'add': {
         const data = Array.isArray(msg.data.data) ? msg.data.data : [msg.data.data]
-        data
-          .filter((v) => !!v.timing)
-          .map((v) => create_st_schedule(v))
-          .forEach((v) => scheduler.add_schedule(v))
+        await Promise.all(
+          data
+            .filter((v) => !!v.timing)
+            .map((v) => runner.add_schedule(sch.StSchedule.from_js(v))),
+        )
         break
       }
 
       case 'subscribe':
-        scheduler.subscribe((id: string) => self.postMessage(subDataFactory(id)))
+        scheduler.subscribe((event: sch.StSchedulerEvent) => {
+          self.postMessage(subDataFactory(event))
+          if (STATEFUL_EVENTS.has(event.kind)) void snapshot()
+        })
+        break
+
+      case 'restore':
+        // The scheduler can only be restored before it is run, after which the runner holds it
+        try {
+          scheduler.restore(sch.StSchedulerSnapshot.from_json(msg.data.data))
+        } catch (error) {
+          console.error('Failed to restore the scheduler:', error)
+        }
+        break
+
+      case 'snapshot':
+        await snapshot()
         break
 
       case 'run':
+        // A runner only ever runs one scheduler, and cannot be run again once stopped
+        if (runner.get_state() !== sch.StRunnerState.Idle) break
         await runner.run(scheduler)
         break
 
       case 'update':
         if (msg.data.data.timing === null) break
-        await runner.update_scheduler_with(create_st_schedule(msg.data.data))
+        await runner.update_scheduler_with(sch.StSchedule.from_js(msg.data.data))
+        break
+
+      case 'upsert':
+        if (msg.data.data.timing === null) break
+        await runner.upsert_schedule(sch.StSchedule.from_js(msg.data.data))
         break
 
       case 'drop': {
@@ -155,15 +111,40 @@ async function main() {
       }
 
       case 'drop_all':
+        await runner.drop_all_schedules()
+        break
+
+      case 'pause': {
+        const until = msg.data.data.until ? BigInt(msg.data.data.until.getTime()) : undefined
+        await runner.pause_schedule(msg.data.data.id, until)
+        break
+      }
+
+      case 'resume':
+        await runner.resume_schedule(msg.data.data.id)
+        break
+
+      case 'snooze':
+        if ('for' in msg.data.data) {
+          await runner.snooze_schedule_for(msg.data.data.id, msg.data.data.for)
+        } else {
+          await runner.snooze_schedule_until(msg.data.data.id, BigInt(msg.data.data.until.getTime()))
+        }
+        break
+
+      case 'acknowledge':
+        await runner.acknowledge_schedule(msg.data.data.id)
         break
 
       case 'abort':
-        await runner.quit()
+        if (runner.get_state() !== sch.StRunnerState.Stopped) runner.stop()
         break
 
       default:
       // no default
     }
+
+    if (STATEFUL_COMMANDS.has(msg.data.command)) await snapshot()
   })
 }
 